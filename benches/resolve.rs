@@ -0,0 +1,90 @@
+//! Resolution benchmarks, timed with a minimal harness so the crate needs no
+//! benchmarking dependency. Run with `cargo bench`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use desynt::{DynamicPathResolver, PathResolver, PrimitivePathResolver, TypeGroups};
+use phf::{Map, phf_map};
+use syn::{Path, parse_str};
+
+static MAPPINGS: Map<&'static str, &'static str> = phf_map! {
+    "actix_web::HttpResponse" => "HttpResponse",
+    "serde_json::Value" => "JsonValue",
+    "my_crate::types::UserId" => "UserId",
+    "chrono::DateTime" => "DateTime",
+};
+
+const PATHS: &[(&str, &str)] = &[
+    ("builtin_exact", "std::collections::HashMap"),
+    ("builtin_bare", "Option<String>"),
+    ("builtin_shortened", "option::Option"),
+    ("primitive", "u32"),
+    ("custom_exact", "crate_7::module_3::Type42"),
+    ("custom_suffix", "Type42"),
+    ("miss_bare", "MyStruct"),
+    ("miss_path", "my_mod::inner::MyStruct<u8>"),
+];
+
+/// A resolver with as many custom mappings as a generated rustdoc table.
+fn large_resolver() -> DynamicPathResolver {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    for krate in 0..10 {
+        for module in 0..10 {
+            for item in 0..100 {
+                resolver.add_mapping(
+                    format!("crate_{krate}::module_{module}::Type{item}"),
+                    format!("Type{item}"),
+                );
+            }
+        }
+    }
+    resolver
+}
+
+/// Time a closure over enough iterations to fill `TARGET`, and print the mean.
+fn bench<T>(name: &str, mut f: impl FnMut() -> T) {
+    const TARGET: Duration = Duration::from_millis(200);
+
+    // Warm up, and estimate how many iterations fill the target
+    let start = Instant::now();
+    let mut warmup = 0u32;
+    while start.elapsed() < TARGET / 10 {
+        black_box(f());
+        warmup += 1;
+    }
+    let iterations = warmup.saturating_mul(10).max(1);
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(f());
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{name:<40} {:>10.1} ns/iter ({iterations} iterations)",
+        elapsed.as_nanos() as f64 / f64::from(iterations)
+    );
+}
+
+fn main() {
+    let paths: Vec<(&str, Path)> = PATHS
+        .iter()
+        .map(|(name, path)| (*name, parse_str(path).unwrap()))
+        .collect();
+
+    let dynamic = large_resolver();
+    let primitive = PrimitivePathResolver::new(desynt::EmptyStorage, TypeGroups::ALL);
+    let static_resolver = PathResolver::new(&MAPPINGS, TypeGroups::ALL);
+
+    for (name, path) in &paths {
+        bench(&format!("resolve/dynamic/{name}"), || {
+            dynamic.resolve(black_box(path))
+        });
+        bench(&format!("resolve/builtin/{name}"), || {
+            primitive.resolve(black_box(path))
+        });
+        bench(&format!("resolve/static/{name}"), || {
+            static_resolver.resolve(black_box(path))
+        });
+    }
+}
@@ -1,4 +1,6 @@
-use desynt::{DynamicPathResolver, PathResolver, TypeGroups, create_static_resolver};
+use desynt::{
+    DynamicPathResolver, LayeredResolver, PathResolver, TypeGroups, create_static_resolver,
+};
 use phf::{Map, phf_map};
 
 // Domain-specific type mappings for web applications
@@ -82,17 +84,22 @@ fn main() {
     println!("\n3. Combined Dynamic Resolver:");
     test_combined_resolver();
 
+    // Test layered resolver over both domains
+    println!("\n4. Layered Resolver:");
+    test_layered_resolver();
+
     // Test domain-specific path resolution
-    println!("\n4. Domain-Specific Path Analysis:");
+    println!("\n5. Domain-Specific Path Analysis:");
     analyze_domain_paths();
 
-    println!("\n5. Performance Comparison:");
+    println!("\n6. Performance Comparison:");
     performance_comparison();
 
     println!("\n=== Summary ===");
     println!("✓ Domain-specific resolvers for specialized type mapping");
     println!("✓ Static PHF maps for compile-time domain knowledge");
     println!("✓ Combined resolvers for full-stack applications");
+    println!("✓ Layered resolvers overriding shared static tables");
     println!("✓ Zero-cost abstractions with const initialization");
     println!("✓ Type safety across domain boundaries");
 }
@@ -184,6 +191,41 @@ fn test_combined_resolver() {
     }
 }
 
+fn test_layered_resolver() {
+    // Project overrides on top of both shared domain tables
+    let mut overrides = DynamicPathResolver::default();
+    overrides.add_mapping("sqlx::Pool", "PrimaryDbPool");
+    overrides.add_mapping("my_app::models::User", "UserModel");
+
+    let layered = LayeredResolver::new()
+        .with_layer("overrides", overrides)
+        .with_layer("web", WEB_RESOLVER)
+        .with_layer("database", DATABASE_RESOLVER);
+
+    let test_paths = [
+        "sqlx::Pool",             // overridden
+        "sqlx::Transaction",      // database domain
+        "actix_web::HttpRequest", // web domain
+        "my_app::models::User",   // app-specific
+        "std::vec::Vec",          // type group of the web layer
+        "unknown::Type",          // unmapped
+    ];
+
+    println!("Testing Layered resolver:");
+    println!("  Layers: {:?}", layered.layer_names().collect::<Vec<_>>());
+
+    for path_str in &test_paths {
+        let path: syn::Path = syn::parse_str(path_str).unwrap();
+        match layered.resolve_detailed(&path) {
+            Some(found) => println!(
+                "  {} -> {} (from {})",
+                path_str, found.resolution.canonical, found.layer
+            ),
+            None => println!("  {} -> (not found)", path_str),
+        }
+    }
+}
+
 fn analyze_domain_paths() {
     println!("Analyzing domain-specific path patterns:");
 
@@ -0,0 +1,464 @@
+//! Registering `type` alias items as mappings.
+
+use std::collections::HashMap;
+
+use syn::{File, GenericArgument, GenericParam, Item, ItemType, Path, PathArguments, Type};
+
+use crate::{
+    CanonicalType, DynamicPathResolver, DynamicStorage, MappingStorage, PathResolver,
+    ResolutionPolicy, Scope, StripRaw, TypeGroups,
+};
+
+/// A `type` alias waiting to be registered, with the scope it was declared in.
+struct PendingAlias<'a> {
+    item: &'a ItemType,
+    scope: &'a Scope,
+}
+
+/// A set of `type` alias definitions that can be expanded at their use sites.
+///
+/// Unlike [`DynamicPathResolver::add_type_aliases`], which maps an alias to the
+/// canonical name of its outermost type, this keeps each alias definition with
+/// its generic parameters. Resolving a use site substitutes the written
+/// arguments, or the parameter defaults, into the aliased type, so with
+/// `type MyResult<T> = std::result::Result<T, MyError>;` the type
+/// `MyResult<User>` resolves to `Result<User, MyError>`.
+///
+/// Aliases are looked up by their exact pattern, through the imports and
+/// module path of the use site scope, relative to the current module, or by
+/// their bare name. Parameters
+/// without an argument or default expand to [`CanonicalType::Infer`].
+/// An alias found again while it is being expanded is left unexpanded.
+///
+/// # Examples
+///
+/// ```
+/// use desynt::{CanonicalType, DynamicPathResolver, Scope, TypeAliases};
+/// use syn::{ItemType, Type};
+///
+/// let item: ItemType =
+///     syn::parse_str("type MyResult<T> = std::result::Result<T, MyError>;").unwrap();
+/// let mut aliases = TypeAliases::new();
+/// aliases.add(&item, &Scope::new());
+///
+/// let resolver = DynamicPathResolver::with_all_groups();
+/// let ty: Type = syn::parse_str("MyResult<String>").unwrap();
+/// let CanonicalType::Path(result) = aliases.resolve_type(&resolver, &ty) else {
+///     panic!("expected a path");
+/// };
+/// assert_eq!(result.name, "Result");
+///
+/// let CanonicalType::Path(error) = &result.args[1] else {
+///     panic!("expected a path");
+/// };
+/// assert_eq!(error.name, "MyError");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypeAliases {
+    /// Alias definitions by pattern, with the index of their declaring scope.
+    definitions: HashMap<String, (ItemType, usize)>,
+    /// The scopes the aliases were declared in.
+    scopes: Vec<Scope>,
+    /// Resolves use sites to alias patterns, mapping each pattern to itself.
+    lookup: DynamicPathResolver,
+}
+
+impl TypeAliases {
+    /// Create an empty set of alias definitions.
+    pub fn new() -> Self {
+        let mut lookup = DynamicPathResolver::new(DynamicStorage::new(), TypeGroups::NONE);
+        lookup.set_policy(ResolutionPolicy {
+            suffix: true,
+            ..ResolutionPolicy::EXACT
+        });
+        Self {
+            definitions: HashMap::new(),
+            scopes: Vec::new(),
+            lookup,
+        }
+    }
+
+    /// Build alias definitions from every `type` alias item of a file.
+    ///
+    /// `module_path` has the same meaning as for
+    /// [`DynamicPathResolver::add_type_aliases_from_file`], and an error is
+    /// returned if a segment of it is not an identifier.
+    pub fn from_file(file: &File, module_path: &str) -> syn::Result<Self> {
+        let mut modules = Vec::new();
+        let module_path = Some(module_path.to_string()).filter(|path| !path.is_empty());
+        collect_modules(&file.items, module_path, &mut modules)?;
+
+        let mut aliases = Self::new();
+        for (items, scope) in modules {
+            for item in items {
+                if let Item::Type(item) = item {
+                    aliases.add(item, &scope);
+                }
+            }
+        }
+        Ok(aliases)
+    }
+
+    /// Add an alias definition declared in `scope`.
+    ///
+    /// The alias is keyed like [`DynamicPathResolver::add_type_aliases`] keys it,
+    /// and replaces any earlier definition with the same pattern.
+    pub fn add(&mut self, item: &ItemType, scope: &Scope) {
+        if self.scopes.last() != Some(scope) {
+            self.scopes.push(scope.clone());
+        }
+        let pattern = alias_pattern(item, scope);
+        self.lookup.add_mapping(pattern.clone(), pattern.clone());
+        self.definitions
+            .insert(pattern, (item.clone(), self.scopes.len() - 1));
+    }
+
+    /// Return the alias definition registered under a pattern.
+    pub fn get(&self, pattern: &str) -> Option<&ItemType> {
+        self.definitions.get(pattern).map(|(item, _)| item)
+    }
+
+    /// Return the number of alias definitions.
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    /// Return `true` if there are no alias definitions.
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+
+    /// Resolve a syn [`Type`], expanding every alias it uses.
+    ///
+    /// Named types that are not aliases are resolved by `resolver`, as
+    /// [`PathResolver::resolve_type`] would.
+    pub fn resolve_type<M>(&self, resolver: &PathResolver<M>, ty: &Type) -> CanonicalType
+    where
+        M: MappingStorage,
+    {
+        self.resolve_type_in_scope(resolver, ty, &Scope::new())
+    }
+
+    /// Resolve a syn [`Type`] written in `scope`, expanding every alias it uses.
+    ///
+    /// See [`TypeAliases::resolve_type`] and [`PathResolver::resolve_type_in_scope`].
+    pub fn resolve_type_in_scope<M>(
+        &self,
+        resolver: &PathResolver<M>,
+        ty: &Type,
+        scope: &Scope,
+    ) -> CanonicalType
+    where
+        M: MappingStorage,
+    {
+        self.expand_type(resolver, ty, scope, &HashMap::new(), &mut Vec::new())
+    }
+
+    /// Resolve a type, substituting the bound generic parameters of `env`.
+    ///
+    /// `expanding` holds the aliases currently being expanded, to stop on cycles.
+    fn expand_type<M>(
+        &self,
+        resolver: &PathResolver<M>,
+        ty: &Type,
+        scope: &Scope,
+        env: &HashMap<String, CanonicalType>,
+        expanding: &mut Vec<String>,
+    ) -> CanonicalType
+    where
+        M: MappingStorage,
+    {
+        resolver.canonical_type(ty, Some(scope), &mut |path| {
+            self.expand_path(resolver, path, scope, env, expanding)
+        })
+    }
+
+    /// Expand a named type if it is a bound generic parameter or an alias.
+    fn expand_path<M>(
+        &self,
+        resolver: &PathResolver<M>,
+        path: &Path,
+        scope: &Scope,
+        env: &HashMap<String, CanonicalType>,
+        expanding: &mut Vec<String>,
+    ) -> Option<CanonicalType>
+    where
+        M: MappingStorage,
+    {
+        if let (None, Some(segment)) = (path.leading_colon, path.segments.first()) {
+            if path.segments.len() == 1 && segment.arguments.is_none() {
+                if let Some(bound) = env.get(&segment.ident.strip_raw().to_string()) {
+                    return Some(bound.clone());
+                }
+            }
+        }
+
+        let local = referenced_pattern(path, scope);
+        let pattern = match self.definitions.get_key_value(&local) {
+            Some((pattern, _)) => pattern.as_str(),
+            None => self.lookup.resolve_in_scope(path, scope)?,
+        };
+        if expanding.iter().any(|alias| alias == pattern) {
+            return None;
+        }
+        let (item, scope_index) = self.definitions.get(pattern)?;
+        let definition_scope = &self.scopes[*scope_index];
+
+        // Arguments written at the use site, in the use site scope
+        let mut args = Vec::new();
+        if let Some(PathArguments::AngleBracketed(generic)) =
+            path.segments.last().map(|segment| &segment.arguments)
+        {
+            for arg in &generic.args {
+                match arg {
+                    GenericArgument::Type(ty) => {
+                        args.push(self.expand_type(resolver, ty, scope, env, expanding))
+                    }
+                    GenericArgument::Const(expr) => args.push(CanonicalType::Const(expr.clone())),
+                    _ => {}
+                }
+            }
+        }
+
+        // Bind the parameters in order, so defaults can refer to earlier ones
+        let mut bindings = HashMap::new();
+        let mut args = args.into_iter();
+        expanding.push(pattern.to_string());
+        for param in &item.generics.params {
+            let (name, default) = match param {
+                GenericParam::Type(param) => (&param.ident, param.default.as_ref()),
+                GenericParam::Const(param) => (&param.ident, None),
+                GenericParam::Lifetime(_) => continue,
+            };
+            let value = match (args.next(), default) {
+                (Some(arg), _) => arg,
+                (None, Some(default)) => {
+                    self.expand_type(resolver, default, definition_scope, &bindings, expanding)
+                }
+                (None, None) => CanonicalType::Infer,
+            };
+            bindings.insert(name.strip_raw().to_string(), value);
+        }
+        let expanded = self.expand_type(resolver, &item.ty, definition_scope, &bindings, expanding);
+        expanding.pop();
+
+        Some(expanded)
+    }
+}
+
+impl Default for TypeAliases {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DynamicPathResolver {
+    /// Register `type` alias items as additional patterns for the types they alias.
+    ///
+    /// Each alias is keyed by its name with raw prefixes stripped, prefixed by
+    /// the module path of `scope` when one is set (see [`Scope::with_module`]).
+    /// It is mapped to the canonical name its right-hand side resolves to
+    /// through `scope`. Aliases of other aliases in the same call are followed
+    /// through to the final canonical name, whatever their declaration order.
+    ///
+    /// Aliases whose right-hand side is not a path, cannot be resolved, or is
+    /// part of a cycle are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::{DynamicPathResolver, Scope};
+    /// use syn::{ItemType, Path};
+    ///
+    /// let outer: ItemType = syn::parse_str("type Outer = r#Inner;").unwrap();
+    /// let inner: ItemType = syn::parse_str("type r#Inner = Vec<u8>;").unwrap();
+    ///
+    /// let mut resolver = DynamicPathResolver::with_all_groups();
+    /// resolver.add_type_aliases([&outer, &inner], &Scope::new());
+    ///
+    /// let path: Path = syn::parse_str("Outer").unwrap();
+    /// assert_eq!(resolver.resolve(&path), Some("Vec"));
+    /// ```
+    pub fn add_type_aliases<'a, I>(&mut self, aliases: I, scope: &Scope)
+    where
+        I: IntoIterator<Item = &'a ItemType>,
+    {
+        let pending = aliases
+            .into_iter()
+            .map(|item| (alias_pattern(item, scope), PendingAlias { item, scope }))
+            .collect();
+        self.register_aliases(pending);
+    }
+
+    /// Register every `type` alias item of a file, including those in inline modules.
+    ///
+    /// `module_path` is the absolute path of the module the file defines,
+    /// starting with the crate name (e.g. `my_crate::api`), or an empty string
+    /// to register aliases by their bare names. The `use` declarations of each
+    /// module are applied to the right-hand sides of its aliases. Aliases may
+    /// refer to aliases in other modules of the same file.
+    ///
+    /// Returns an error, without registering any alias, if a segment of
+    /// `module_path` is not an identifier. See
+    /// [`DynamicPathResolver::add_type_aliases`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::DynamicPathResolver;
+    /// use syn::Path;
+    ///
+    /// let file: syn::File = syn::parse_str(
+    ///     r#"
+    ///     use std::collections::HashMap as Map;
+    ///
+    ///     type Registry = Map<String, u32>;
+    ///
+    ///     mod ids {
+    ///         pub type UserId = super::Registry;
+    ///     }
+    ///     "#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut resolver = DynamicPathResolver::with_all_groups();
+    /// resolver.add_type_aliases_from_file(&file, "my_crate").unwrap();
+    ///
+    /// let path: Path = syn::parse_str("my_crate::ids::UserId").unwrap();
+    /// assert_eq!(resolver.resolve(&path), Some("HashMap"));
+    /// ```
+    pub fn add_type_aliases_from_file(
+        &mut self,
+        file: &File,
+        module_path: &str,
+    ) -> syn::Result<()> {
+        let mut modules = Vec::new();
+        let module_path = Some(module_path.to_string()).filter(|path| !path.is_empty());
+        collect_modules(&file.items, module_path, &mut modules)?;
+
+        let pending = modules
+            .iter()
+            .flat_map(|(items, scope)| {
+                items.iter().filter_map(move |item| match item {
+                    Item::Type(item) => {
+                        Some((alias_pattern(item, scope), PendingAlias { item, scope }))
+                    }
+                    _ => None,
+                })
+            })
+            .collect();
+        self.register_aliases(pending);
+        Ok(())
+    }
+
+    /// Resolve a batch of aliases, following aliases of aliases, and add their mappings.
+    fn register_aliases(&mut self, pending: HashMap<String, PendingAlias<'_>>) {
+        let mut resolved = HashMap::new();
+        for pattern in pending.keys() {
+            self.alias_target(pattern, &pending, &mut resolved, &mut Vec::new());
+        }
+
+        for (pattern, canonical) in resolved {
+            if let Some(canonical) = canonical {
+                self.add_mapping(pattern, canonical);
+            }
+        }
+    }
+
+    /// Find the canonical name an alias resolves to, memoizing the result.
+    ///
+    /// `visiting` holds the aliases currently being resolved, to stop on cycles.
+    fn alias_target(
+        &self,
+        pattern: &str,
+        pending: &HashMap<String, PendingAlias<'_>>,
+        resolved: &mut HashMap<String, Option<String>>,
+        visiting: &mut Vec<String>,
+    ) -> Option<String> {
+        if let Some(canonical) = resolved.get(pattern) {
+            return canonical.clone();
+        }
+        if visiting.iter().any(|alias| alias == pattern) {
+            return None;
+        }
+        let alias = pending.get(pattern)?;
+
+        visiting.push(pattern.to_string());
+        let canonical = match &*alias.item.ty {
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                let target = referenced_pattern(&type_path.path, alias.scope);
+                if pending.contains_key(&target) {
+                    self.alias_target(&target, pending, resolved, visiting)
+                } else {
+                    self.resolve_in_scope(&type_path.path, alias.scope)
+                        .map(str::to_string)
+                }
+            }
+            _ => None,
+        };
+        visiting.pop();
+
+        resolved.insert(pattern.to_string(), canonical.clone());
+        canonical
+    }
+}
+
+/// Collect the items of a module and of its inline submodules, each with its scope.
+fn collect_modules<'a>(
+    items: &'a [Item],
+    module_path: Option<String>,
+    modules: &mut Vec<(&'a [Item], Scope)>,
+) -> syn::Result<()> {
+    let mut scope = Scope::from_items(items);
+    if let Some(module_path) = &module_path {
+        scope.set_module(module_path)?;
+    }
+
+    for item in items {
+        if let Item::Mod(module) = item {
+            if let Some((_, content)) = &module.content {
+                let child = module_path
+                    .as_ref()
+                    .map(|parent| format!("{}::{}", parent, module.ident.strip_raw()));
+                collect_modules(content, child, modules)?;
+            }
+        }
+    }
+    modules.push((items, scope));
+    Ok(())
+}
+
+/// The pattern an alias is registered under.
+fn alias_pattern(item: &ItemType, scope: &Scope) -> String {
+    let name = item.ident.strip_raw().to_string();
+    match scope.module() {
+        Some(module) => format!("{}::{}", module, name),
+        None => name,
+    }
+}
+
+/// The pattern an alias would be registered under if `path` named one.
+///
+/// Paths that were neither imported nor written relative to `crate`, `self`
+/// or `super` are looked up in the current module, as in the 2018 edition.
+fn referenced_pattern(path: &Path, scope: &Scope) -> String {
+    let (path, local) = match scope.expand(path) {
+        Some(expanded) => (expanded, false),
+        None => (path.clone(), path.leading_colon.is_none()),
+    };
+    let (path, local) = match scope.absolutize(&path) {
+        Some(absolute) => (absolute, false),
+        None => (path, local),
+    };
+
+    let name = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.strip_raw().to_string())
+        .collect::<Vec<_>>()
+        .join("::");
+    match scope.module() {
+        Some(module) if local => format!("{}::{}", module, name),
+        _ => name,
+    }
+}
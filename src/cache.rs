@@ -0,0 +1,335 @@
+//! Memoized resolution for callers that resolve the same paths repeatedly.
+//!
+//! Proc macros typically resolve the same few dozen paths many times while
+//! expanding a crate. [`CachingResolver`] wraps a [`PathResolver`] and
+//! remembers the result for each normalized path, so repeated lookups skip
+//! the resolution strategies entirely.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use syn::{Path, PathArguments};
+
+use crate::normalized::NormalizedPath;
+use crate::{
+    DynamicPathResolver, DynamicStorage, MappingStorage, Matcher, PathResolver, ResolutionPolicy,
+    TypeGroups,
+};
+
+/// A [`PathResolver`] that memoizes results by normalized path.
+///
+/// The cache uses interior mutability, so lookups only need `&self` and a
+/// caching resolver can be shared between threads. It holds at most
+/// [`capacity`](Self::capacity) entries, evicting the oldest entry first.
+///
+/// Every method that changes the configuration of the wrapped resolver, such
+/// as [`add_mapping`](Self::add_mapping), [`set_groups`](Self::set_groups) or
+/// [`clear`](Self::clear), also empties the cache, as does
+/// [`resolver_mut`](Self::resolver_mut).
+///
+/// Paths with generic arguments are only cached when the storage has no
+/// generic pattern keys (see [`MappingStorage::generic_pattern_keys`]), as
+/// otherwise the arguments can change the result.
+///
+/// # Examples
+///
+/// ```
+/// use desynt::{CachingResolver, DynamicPathResolver};
+/// use syn::Path;
+///
+/// let mut resolver = CachingResolver::new(DynamicPathResolver::with_all_groups());
+/// resolver.add_mapping("my_crate::types::UserId", "UserId");
+///
+/// let path: Path = syn::parse_str("my_crate::types::UserId").unwrap();
+/// assert_eq!(resolver.resolve(&path).as_deref(), Some("UserId"));
+/// assert_eq!(resolver.resolve(&path).as_deref(), Some("UserId"));
+/// assert_eq!((resolver.hits(), resolver.misses()), (1, 1));
+///
+/// // Changing the configuration invalidates the cache
+/// resolver.add_mapping("my_crate::types::UserId", "Identifier");
+/// assert_eq!(resolver.resolve(&path).as_deref(), Some("Identifier"));
+/// ```
+pub struct CachingResolver<M = DynamicStorage> {
+    resolver: PathResolver<M>,
+    capacity: usize,
+    cache: Mutex<Cache>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Cached results, keyed on the normalized path.
+#[derive(Default)]
+struct Cache {
+    entries: HashMap<String, Option<Arc<str>>>,
+    /// Keys in insertion order, oldest first.
+    order: VecDeque<String>,
+}
+
+impl<M> CachingResolver<M>
+where
+    M: MappingStorage,
+{
+    /// The number of entries cached by [`CachingResolver::new`].
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    /// Wrap a resolver, caching up to [`DEFAULT_CAPACITY`](Self::DEFAULT_CAPACITY) paths.
+    pub fn new(resolver: PathResolver<M>) -> Self {
+        Self::with_capacity(resolver, Self::DEFAULT_CAPACITY)
+    }
+
+    /// Wrap a resolver, caching up to `capacity` paths.
+    ///
+    /// A capacity of `0` disables caching.
+    pub fn with_capacity(resolver: PathResolver<M>, capacity: usize) -> Self {
+        Self {
+            resolver,
+            capacity,
+            cache: Mutex::default(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Resolve a syn [`Path`] to its canonical type name, using the cache.
+    ///
+    /// See [`PathResolver::resolve`].
+    pub fn resolve(&self, path: &Path) -> Option<Arc<str>> {
+        if !self.is_cacheable(path) {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return self.resolver.resolve(path).map(Arc::from);
+        }
+
+        let normalized = NormalizedPath::new(path);
+        let key = normalized.as_str();
+        if let Some(cached) = self.lock().entries.get(key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return cached.clone();
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let resolved: Option<Arc<str>> = self.resolver.resolve(path).map(Arc::from);
+        self.lock().insert(key, resolved.clone(), self.capacity);
+        resolved
+    }
+
+    /// Return the wrapped resolver.
+    pub fn resolver(&self) -> &PathResolver<M> {
+        &self.resolver
+    }
+
+    /// Return the wrapped resolver mutably, emptying the cache.
+    pub fn resolver_mut(&mut self) -> &mut PathResolver<M> {
+        self.invalidate();
+        &mut self.resolver
+    }
+
+    /// Unwrap the resolver, discarding the cache.
+    pub fn into_inner(self) -> PathResolver<M> {
+        self.resolver
+    }
+
+    /// Return the maximum number of cached paths.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Return the number of cached paths.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Return `true` if no paths are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the number of lookups answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Return the number of lookups passed on to the wrapped resolver.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Reset the hit and miss counters to zero.
+    pub fn reset_stats(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// Empty the cache, keeping the hit and miss counters.
+    pub fn invalidate(&mut self) {
+        let cache = self.cache.get_mut().unwrap_or_else(PoisonError::into_inner);
+        cache.entries.clear();
+        cache.order.clear();
+    }
+
+    /// Return `true` if the result for a path depends only on its segments.
+    fn is_cacheable(&self, path: &Path) -> bool {
+        if self.capacity == 0 {
+            return false;
+        }
+        let has_arguments = path
+            .segments
+            .iter()
+            .any(|segment| !matches!(segment.arguments, PathArguments::None));
+        !has_arguments
+            || self
+                .resolver
+                .mappings
+                .generic_pattern_keys()
+                .next()
+                .is_none()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Cache> {
+        // Entries are only ever replaced whole, so a poisoned cache is still valid
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Cache {
+    fn insert(&mut self, key: &str, resolved: Option<Arc<str>>, capacity: usize) {
+        // Another thread may have cached the same path while the lock was released
+        if self.entries.contains_key(key) {
+            return;
+        }
+        while self.entries.len() >= capacity {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(key.to_string(), resolved);
+        self.order.push_back(key.to_string());
+    }
+}
+
+// Configuration methods of DynamicPathResolver, each emptying the cache
+impl CachingResolver<DynamicStorage> {
+    /// Add a custom mapping, emptying the cache.
+    ///
+    /// See [`DynamicPathResolver::add_mapping`].
+    pub fn add_mapping<S1, S2>(&mut self, path_pattern: S1, canonical_type: S2)
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.resolver_mut()
+            .add_mapping(path_pattern, canonical_type);
+    }
+
+    /// Add a custom mapping together with its priority, emptying the cache.
+    ///
+    /// See [`DynamicPathResolver::add_mapping_with_priority`].
+    pub fn add_mapping_with_priority<S1, S2>(
+        &mut self,
+        path_pattern: S1,
+        canonical_type: S2,
+        priority: i32,
+    ) where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.resolver_mut()
+            .add_mapping_with_priority(path_pattern, canonical_type, priority);
+    }
+
+    /// Set the priority of a pattern, emptying the cache.
+    ///
+    /// See [`DynamicPathResolver::set_priority`].
+    pub fn set_priority<S>(&mut self, path_pattern: S, priority: i32)
+    where
+        S: Into<String>,
+    {
+        self.resolver_mut().set_priority(path_pattern, priority);
+    }
+
+    /// Add a named [`Matcher`], emptying the cache.
+    ///
+    /// See [`DynamicPathResolver::add_matcher`].
+    pub fn add_matcher<S, T>(&mut self, name: S, matcher: T)
+    where
+        S: Into<String>,
+        T: Matcher + Send + Sync + 'static,
+    {
+        self.resolver_mut().add_matcher(name, matcher);
+    }
+
+    /// Remove a named matcher, emptying the cache.
+    ///
+    /// See [`DynamicPathResolver::remove_matcher`].
+    pub fn remove_matcher(&mut self, name: &str) -> bool {
+        self.resolver_mut().remove_matcher(name)
+    }
+
+    /// Add a facade rule, emptying the cache.
+    ///
+    /// See [`DynamicPathResolver::add_facade`].
+    pub fn add_facade<S1, S2>(&mut self, prefix: S1, target: S2)
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.resolver_mut().add_facade(prefix, target);
+    }
+
+    /// Remove the facade rule for a prefix, emptying the cache.
+    ///
+    /// See [`DynamicPathResolver::remove_facade`].
+    pub fn remove_facade(&mut self, prefix: &str) -> bool {
+        self.resolver_mut().remove_facade(prefix)
+    }
+
+    /// Set which type groups to use, emptying the cache.
+    pub fn set_groups(&mut self, groups: TypeGroups) {
+        self.resolver_mut().set_groups(groups);
+    }
+
+    /// Set which resolution strategies may be used, emptying the cache.
+    pub fn set_policy(&mut self, policy: ResolutionPolicy) {
+        self.resolver_mut().set_policy(policy);
+    }
+
+    /// Remove all custom mappings, their priorities and matchers, emptying the cache.
+    ///
+    /// See [`DynamicPathResolver::clear`].
+    pub fn clear(&mut self) {
+        self.resolver_mut().clear();
+    }
+}
+
+impl<M> From<PathResolver<M>> for CachingResolver<M>
+where
+    M: MappingStorage,
+{
+    fn from(resolver: PathResolver<M>) -> Self {
+        Self::new(resolver)
+    }
+}
+
+impl Default for CachingResolver<DynamicStorage> {
+    fn default() -> Self {
+        Self::new(DynamicPathResolver::default())
+    }
+}
+
+impl<M> fmt::Debug for CachingResolver<M>
+where
+    M: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+        f.debug_struct("CachingResolver")
+            .field("resolver", &self.resolver)
+            .field("capacity", &self.capacity)
+            .field("len", &cache.entries.len())
+            .field("hits", &self.hits.load(Ordering::Relaxed))
+            .field("misses", &self.misses.load(Ordering::Relaxed))
+            .finish()
+    }
+}
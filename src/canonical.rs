@@ -0,0 +1,353 @@
+//! Structured canonical representation of whole [`syn::Type`] trees.
+//!
+//! [`PathResolver::resolve_type`] walks references, slices, arrays, tuples,
+//! pointers, function pointers and trait bounds, resolving every named type
+//! it finds through the regular path resolution logic.
+
+use syn::punctuated::Punctuated;
+use syn::token::Plus;
+use syn::{Expr, GenericArgument, Path, PathArguments, ReturnType, Type, TypeParamBound};
+
+use crate::projection::Projection;
+use crate::{MappingStorage, PathResolver, Scope, StripRaw};
+
+/// A canonical view of a [`syn::Type`].
+///
+/// Lifetimes, parentheses and invisible groups are dropped, as they do not
+/// change which type is being referred to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CanonicalType {
+    /// A named type such as `Vec<u8>`.
+    Path(CanonicalPath),
+    /// A reference, `&T` or `&mut T`.
+    Reference {
+        /// Whether this is a `&mut` reference.
+        mutable: bool,
+        /// The referenced type.
+        elem: Box<CanonicalType>,
+    },
+    /// A raw pointer, `*const T` or `*mut T`.
+    Ptr {
+        /// Whether this is a `*mut` pointer.
+        mutable: bool,
+        /// The pointed-to type.
+        elem: Box<CanonicalType>,
+    },
+    /// A dynamically sized slice, `[T]`.
+    Slice(Box<CanonicalType>),
+    /// A fixed size array, `[T; N]`.
+    Array {
+        /// The element type.
+        elem: Box<CanonicalType>,
+        /// The length expression, kept as written.
+        len: Expr,
+    },
+    /// A tuple, `(A, B)`. The unit type is an empty tuple.
+    Tuple(Vec<CanonicalType>),
+    /// A function pointer, `fn(A, B) -> C`.
+    ///
+    /// A missing return type is represented as the unit type.
+    BareFn {
+        /// The argument types.
+        inputs: Vec<CanonicalType>,
+        /// The return type.
+        output: Box<CanonicalType>,
+    },
+    /// A trait object, `dyn Trait + Send`. Only trait bounds are kept.
+    TraitObject(Vec<CanonicalPath>),
+    /// An `impl Trait` type. Only trait bounds are kept.
+    ImplTrait(Vec<CanonicalPath>),
+    /// An associated type projection without a mapping, such as
+    /// `<T as Trait>::Assoc` or `Self::Item`.
+    Projection {
+        /// The type the associated type is projected from.
+        qself: Box<CanonicalType>,
+        /// The trait declaring the associated type, if known.
+        trait_path: Option<CanonicalPath>,
+        /// The associated type name.
+        assoc: String,
+    },
+    /// The never type, `!`.
+    Never,
+    /// The inferred type, `_`.
+    Infer,
+    /// A const generic argument, such as the `16` in `ArrayVec<u8, 16>`.
+    Const(Expr),
+    /// A type that has no canonical form, such as a macro in type position.
+    Other(Type),
+}
+
+/// A named type together with its canonical generic arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalPath {
+    /// The canonical type name if the path was resolved,
+    /// otherwise the normalized path (e.g. `my_crate::Thing`).
+    pub name: String,
+    /// Whether `name` was produced by the resolver.
+    pub resolved: bool,
+    /// The canonical generic arguments of the last path segment.
+    ///
+    /// Lifetime arguments are dropped. Parenthesized arguments such as
+    /// `Fn(A, B) -> C` contribute a single tuple of their inputs.
+    pub args: Vec<CanonicalType>,
+    /// Associated type bindings such as `Item = T`, in source order.
+    ///
+    /// The return type of parenthesized arguments is bound as `Output`.
+    pub bindings: Vec<(String, CanonicalType)>,
+}
+
+impl<M> PathResolver<M>
+where
+    M: MappingStorage,
+{
+    /// Resolve a syn [`Type`] into a [`CanonicalType`] tree.
+    ///
+    /// Every named type inside the tree, including generic arguments, is
+    /// resolved using the same strategies as [`PathResolver::resolve`].
+    /// Named types that cannot be resolved are kept with their normalized
+    /// path and `resolved` set to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::{CanonicalType, DynamicPathResolver};
+    /// use syn::Type;
+    ///
+    /// let mut resolver = DynamicPathResolver::with_all_groups();
+    /// resolver.add_mapping("MyVec", "Vec");
+    ///
+    /// let ty: Type = syn::parse_str("&'a r#MyVec<[u8; 4]>").unwrap();
+    /// let CanonicalType::Reference { mutable, elem } = resolver.resolve_type(&ty) else {
+    ///     panic!("expected a reference");
+    /// };
+    /// assert!(!mutable);
+    ///
+    /// let CanonicalType::Path(vec) = *elem else {
+    ///     panic!("expected a path");
+    /// };
+    /// assert_eq!(vec.name, "Vec");
+    /// assert!(matches!(vec.args[0], CanonicalType::Array { .. }));
+    /// ```
+    pub fn resolve_type(&self, ty: &Type) -> CanonicalType {
+        self.canonical_type(ty, None, &mut |_| None)
+    }
+
+    /// Resolve a syn [`Type`] into a [`CanonicalType`] tree, expanding every
+    /// named type through the imports of `scope`.
+    ///
+    /// See [`PathResolver::resolve_in_scope`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::{DynamicPathResolver, Scope};
+    /// use syn::Type;
+    ///
+    /// let mut resolver = DynamicPathResolver::with_all_groups();
+    /// resolver.add_mapping("my_crate::types::UserId", "UserId");
+    ///
+    /// let file: syn::File = syn::parse_str("use my_crate::types::UserId as Id;").unwrap();
+    /// let scope = Scope::from_file(&file);
+    ///
+    /// let ty: Type = syn::parse_str("Vec<Id>").unwrap();
+    /// let canonical = resolver.resolve_type_in_scope(&ty, &scope);
+    /// assert_eq!(resolver.resolve_type(&syn::parse_str("Vec<UserId>").unwrap()), canonical);
+    /// ```
+    pub fn resolve_type_in_scope(&self, ty: &Type, scope: &Scope) -> CanonicalType {
+        self.canonical_type(ty, Some(scope), &mut |_| None)
+    }
+
+    /// Resolve a syn [`Path`] together with all of its generic arguments.
+    ///
+    /// Unlike [`PathResolver::resolve`], which only returns the canonical name
+    /// of the outermost type, this walks angle bracketed and parenthesized
+    /// arguments recursively and resolves every nested path through the same
+    /// mappings and type groups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::{CanonicalType, DynamicPathResolver};
+    /// use syn::Path;
+    ///
+    /// let mut resolver = DynamicPathResolver::with_all_groups();
+    /// resolver.add_mapping("my::UserId", "UserId");
+    ///
+    /// let path: Path = syn::parse_str("Option<Vec<my::UserId>>").unwrap();
+    /// let option = resolver.resolve_canonical(&path);
+    /// assert_eq!(option.name, "Option");
+    ///
+    /// let CanonicalType::Path(vec) = &option.args[0] else {
+    ///     panic!("expected a path");
+    /// };
+    /// assert_eq!(vec.name, "Vec");
+    ///
+    /// let CanonicalType::Path(user_id) = &vec.args[0] else {
+    ///     panic!("expected a path");
+    /// };
+    /// assert_eq!(user_id.name, "UserId");
+    /// ```
+    pub fn resolve_canonical(&self, path: &Path) -> CanonicalPath {
+        self.canonical_path(path, None, &mut |_| None)
+    }
+
+    /// Resolve a type tree, optionally through a scope.
+    ///
+    /// `expand` is offered every named type first and may replace it, as
+    /// type alias expansion does.
+    pub(crate) fn canonical_type(
+        &self,
+        ty: &Type,
+        scope: Option<&Scope>,
+        expand: &mut dyn FnMut(&Path) -> Option<CanonicalType>,
+    ) -> CanonicalType {
+        match ty {
+            Type::Path(type_path) => {
+                if type_path.qself.is_none() {
+                    if let Some(expanded) = expand(&type_path.path) {
+                        return expanded;
+                    }
+                }
+                if let Some(projection) = Projection::from_type_path(type_path, scope) {
+                    return self.canonical_projection(&projection, scope, expand);
+                }
+                if let Some(self_type) = scope.and_then(|scope| scope.self_type_of(&type_path.path))
+                {
+                    return self.canonical_type(self_type, scope, expand);
+                }
+                CanonicalType::Path(self.canonical_path(&type_path.path, scope, expand))
+            }
+            Type::Reference(reference) => CanonicalType::Reference {
+                mutable: reference.mutability.is_some(),
+                elem: Box::new(self.canonical_type(&reference.elem, scope, expand)),
+            },
+            Type::Ptr(ptr) => CanonicalType::Ptr {
+                mutable: ptr.mutability.is_some(),
+                elem: Box::new(self.canonical_type(&ptr.elem, scope, expand)),
+            },
+            Type::Slice(slice) => {
+                CanonicalType::Slice(Box::new(self.canonical_type(&slice.elem, scope, expand)))
+            }
+            Type::Array(array) => CanonicalType::Array {
+                elem: Box::new(self.canonical_type(&array.elem, scope, expand)),
+                len: match &array.len {
+                    // A const generic parameter used as the length may be substituted
+                    Expr::Path(len) if len.qself.is_none() => match expand(&len.path) {
+                        Some(CanonicalType::Const(expr)) => expr,
+                        _ => array.len.clone(),
+                    },
+                    len => len.clone(),
+                },
+            },
+            Type::Tuple(tuple) => CanonicalType::Tuple(
+                tuple
+                    .elems
+                    .iter()
+                    .map(|elem| self.canonical_type(elem, scope, expand))
+                    .collect(),
+            ),
+            Type::BareFn(bare_fn) => CanonicalType::BareFn {
+                inputs: bare_fn
+                    .inputs
+                    .iter()
+                    .map(|arg| self.canonical_type(&arg.ty, scope, expand))
+                    .collect(),
+                output: Box::new(match &bare_fn.output {
+                    ReturnType::Default => CanonicalType::Tuple(Vec::new()),
+                    ReturnType::Type(_, ty) => self.canonical_type(ty, scope, expand),
+                }),
+            },
+            Type::TraitObject(object) => {
+                CanonicalType::TraitObject(self.resolve_bounds(&object.bounds, scope, expand))
+            }
+            Type::ImplTrait(impl_trait) => {
+                CanonicalType::ImplTrait(self.resolve_bounds(&impl_trait.bounds, scope, expand))
+            }
+            Type::Paren(paren) => self.canonical_type(&paren.elem, scope, expand),
+            Type::Group(group) => self.canonical_type(&group.elem, scope, expand),
+            Type::Never(_) => CanonicalType::Never,
+            Type::Infer(_) => CanonicalType::Infer,
+            other => CanonicalType::Other(other.clone()),
+        }
+    }
+
+    /// Resolve a path and its generic arguments, optionally through a scope.
+    pub(crate) fn canonical_path(
+        &self,
+        path: &Path,
+        scope: Option<&Scope>,
+        expand: &mut dyn FnMut(&Path) -> Option<CanonicalType>,
+    ) -> CanonicalPath {
+        let mut args = Vec::new();
+        let mut bindings = Vec::new();
+
+        match path.segments.last().map(|segment| &segment.arguments) {
+            Some(PathArguments::AngleBracketed(generic)) => {
+                for arg in &generic.args {
+                    match arg {
+                        GenericArgument::Type(ty) => {
+                            args.push(self.canonical_type(ty, scope, expand))
+                        }
+                        GenericArgument::Const(expr) => {
+                            args.push(CanonicalType::Const(expr.clone()))
+                        }
+                        GenericArgument::AssocType(assoc) => {
+                            let name = assoc.ident.strip_raw().to_string();
+                            bindings.push((name, self.canonical_type(&assoc.ty, scope, expand)));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Some(PathArguments::Parenthesized(parenthesized)) => {
+                let inputs = parenthesized
+                    .inputs
+                    .iter()
+                    .map(|ty| self.canonical_type(ty, scope, expand))
+                    .collect();
+                args.push(CanonicalType::Tuple(inputs));
+                if let ReturnType::Type(_, ty) = &parenthesized.output {
+                    bindings.push(("Output".to_string(), self.canonical_type(ty, scope, expand)));
+                }
+            }
+            _ => {}
+        }
+
+        let resolved = match scope {
+            Some(scope) => self.resolve_in_scope(path, scope),
+            None => self.resolve(path),
+        };
+        match resolved {
+            Some(name) => CanonicalPath {
+                name: name.to_string(),
+                resolved: true,
+                args,
+                bindings,
+            },
+            None => CanonicalPath {
+                name: self.normalize_path(path),
+                resolved: false,
+                args,
+                bindings,
+            },
+        }
+    }
+
+    /// Resolve the trait bounds of a trait object or `impl Trait` type.
+    fn resolve_bounds(
+        &self,
+        bounds: &Punctuated<TypeParamBound, Plus>,
+        scope: Option<&Scope>,
+        expand: &mut dyn FnMut(&Path) -> Option<CanonicalType>,
+    ) -> Vec<CanonicalPath> {
+        bounds
+            .iter()
+            .filter_map(|bound| match bound {
+                TypeParamBound::Trait(trait_bound) => {
+                    Some(self.canonical_path(&trait_bound.path, scope, expand))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
@@ -1,270 +1,976 @@
 //! Built-in type path mappings for Rust standard library types.
 //!
-//! This module contains the mapping functions that resolve various path forms
-//! of Rust standard library types to their canonical names.
-
-use crate::TypeGroups;
+//! Every builtin type is listed once in [`BUILTIN_TYPES`], together with its
+//! module and each path it can be written as. Exact lookups, the bare type
+//! name and shortening heuristics, and [`PathResolver::canonical_types`] all
+//! read this table, through hash indexes built on first use, so neither
+//! needs to build path strings or scan every path.
+//!
+//! The table lists `std` paths only. `std` is a facade re-exporting the items
+//! of `core` and `alloc` under the same module paths, so each type's paths are
+//! also indexed under the crate that defines it, its
+//! [`origin`](BuiltinType::origin): `core::option::Option` and
+//! `alloc::collections::BTreeMap` resolve like their `std` paths.
+//!
+//! [`PathResolver::canonical_types`]: crate::PathResolver::canonical_types
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use crate::{TypeGroup, TypeGroups};
+
+/// A builtin type, the type group providing it and the paths it can be written as.
+///
+/// Every builtin type is listed in [`BUILTIN_TYPES`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BuiltinType {
+    /// The canonical type name, such as `HashMap`.
+    pub canonical: &'static str,
+    /// The type group that provides the type.
+    pub group: TypeGroup,
+    /// The module the type is documented under, such as `std::collections`.
+    pub module: &'static str,
+    /// The crate defining the type, `core`, `alloc` or `std`.
+    ///
+    /// A type defined in `core` or `alloc` can also be written with that
+    /// crate in place of `std` at the start of each of its paths.
+    pub origin: &'static str,
+    /// Every `std` path the type can be written as, such as
+    /// `std::collections::hash_map::HashMap`.
+    pub paths: &'static [&'static str],
+}
 
-/// Built-in type mappings for primitives and common standard library types
-#[cfg(feature = "static-resolver")]
-pub(crate) fn get_builtin_mapping_static(path: &str, groups: TypeGroups) -> Option<&'static str> {
-    // Try primitives first if enabled
-    if groups.primitives {
-        if let Some(result) = get_primitive_mapping_static(path) {
-            return Some(result);
-        }
+impl BuiltinType {
+    /// Return every path the type can be written as, its `std` paths followed
+    /// by the same paths under its [`origin`](BuiltinType::origin) crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::BUILTIN_TYPES;
+    ///
+    /// let cow = BUILTIN_TYPES.iter().find(|builtin| builtin.canonical == "Cow").unwrap();
+    /// assert_eq!(cow.origin, "alloc");
+    /// assert_eq!(cow.all_paths().collect::<Vec<_>>(), ["std::borrow::Cow", "alloc::borrow::Cow"]);
+    /// ```
+    pub fn all_paths(&self) -> impl Iterator<Item = Cow<'static, str>> {
+        let origin = self.origin;
+        let reexported = self
+            .paths
+            .iter()
+            .filter(move |_| origin != "std")
+            .filter_map(move |path| Some(format!("{origin}{}", path.strip_prefix("std")?)));
+        self.paths
+            .iter()
+            .map(|path| Cow::Borrowed(*path))
+            .chain(reexported.map(Cow::Owned))
     }
+}
 
-    // Try prelude types if enabled
-    if groups.prelude {
-        if let Some(result) = get_prelude_mapping_static(path) {
-            return Some(result);
-        }
+const fn builtin(
+    canonical: &'static str,
+    group: TypeGroup,
+    module: &'static str,
+    origin: &'static str,
+    paths: &'static [&'static str],
+) -> BuiltinType {
+    BuiltinType {
+        canonical,
+        group,
+        module,
+        origin,
+        paths,
     }
+}
 
-    // Try common std types if enabled
-    if groups.common_std {
-        if let Some(result) = get_common_std_mapping_static(path) {
-            return Some(result);
-        }
+/// Every builtin type, grouped by type group in lookup order.
+///
+/// This is the single source of the builtin mappings. It can be used to list
+/// what each type group covers.
+///
+/// # Examples
+///
+/// ```
+/// use desynt::{BUILTIN_TYPES, TypeGroup};
+///
+/// let prelude: Vec<&str> = BUILTIN_TYPES
+///     .iter()
+///     .filter(|builtin| builtin.group == TypeGroup::Prelude)
+///     .map(|builtin| builtin.canonical)
+///     .collect();
+/// assert_eq!(prelude, ["String", "Vec", "Option", "Result", "Box"]);
+/// ```
+pub static BUILTIN_TYPES: &[BuiltinType] = &[
+    // Primitive integer types (actual language primitives)
+    builtin(
+        "i8",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::i8", "std::i8"],
+    ),
+    builtin(
+        "i16",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::i16", "std::i16"],
+    ),
+    builtin(
+        "i32",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::i32", "std::i32"],
+    ),
+    builtin(
+        "i64",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::i64", "std::i64"],
+    ),
+    builtin(
+        "i128",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::i128", "std::i128"],
+    ),
+    builtin(
+        "isize",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::isize", "std::isize"],
+    ),
+    // Primitive unsigned integer types
+    builtin(
+        "u8",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::u8", "std::u8"],
+    ),
+    builtin(
+        "u16",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::u16", "std::u16"],
+    ),
+    builtin(
+        "u32",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::u32", "std::u32"],
+    ),
+    builtin(
+        "u64",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::u64", "std::u64"],
+    ),
+    builtin(
+        "u128",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::u128", "std::u128"],
+    ),
+    builtin(
+        "usize",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::usize", "std::usize"],
+    ),
+    // Primitive floating point types
+    builtin(
+        "f32",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::f32", "std::f32"],
+    ),
+    builtin(
+        "f64",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::f64", "std::f64"],
+    ),
+    // Other primitive types
+    builtin(
+        "bool",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::bool", "std::bool"],
+    ),
+    builtin(
+        "char",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::char", "std::char"],
+    ),
+    builtin(
+        "str",
+        TypeGroup::Primitives,
+        "std::primitive",
+        "core",
+        &["std::primitive::str", "std::str"],
+    ),
+    // Rust prelude types (automatically imported in every module)
+    builtin(
+        "String",
+        TypeGroup::Prelude,
+        "std::string",
+        "alloc",
+        &["std::string::String"],
+    ),
+    builtin(
+        "Vec",
+        TypeGroup::Prelude,
+        "std::vec",
+        "alloc",
+        &["std::vec::Vec"],
+    ),
+    builtin(
+        "Option",
+        TypeGroup::Prelude,
+        "std::option",
+        "core",
+        &["std::option::Option"],
+    ),
+    builtin(
+        "Result",
+        TypeGroup::Prelude,
+        "std::result",
+        "core",
+        &["std::result::Result"],
+    ),
+    builtin(
+        "Box",
+        TypeGroup::Prelude,
+        "std::boxed",
+        "alloc",
+        &["std::boxed::Box"],
+    ),
+    // Common std library types (not in prelude but frequently used)
+    builtin(
+        "HashMap",
+        TypeGroup::CommonStd,
+        "std::collections",
+        "std",
+        &[
+            "std::collections::HashMap",
+            "std::collections::hash_map::HashMap",
+        ],
+    ),
+    builtin(
+        "HashSet",
+        TypeGroup::CommonStd,
+        "std::collections",
+        "std",
+        &[
+            "std::collections::HashSet",
+            "std::collections::hash_set::HashSet",
+        ],
+    ),
+    builtin(
+        "BTreeMap",
+        TypeGroup::CommonStd,
+        "std::collections",
+        "alloc",
+        &[
+            "std::collections::BTreeMap",
+            "std::collections::btree_map::BTreeMap",
+        ],
+    ),
+    builtin(
+        "BTreeSet",
+        TypeGroup::CommonStd,
+        "std::collections",
+        "alloc",
+        &[
+            "std::collections::BTreeSet",
+            "std::collections::btree_set::BTreeSet",
+        ],
+    ),
+    builtin(
+        "LinkedList",
+        TypeGroup::CommonStd,
+        "std::collections",
+        "alloc",
+        &[
+            "std::collections::LinkedList",
+            "std::collections::linked_list::LinkedList",
+        ],
+    ),
+    builtin(
+        "Cow",
+        TypeGroup::CommonStd,
+        "std::borrow",
+        "alloc",
+        &["std::borrow::Cow"],
+    ),
+    builtin(
+        "RefCell",
+        TypeGroup::CommonStd,
+        "std::cell",
+        "core",
+        &["std::cell::RefCell"],
+    ),
+    builtin(
+        "Arc",
+        TypeGroup::CommonStd,
+        "std::sync",
+        "alloc",
+        &["std::sync::Arc"],
+    ),
+    builtin(
+        "Rc",
+        TypeGroup::CommonStd,
+        "std::rc",
+        "alloc",
+        &["std::rc::Rc"],
+    ),
+    // Concurrency and synchronization types
+    builtin(
+        "Mutex",
+        TypeGroup::Concurrency,
+        "std::sync",
+        "std",
+        &["std::sync::Mutex"],
+    ),
+    builtin(
+        "RwLock",
+        TypeGroup::Concurrency,
+        "std::sync",
+        "std",
+        &["std::sync::RwLock"],
+    ),
+    builtin(
+        "Condvar",
+        TypeGroup::Concurrency,
+        "std::sync",
+        "std",
+        &["std::sync::Condvar"],
+    ),
+    builtin(
+        "Once",
+        TypeGroup::Concurrency,
+        "std::sync",
+        "std",
+        &["std::sync::Once"],
+    ),
+    builtin(
+        "OnceLock",
+        TypeGroup::Concurrency,
+        "std::sync",
+        "std",
+        &["std::sync::OnceLock"],
+    ),
+    builtin(
+        "LazyLock",
+        TypeGroup::Concurrency,
+        "std::sync",
+        "std",
+        &["std::sync::LazyLock"],
+    ),
+    builtin(
+        "Weak",
+        TypeGroup::Concurrency,
+        "std::sync",
+        "alloc",
+        &["std::sync::Weak"],
+    ),
+    builtin(
+        "Weak",
+        TypeGroup::Concurrency,
+        "std::rc",
+        "alloc",
+        &["std::rc::Weak"],
+    ),
+    builtin(
+        "Cell",
+        TypeGroup::Concurrency,
+        "std::cell",
+        "core",
+        &["std::cell::Cell"],
+    ),
+    builtin(
+        "OnceCell",
+        TypeGroup::Concurrency,
+        "std::cell",
+        "core",
+        &["std::cell::OnceCell"],
+    ),
+    builtin(
+        "UnsafeCell",
+        TypeGroup::Concurrency,
+        "std::cell",
+        "core",
+        &["std::cell::UnsafeCell"],
+    ),
+    builtin(
+        "AtomicBool",
+        TypeGroup::Concurrency,
+        "std::sync::atomic",
+        "core",
+        &["std::sync::atomic::AtomicBool"],
+    ),
+    builtin(
+        "AtomicI8",
+        TypeGroup::Concurrency,
+        "std::sync::atomic",
+        "core",
+        &["std::sync::atomic::AtomicI8"],
+    ),
+    builtin(
+        "AtomicI16",
+        TypeGroup::Concurrency,
+        "std::sync::atomic",
+        "core",
+        &["std::sync::atomic::AtomicI16"],
+    ),
+    builtin(
+        "AtomicI32",
+        TypeGroup::Concurrency,
+        "std::sync::atomic",
+        "core",
+        &["std::sync::atomic::AtomicI32"],
+    ),
+    builtin(
+        "AtomicI64",
+        TypeGroup::Concurrency,
+        "std::sync::atomic",
+        "core",
+        &["std::sync::atomic::AtomicI64"],
+    ),
+    builtin(
+        "AtomicIsize",
+        TypeGroup::Concurrency,
+        "std::sync::atomic",
+        "core",
+        &["std::sync::atomic::AtomicIsize"],
+    ),
+    builtin(
+        "AtomicU8",
+        TypeGroup::Concurrency,
+        "std::sync::atomic",
+        "core",
+        &["std::sync::atomic::AtomicU8"],
+    ),
+    builtin(
+        "AtomicU16",
+        TypeGroup::Concurrency,
+        "std::sync::atomic",
+        "core",
+        &["std::sync::atomic::AtomicU16"],
+    ),
+    builtin(
+        "AtomicU32",
+        TypeGroup::Concurrency,
+        "std::sync::atomic",
+        "core",
+        &["std::sync::atomic::AtomicU32"],
+    ),
+    builtin(
+        "AtomicU64",
+        TypeGroup::Concurrency,
+        "std::sync::atomic",
+        "core",
+        &["std::sync::atomic::AtomicU64"],
+    ),
+    builtin(
+        "AtomicUsize",
+        TypeGroup::Concurrency,
+        "std::sync::atomic",
+        "core",
+        &["std::sync::atomic::AtomicUsize"],
+    ),
+    builtin(
+        "AtomicPtr",
+        TypeGroup::Concurrency,
+        "std::sync::atomic",
+        "core",
+        &["std::sync::atomic::AtomicPtr"],
+    ),
+    builtin(
+        "Sender",
+        TypeGroup::Concurrency,
+        "std::sync::mpsc",
+        "std",
+        &["std::sync::mpsc::Sender"],
+    ),
+    builtin(
+        "SyncSender",
+        TypeGroup::Concurrency,
+        "std::sync::mpsc",
+        "std",
+        &["std::sync::mpsc::SyncSender"],
+    ),
+    builtin(
+        "Receiver",
+        TypeGroup::Concurrency,
+        "std::sync::mpsc",
+        "std",
+        &["std::sync::mpsc::Receiver"],
+    ),
+    builtin(
+        "JoinHandle",
+        TypeGroup::Concurrency,
+        "std::thread",
+        "std",
+        &["std::thread::JoinHandle"],
+    ),
+    // Filesystem, OS and C string, networking and time types
+    builtin(
+        "PathBuf",
+        TypeGroup::System,
+        "std::path",
+        "std",
+        &["std::path::PathBuf"],
+    ),
+    builtin(
+        "Path",
+        TypeGroup::System,
+        "std::path",
+        "std",
+        &["std::path::Path"],
+    ),
+    builtin(
+        "OsString",
+        TypeGroup::System,
+        "std::ffi",
+        "std",
+        &["std::ffi::OsString"],
+    ),
+    builtin(
+        "OsStr",
+        TypeGroup::System,
+        "std::ffi",
+        "std",
+        &["std::ffi::OsStr"],
+    ),
+    builtin(
+        "CString",
+        TypeGroup::System,
+        "std::ffi",
+        "alloc",
+        &["std::ffi::CString"],
+    ),
+    builtin(
+        "CStr",
+        TypeGroup::System,
+        "std::ffi",
+        "core",
+        &["std::ffi::CStr"],
+    ),
+    builtin(
+        "IpAddr",
+        TypeGroup::System,
+        "std::net",
+        "core",
+        &["std::net::IpAddr"],
+    ),
+    builtin(
+        "Ipv4Addr",
+        TypeGroup::System,
+        "std::net",
+        "core",
+        &["std::net::Ipv4Addr"],
+    ),
+    builtin(
+        "Ipv6Addr",
+        TypeGroup::System,
+        "std::net",
+        "core",
+        &["std::net::Ipv6Addr"],
+    ),
+    builtin(
+        "SocketAddr",
+        TypeGroup::System,
+        "std::net",
+        "core",
+        &["std::net::SocketAddr"],
+    ),
+    builtin(
+        "Duration",
+        TypeGroup::System,
+        "std::time",
+        "core",
+        &["std::time::Duration"],
+    ),
+    builtin(
+        "Instant",
+        TypeGroup::System,
+        "std::time",
+        "std",
+        &["std::time::Instant"],
+    ),
+    builtin(
+        "SystemTime",
+        TypeGroup::System,
+        "std::time",
+        "std",
+        &["std::time::SystemTime"],
+    ),
+    // Numeric wrapper types
+    builtin(
+        "NonZeroI8",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZeroI8"],
+    ),
+    builtin(
+        "NonZeroI16",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZeroI16"],
+    ),
+    builtin(
+        "NonZeroI32",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZeroI32"],
+    ),
+    builtin(
+        "NonZeroI64",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZeroI64"],
+    ),
+    builtin(
+        "NonZeroI128",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZeroI128"],
+    ),
+    builtin(
+        "NonZeroIsize",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZeroIsize"],
+    ),
+    builtin(
+        "NonZeroU8",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZeroU8"],
+    ),
+    builtin(
+        "NonZeroU16",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZeroU16"],
+    ),
+    builtin(
+        "NonZeroU32",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZeroU32"],
+    ),
+    builtin(
+        "NonZeroU64",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZeroU64"],
+    ),
+    builtin(
+        "NonZeroU128",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZeroU128"],
+    ),
+    builtin(
+        "NonZeroUsize",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZeroUsize"],
+    ),
+    builtin(
+        "NonZero",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::NonZero"],
+    ),
+    builtin(
+        "Wrapping",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::Wrapping"],
+    ),
+    builtin(
+        "Saturating",
+        TypeGroup::Numeric,
+        "std::num",
+        "core",
+        &["std::num::Saturating"],
+    ),
+    // Marker and pinning types
+    builtin(
+        "PhantomData",
+        TypeGroup::Markers,
+        "std::marker",
+        "core",
+        &["std::marker::PhantomData"],
+    ),
+    builtin(
+        "PhantomPinned",
+        TypeGroup::Markers,
+        "std::marker",
+        "core",
+        &["std::marker::PhantomPinned"],
+    ),
+    builtin(
+        "Pin",
+        TypeGroup::Markers,
+        "std::pin",
+        "core",
+        &["std::pin::Pin"],
+    ),
+    builtin(
+        "ManuallyDrop",
+        TypeGroup::Markers,
+        "std::mem",
+        "core",
+        &["std::mem::ManuallyDrop"],
+    ),
+];
+
+/// The crates tried, in order, when matching a bare type name.
+const BARE_NAME_CRATES: &[&str] = &["std", "core", "alloc"];
+
+/// The modules tried, in order within each crate, when matching a bare type name.
+const BARE_NAME_MODULES: &[&str] = &[
+    "",
+    "primitive",
+    "string",
+    "vec",
+    "collections",
+    "collections::hash_map",
+    "collections::hash_set",
+    "collections::btree_map",
+    "collections::btree_set",
+    "collections::linked_list",
+    "option",
+    "result",
+    "boxed",
+    "borrow",
+    "cell",
+    "sync",
+    "sync::atomic",
+    "sync::mpsc",
+    "rc",
+    "thread",
+    "path",
+    "ffi",
+    "net",
+    "time",
+    "num",
+    "marker",
+    "pin",
+    "mem",
+];
+
+/// Return `true` if a type group is enabled.
+pub(crate) const fn group_enabled(groups: TypeGroups, group: TypeGroup) -> bool {
+    match group {
+        TypeGroup::Primitives => groups.primitives,
+        TypeGroup::Prelude => groups.prelude,
+        TypeGroup::CommonStd => groups.common_std,
+        TypeGroup::Concurrency => groups.concurrency,
+        TypeGroup::System => groups.system,
+        TypeGroup::Numeric => groups.numeric,
+        TypeGroup::Markers => groups.markers,
     }
+}
 
-    None
+/// Lookup tables over [`BUILTIN_TYPES`], built once on first use.
+struct BuiltinIndex {
+    /// The builtin types written as each path, in table order.
+    by_path: HashMap<Cow<'static, str>, Vec<&'static BuiltinType>>,
+    /// The paths a bare type name may refer to, most likely first.
+    by_name: HashMap<&'static str, Vec<(Cow<'static, str>, &'static BuiltinType)>>,
+    /// The canonical names whose shortened paths may be matched by suffix.
+    shortening_names: HashSet<&'static str>,
+    /// The trailing parts of the `std` paths of those types that keep at
+    /// least one module, such as `collections::HashMap` and
+    /// `hash_map::HashMap` for `std::collections::hash_map::HashMap`.
+    shortened_paths: HashSet<&'static str>,
 }
 
-/// Rust language primitive type mappings
-#[cfg(feature = "static-resolver")]
-fn get_primitive_mapping_static(path: &str) -> Option<&'static str> {
-    match path {
-        // Primitive integer types (actual language primitives)
-        "std::primitive::i8" | "core::primitive::i8" | "std::i8" | "core::i8" => Some("i8"),
-        "std::primitive::i16" | "core::primitive::i16" | "std::i16" | "core::i16" => Some("i16"),
-        "std::primitive::i32" | "core::primitive::i32" | "std::i32" | "core::i32" => Some("i32"),
-        "std::primitive::i64" | "core::primitive::i64" | "std::i64" | "core::i64" => Some("i64"),
-        "std::primitive::i128" | "core::primitive::i128" | "std::i128" | "core::i128" => {
-            Some("i128")
-        }
-        "std::primitive::isize" | "core::primitive::isize" | "std::isize" | "core::isize" => {
-            Some("isize")
-        }
+impl BuiltinIndex {
+    fn get() -> &'static Self {
+        static INDEX: OnceLock<BuiltinIndex> = OnceLock::new();
+        INDEX.get_or_init(Self::build)
+    }
 
-        // Primitive unsigned integer types
-        "std::primitive::u8" | "core::primitive::u8" | "std::u8" | "core::u8" => Some("u8"),
-        "std::primitive::u16" | "core::primitive::u16" | "std::u16" | "core::u16" => Some("u16"),
-        "std::primitive::u32" | "core::primitive::u32" | "std::u32" | "core::u32" => Some("u32"),
-        "std::primitive::u64" | "core::primitive::u64" | "std::u64" | "core::u64" => Some("u64"),
-        "std::primitive::u128" | "core::primitive::u128" | "std::u128" | "core::u128" => {
-            Some("u128")
+    fn build() -> Self {
+        let mut by_path: HashMap<_, Vec<_>> = HashMap::new();
+        let mut by_name: HashMap<_, Vec<_>> = HashMap::new();
+        let mut shortening_names = HashSet::new();
+        let mut shortened_paths = HashSet::new();
+        for builtin in BUILTIN_TYPES {
+            // Primitives are never written as shortened module paths
+            if builtin.group != TypeGroup::Primitives {
+                shortening_names.insert(builtin.canonical);
+                for path in builtin.paths {
+                    shortened_paths.extend(
+                        path.match_indices("::")
+                            .map(|(index, _)| &path[index + 2..])
+                            .filter(|suffix| suffix.contains("::")),
+                    );
+                }
+            }
+            for path in builtin.all_paths() {
+                if bare_name_rank(&path, builtin.canonical).is_some() {
+                    by_name
+                        .entry(builtin.canonical)
+                        .or_default()
+                        .push((path.clone(), builtin));
+                }
+                by_path.entry(path).or_default().push(builtin);
+            }
         }
-        "std::primitive::usize" | "core::primitive::usize" | "std::usize" | "core::usize" => {
-            Some("usize")
+        for paths in by_name.values_mut() {
+            // Stable, so equally ranked paths keep their table order
+            paths.sort_by_key(|(path, builtin)| bare_name_rank(path, builtin.canonical));
         }
-
-        // Primitive floating point types
-        "std::primitive::f32" | "core::primitive::f32" | "std::f32" | "core::f32" => Some("f32"),
-        "std::primitive::f64" | "core::primitive::f64" | "std::f64" | "core::f64" => Some("f64"),
-
-        // Other primitive types
-        "std::primitive::bool" | "core::primitive::bool" | "std::bool" | "core::bool" => {
-            Some("bool")
+        Self {
+            by_path,
+            by_name,
+            shortening_names,
+            shortened_paths,
         }
-        "std::primitive::char" | "core::primitive::char" | "std::char" | "core::char" => {
-            Some("char")
-        }
-        "std::primitive::str" | "core::primitive::str" | "std::str" | "core::str" => Some("str"),
-
-        _ => None,
     }
 }
 
-/// Rust prelude type mappings (automatically imported in every module)
-#[cfg(feature = "static-resolver")]
-fn get_prelude_mapping_static(path: &str) -> Option<&'static str> {
-    match path {
-        // String type
-        "std::string::String" => Some("String"),
-
-        // Vec type
-        "std::vec::Vec" | "alloc::vec::Vec" => Some("Vec"),
-
-        // Option type
-        "std::option::Option" | "core::option::Option" => Some("Option"),
-
-        // Result type
-        "std::result::Result" => Some("Result"),
-
-        // Box type
-        "std::boxed::Box" | "alloc::boxed::Box" => Some("Box"),
-
-        _ => None,
-    }
+/// Built-in type mappings for primitives and common standard library types,
+/// together with the group that provided the match.
+pub(crate) fn get_builtin_mapping(
+    path: &str,
+    groups: TypeGroups,
+) -> Option<(&'static str, TypeGroup)> {
+    BuiltinIndex::get()
+        .by_path
+        .get(path)?
+        .iter()
+        .find(|builtin| group_enabled(groups, builtin.group))
+        .map(|builtin| (builtin.canonical, builtin.group))
 }
 
-/// Common std library type mappings (not in prelude but frequently used)
-#[cfg(feature = "static-resolver")]
-fn get_common_std_mapping_static(path: &str) -> Option<&'static str> {
-    match path {
-        // HashMap
-        "std::collections::HashMap" | "std::collections::hash_map::HashMap" => Some("HashMap"),
-
-        // HashSet
-        "std::collections::HashSet" | "std::collections::hash_set::HashSet" => Some("HashSet"),
-
-        // BTreeMap
-        "std::collections::BTreeMap" | "std::collections::btree_map::BTreeMap" => Some("BTreeMap"),
-
-        // BTreeSet
-        "std::collections::BTreeSet" | "std::collections::btree_set::BTreeSet" => Some("BTreeSet"),
-
-        // LinkedList
-        "std::collections::LinkedList" | "std::collections::linked_list::LinkedList" => {
-            Some("LinkedList")
-        }
-
-        // Cow
-        "std::borrow::Cow" => Some("Cow"),
-
-        // RefCell
-        "std::cell::RefCell" | "core::cell::RefCell" => Some("RefCell"),
-
-        // Arc
-        "std::sync::Arc" | "alloc::sync::Arc" => Some("Arc"),
-
-        // Rc
-        "std::rc::Rc" | "alloc::rc::Rc" => Some("Rc"),
-
-        _ => None,
-    }
+/// Find the builtin path a bare type name most likely refers to.
+///
+/// Paths are preferred by crate (`std`, `core`, then `alloc`), then by module
+/// in the order of [`BARE_NAME_MODULES`]. Returns the path, the canonical
+/// name and the group. A primitive is its own pattern, since a bare `i32`
+/// names the primitive rather than a path such as `std::i32`.
+pub(crate) fn get_builtin_pattern(
+    base_type: &str,
+    groups: TypeGroups,
+) -> Option<(&'static str, &'static str, TypeGroup)> {
+    BuiltinIndex::get()
+        .by_name
+        .get(base_type)?
+        .iter()
+        .find(|(_, builtin)| group_enabled(groups, builtin.group))
+        .map(|(path, builtin)| match builtin.group {
+            TypeGroup::Primitives => (builtin.canonical, builtin.canonical, builtin.group),
+            _ => (path.as_ref(), builtin.canonical, builtin.group),
+        })
 }
 
-/// Fallback built-in type resolution when PHF is not available
-#[cfg(not(feature = "static-resolver"))]
-pub(crate) fn get_builtin_mapping(path: &str, groups: TypeGroups) -> Option<&'static str> {
-    // Try primitives first if enabled
-    if groups.primitives {
-        if let Some(result) = get_primitive_mapping(path) {
-            return Some(result);
-        }
+/// Check if a path could reasonably be a shortening of a standard library path.
+///
+/// The base type must be a builtin type other than a primitive, and the path
+/// must either start with `std`, `core` or `alloc`, or be a trailing part of
+/// one of the type's paths, such as `collections::HashMap` or
+/// `sync::mpsc::Sender`. Paths into other crates that reuse std module names,
+/// such as `tokio::sync::Mutex`, are not shortenings.
+pub(crate) fn could_be_stdlib_shortening(normalized: &str, base_type: &str) -> bool {
+    let index = BuiltinIndex::get();
+    if !index.shortening_names.contains(base_type) {
+        return false;
     }
-
-    // Try prelude types if enabled
-    if groups.prelude {
-        if let Some(result) = get_prelude_mapping(path) {
-            return Some(result);
-        }
-    }
-
-    // Try common std types if enabled
-    if groups.common_std {
-        if let Some(result) = get_common_std_mapping(path) {
-            return Some(result);
-        }
-    }
-
-    None
+    let krate = normalized.split("::").next().unwrap_or(normalized);
+    BARE_NAME_CRATES.contains(&krate) || index.shortened_paths.contains(normalized)
 }
 
-/// Rust language primitive type mappings (non-static fallback)
-#[cfg(not(feature = "static-resolver"))]
-fn get_primitive_mapping(path: &str) -> Option<&'static str> {
-    match path {
-        // Primitive integer types (actual language primitives)
-        "std::primitive::i8" | "core::primitive::i8" | "std::i8" | "core::i8" => Some("i8"),
-        "std::primitive::i16" | "core::primitive::i16" | "std::i16" | "core::i16" => Some("i16"),
-        "std::primitive::i32" | "core::primitive::i32" | "std::i32" | "core::i32" => Some("i32"),
-        "std::primitive::i64" | "core::primitive::i64" | "std::i64" | "core::i64" => Some("i64"),
-        "std::primitive::i128" | "core::primitive::i128" | "std::i128" | "core::i128" => {
-            Some("i128")
-        }
-        "std::primitive::isize" | "core::primitive::isize" | "std::isize" | "core::isize" => {
-            Some("isize")
-        }
-
-        // Primitive unsigned integer types
-        "std::primitive::u8" | "core::primitive::u8" | "std::u8" | "core::u8" => Some("u8"),
-        "std::primitive::u16" | "core::primitive::u16" | "std::u16" | "core::u16" => Some("u16"),
-        "std::primitive::u32" | "core::primitive::u32" | "std::u32" | "core::u32" => Some("u32"),
-        "std::primitive::u64" | "core::primitive::u64" | "std::u64" | "core::u64" => Some("u64"),
-        "std::primitive::u128" | "core::primitive::u128" | "std::u128" | "core::u128" => {
-            Some("u128")
-        }
-        "std::primitive::usize" | "core::primitive::usize" | "std::usize" | "core::usize" => {
-            Some("usize")
-        }
-
-        // Primitive floating point types
-        "std::primitive::f32" | "core::primitive::f32" | "std::f32" | "core::f32" => Some("f32"),
-        "std::primitive::f64" | "core::primitive::f64" | "std::f64" | "core::f64" => Some("f64"),
-
-        // Other primitive types
-        "std::primitive::bool" | "core::primitive::bool" | "std::bool" | "core::bool" => {
-            Some("bool")
-        }
-        "std::primitive::char" | "core::primitive::char" | "std::char" | "core::char" => {
-            Some("char")
-        }
-        "std::primitive::str" | "core::primitive::str" | "std::str" | "core::str" => Some("str"),
+/// The canonical names of the builtin types of the enabled type groups, in table order.
+///
+/// A name shared by several types, such as `Weak`, is returned once.
+pub(crate) fn canonical_names(groups: TypeGroups) -> impl Iterator<Item = &'static str> {
+    groups
+        .builtin_types()
+        .enumerate()
+        .filter(move |(index, builtin)| {
+            !groups
+                .builtin_types()
+                .take(*index)
+                .any(|earlier| earlier.canonical == builtin.canonical)
+        })
+        .map(|(_, builtin)| builtin.canonical)
+}
 
-        _ => None,
+impl TypeGroups {
+    /// Return the builtin types of the enabled type groups, in table order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::TypeGroups;
+    ///
+    /// let names: Vec<&str> = TypeGroups::PRELUDE
+    ///     .builtin_types()
+    ///     .map(|builtin| builtin.canonical)
+    ///     .filter(|name| name.starts_with(char::is_uppercase))
+    ///     .collect();
+    /// assert_eq!(names, ["String", "Vec", "Option", "Result", "Box"]);
+    /// ```
+    pub fn builtin_types(self) -> impl Iterator<Item = &'static BuiltinType> {
+        BUILTIN_TYPES
+            .iter()
+            .filter(move |builtin| group_enabled(self, builtin.group))
     }
 }
 
-/// Rust prelude type mappings (non-static fallback)
-#[cfg(not(feature = "static-resolver"))]
-fn get_prelude_mapping(path: &str) -> Option<&'static str> {
-    match path {
-        // String type
-        "std::string::String" => Some("String"),
-
-        // Vec type
-        "std::vec::Vec" | "alloc::vec::Vec" => Some("Vec"),
-
-        // Option type
-        "std::option::Option" | "core::option::Option" => Some("Option"),
-
-        // Result type
-        "std::result::Result" => Some("Result"),
-
-        // Box type
-        "std::boxed::Box" | "alloc::boxed::Box" => Some("Box"),
-
-        _ => None,
+impl TypeGroup {
+    /// Return the builtin types this type group provides, in table order.
+    pub fn builtin_types(self) -> impl Iterator<Item = &'static BuiltinType> {
+        BUILTIN_TYPES
+            .iter()
+            .filter(move |builtin| builtin.group == self)
     }
 }
 
-/// Common std library type mappings (non-static fallback)
-#[cfg(not(feature = "static-resolver"))]
-fn get_common_std_mapping(path: &str) -> Option<&'static str> {
-    match path {
-        // HashMap
-        "std::collections::HashMap" | "std::collections::hash_map::HashMap" => Some("HashMap"),
-
-        // HashSet
-        "std::collections::HashSet" | "std::collections::hash_set::HashSet" => Some("HashSet"),
-
-        // BTreeMap
-        "std::collections::BTreeMap" | "std::collections::btree_map::BTreeMap" => Some("BTreeMap"),
-
-        // BTreeSet
-        "std::collections::BTreeSet" | "std::collections::btree_set::BTreeSet" => Some("BTreeSet"),
-
-        // LinkedList
-        "std::collections::LinkedList" | "std::collections::linked_list::LinkedList" => {
-            Some("LinkedList")
-        }
-
-        // Cow
-        "std::borrow::Cow" => Some("Cow"),
-
-        // RefCell
-        "std::cell::RefCell" | "core::cell::RefCell" => Some("RefCell"),
-
-        // Arc
-        "std::sync::Arc" | "alloc::sync::Arc" => Some("Arc"),
-
-        // Rc
-        "std::rc::Rc" | "alloc::rc::Rc" => Some("Rc"),
-
-        _ => None,
-    }
+/// Rank a builtin path as the meaning of a bare type name, lower first.
+fn bare_name_rank(path: &str, base_type: &str) -> Option<(usize, usize)> {
+    let module_path = path.strip_suffix(base_type)?.strip_suffix("::")?;
+    let (krate, module) = module_path.split_once("::").unwrap_or((module_path, ""));
+    let crate_rank = BARE_NAME_CRATES.iter().position(|name| *name == krate)?;
+    let module_rank = BARE_NAME_MODULES.iter().position(|name| *name == module)?;
+    Some((crate_rank, module_rank))
 }
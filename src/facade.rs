@@ -0,0 +1,39 @@
+//! Facade rules treating paths under one crate or module as paths under another.
+//!
+//! A facade crate re-exports the items of another crate or module, as
+//! `hashbrown` mirrors `std::collections`. A facade rule `(prefix, target)`
+//! rewrites a path starting with the segments of `prefix` to start with
+//! `target` instead, and looks the rewritten path up as a full path.
+//!
+//! Rules are consulted after exact keys, glob keys and matchers, and before
+//! the shortening and suffix heuristics. The rewritten path is not rewritten
+//! again, so rules cannot loop.
+
+use crate::resolution::Match;
+use crate::{MappingStorage, PathResolver};
+
+impl<M> PathResolver<M>
+where
+    M: MappingStorage,
+{
+    /// Look up a normalized path rewritten by each facade rule, in order.
+    pub(crate) fn resolve_facade<'p>(&self, normalized: &str) -> Option<Match<'_, 'p>> {
+        self.mappings.facades().find_map(|(prefix, target)| {
+            let rewritten = rewrite(normalized, prefix, target)?;
+            self.resolve_full_path(&rewritten)
+                .map(Match::into_owned_path)
+        })
+    }
+}
+
+/// Replace the leading segments `prefix` of a normalized path with `target`.
+fn rewrite(normalized: &str, prefix: &str, target: &str) -> Option<String> {
+    let rest = normalized.strip_prefix(prefix)?;
+    if !rest.is_empty() && !rest.starts_with("::") {
+        return None;
+    }
+    match (target.is_empty(), rest.strip_prefix("::")) {
+        (true, Some(rest)) => Some(rest.to_string()),
+        _ => Some(format!("{target}{rest}")),
+    }
+}
@@ -0,0 +1,208 @@
+//! Path pattern keys with segment wildcards, such as `my_crate::*::UserId`.
+//!
+//! A glob key is a path whose segments may be:
+//! - `*`, matching exactly one segment
+//! - `**`, matching any number of segments, including none
+//! - `{a,b}`, matching any one of the listed segments
+//!
+//! Glob keys are matched against the full normalized path after exact keys
+//! and before the shortening and suffix heuristics. When several glob keys
+//! match, the highest [`MappingStorage::priority`] wins, then the key with the
+//! most literal segments, then the most alternations, then the fewest `**`.
+//!
+//! Glob keys are compiled into a [`GlobPattern`] once, when
+//! [`DynamicStorage`](crate::DynamicStorage) or
+//! [`IndexedStorage`](crate::IndexedStorage) adds them, and lookups match
+//! against the compiled patterns; see [`MappingStorage::glob_patterns`].
+//! Other storages, such as a plain phf map, have their glob keys compiled on
+//! each lookup that reaches this step.
+
+use std::borrow::Cow;
+use std::str::Split;
+
+use crate::resolution::{Match, MatchedPattern};
+use crate::{MappingStorage, PathResolver, Strategy};
+
+/// Return `true` if a normalized key contains glob segments.
+pub(crate) fn is_glob(key: &str) -> bool {
+    !key.starts_with('<')
+        && key
+            .split("::")
+            .any(|segment| segment == "*" || segment == "**" || segment.starts_with('{'))
+}
+
+/// A glob key, such as `my_crate::*::UserId`, compiled once.
+///
+/// # Examples
+///
+/// ```
+/// use desynt::GlobPattern;
+///
+/// let pattern = GlobPattern::parse("my_crate::{api,db}::**::UserId").unwrap();
+/// assert_eq!(pattern.key(), "my_crate::{api,db}::**::UserId");
+/// assert!(pattern.matches("my_crate::api::v1::UserId"));
+/// assert!(pattern.matches("my_crate::db::UserId"));
+/// assert!(!pattern.matches("my_crate::web::UserId"));
+/// assert!(GlobPattern::parse("my_crate::UserId").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobPattern {
+    key: String,
+    segments: Vec<GlobSegment>,
+    specificity: (usize, usize, usize),
+}
+
+/// A compiled segment of a glob key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum GlobSegment {
+    /// `*`, matching exactly one segment.
+    One,
+    /// `**`, matching any number of segments.
+    AnyDepth,
+    /// `{a,b}`, matching one of the listed segments.
+    OneOf(Vec<String>),
+    /// A literal segment.
+    Literal(String),
+}
+
+impl GlobPattern {
+    /// Compile a normalized key, returning `None` unless it contains glob segments.
+    pub fn parse(key: &str) -> Option<Self> {
+        if !is_glob(key) {
+            return None;
+        }
+        let segments: Vec<GlobSegment> = key.split("::").map(GlobSegment::parse).collect();
+        let specificity = specificity(&segments);
+        Some(Self {
+            key: key.to_string(),
+            segments,
+            specificity,
+        })
+    }
+
+    /// Return the key the pattern was compiled from.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Return `true` if the pattern matches the segments of a normalized path.
+    pub fn matches(&self, normalized: &str) -> bool {
+        matches_from(&self.segments, normalized.split("::"))
+    }
+}
+
+impl GlobSegment {
+    fn parse(segment: &str) -> Self {
+        if segment == "*" {
+            return Self::One;
+        }
+        if segment == "**" {
+            return Self::AnyDepth;
+        }
+        match segment
+            .strip_prefix('{')
+            .and_then(|segment| segment.strip_suffix('}'))
+        {
+            Some(alternatives) => Self::OneOf(
+                alternatives
+                    .split(',')
+                    .map(|alternative| alternative.trim().to_string())
+                    .collect(),
+            ),
+            None => Self::Literal(segment.to_string()),
+        }
+    }
+
+    fn matches(&self, segment: &str) -> bool {
+        match self {
+            Self::One | Self::AnyDepth => true,
+            Self::OneOf(alternatives) => alternatives
+                .iter()
+                .any(|alternative| alternative == segment),
+            Self::Literal(literal) => literal == segment,
+        }
+    }
+}
+
+fn matches_from(globs: &[GlobSegment], mut segments: Split<'_, &str>) -> bool {
+    match globs.split_first() {
+        None => segments.next().is_none(),
+        Some((GlobSegment::AnyDepth, rest)) => loop {
+            if matches_from(rest, segments.clone()) {
+                return true;
+            }
+            if segments.next().is_none() {
+                return false;
+            }
+        },
+        Some((glob, rest)) => match segments.next() {
+            Some(segment) => glob.matches(segment) && matches_from(rest, segments),
+            None => false,
+        },
+    }
+}
+
+/// How specific a glob key is: literal segments, then alternations,
+/// then the fewest `**` segments.
+fn specificity(segments: &[GlobSegment]) -> (usize, usize, usize) {
+    let mut literals = 0;
+    let mut alternations = 0;
+    let mut any_depth = 0;
+    for segment in segments {
+        match segment {
+            GlobSegment::One => {}
+            GlobSegment::AnyDepth => any_depth += 1,
+            GlobSegment::OneOf(_) => alternations += 1,
+            GlobSegment::Literal(_) => literals += 1,
+        }
+    }
+    (literals, alternations, usize::MAX - any_depth)
+}
+
+impl<M> PathResolver<M>
+where
+    M: MappingStorage,
+{
+    /// Find the best glob key matching a normalized path.
+    ///
+    /// Explicit priorities win first, then the most specific key, then
+    /// lexicographic order.
+    pub(crate) fn resolve_glob<'p>(&self, normalized: &str) -> Option<Match<'_, 'p>> {
+        // Storages without compiled patterns have their keys compiled on each lookup
+        let patterns: Cow<'_, [GlobPattern]> = match self.mappings.glob_patterns() {
+            Some(patterns) => Cow::Borrowed(patterns),
+            None => Cow::Owned(
+                self.mappings
+                    .glob_keys()
+                    .filter_map(GlobPattern::parse)
+                    .collect(),
+            ),
+        };
+        let (index, _) = patterns
+            .iter()
+            .enumerate()
+            .filter(|(_, pattern)| pattern.matches(normalized))
+            .max_by(|(_, a), (_, b)| {
+                self.priority(&a.key)
+                    .cmp(&self.priority(&b.key))
+                    .then_with(|| a.specificity.cmp(&b.specificity))
+                    .then_with(|| b.key.cmp(&a.key))
+            })?;
+
+        let pattern = match patterns {
+            Cow::Borrowed(patterns) => MatchedPattern::Key(patterns[index].key()),
+            Cow::Owned(mut patterns) => MatchedPattern::Owned(patterns.swap_remove(index).key),
+        };
+        let key = match &pattern {
+            MatchedPattern::Key(key) => key,
+            MatchedPattern::Owned(key) => key.as_str(),
+            MatchedPattern::Path(path) => path,
+        };
+        Some(Match {
+            canonical: self.mappings.get(key)?,
+            pattern,
+            group: None,
+            strategy: Strategy::Glob,
+        })
+    }
+}
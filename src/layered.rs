@@ -0,0 +1,200 @@
+//! Resolution through several resolvers stacked in order of precedence.
+//!
+//! A team can ship a shared baseline, such as a static phf table, and let a
+//! project override a few entries in a dynamic storage layered on top. Each
+//! layer is a complete [`PathResolver`] with its own storage and
+//! [`TypeGroups`](crate::TypeGroups).
+
+use std::fmt;
+
+use syn::Path;
+
+use crate::{MappingStorage, PathResolver, Resolution, Resolve};
+
+/// Several resolvers consulted in order of precedence, reporting which one answered.
+///
+/// Layers are consulted in the order they were added, so overrides are added
+/// first and the baseline last. A match that is not
+/// [heuristic](Resolution::is_heuristic), such as an exact, glob or matcher
+/// match, in any layer beats a heuristic match, such as a suffix or bare type
+/// name match, in an earlier layer. Among non-heuristic matches, and among
+/// heuristic matches, the earliest layer wins.
+///
+/// # Examples
+///
+/// ```
+/// use desynt::{DynamicPathResolver, LayeredResolver, PathResolver, TypeGroups};
+/// use std::collections::HashMap;
+/// use syn::Path;
+///
+/// let mut baseline = HashMap::new();
+/// baseline.insert("sqlx::Pool".to_string(), "DbPool".to_string());
+/// baseline.insert("my_crate::UserId".to_string(), "UserId".to_string());
+///
+/// let mut overrides = DynamicPathResolver::default();
+/// overrides.add_mapping("my_crate::UserId", "Identifier");
+///
+/// let resolver = LayeredResolver::new()
+///     .with_layer("overrides", overrides)
+///     .with_layer("baseline", PathResolver::new(baseline, TypeGroups::ALL));
+///
+/// let path: Path = syn::parse_str("my_crate::UserId").unwrap();
+/// let found = resolver.resolve_detailed(&path).unwrap();
+/// assert_eq!(found.resolution.canonical, "Identifier");
+/// assert_eq!(found.layer, "overrides");
+///
+/// let path: Path = syn::parse_str("sqlx::Pool").unwrap();
+/// assert_eq!(resolver.resolve(&path), Some("DbPool"));
+///
+/// let path: Path = syn::parse_str("Option<u8>").unwrap();
+/// assert_eq!(resolver.resolve_detailed(&path).unwrap().layer, "baseline");
+/// ```
+#[derive(Default)]
+pub struct LayeredResolver<'a> {
+    layers: Vec<Layer<'a>>,
+}
+
+/// A named resolver in a [`LayeredResolver`].
+struct Layer<'a> {
+    name: String,
+    resolver: Box<dyn LayerResolver + Send + Sync + 'a>,
+}
+
+/// The resolver methods a layer needs, independent of its storage type.
+trait LayerResolver: Resolve {
+    fn resolve_detailed(&self, path: &Path) -> Option<Resolution<'_>>;
+}
+
+impl<M> LayerResolver for PathResolver<M>
+where
+    M: MappingStorage,
+{
+    fn resolve_detailed(&self, path: &Path) -> Option<Resolution<'_>> {
+        PathResolver::resolve_detailed(self, path)
+    }
+}
+
+/// A [`Resolution`] together with the layer that produced it.
+///
+/// Returned by [`LayeredResolver::resolve_detailed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayeredResolution<'a> {
+    /// The name of the layer that answered.
+    pub layer: &'a str,
+    /// The position of the layer that answered, `0` being the highest precedence.
+    pub index: usize,
+    /// How the layer resolved the path.
+    pub resolution: Resolution<'a>,
+}
+
+impl<'a> LayeredResolver<'a> {
+    /// Create a resolver with no layers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a layer below the existing ones, builder style.
+    pub fn with_layer<S, M>(mut self, name: S, resolver: PathResolver<M>) -> Self
+    where
+        S: Into<String>,
+        M: MappingStorage + Send + Sync + 'a,
+    {
+        self.push_layer(name, resolver);
+        self
+    }
+
+    /// Add a layer below the existing ones.
+    ///
+    /// The new layer has the lowest precedence.
+    pub fn push_layer<S, M>(&mut self, name: S, resolver: PathResolver<M>)
+    where
+        S: Into<String>,
+        M: MappingStorage + Send + Sync + 'a,
+    {
+        self.layers.push(Layer {
+            name: name.into(),
+            resolver: Box::new(resolver),
+        });
+    }
+
+    /// Return the layer names, highest precedence first.
+    pub fn layer_names(&self) -> impl Iterator<Item = &str> {
+        self.layers.iter().map(|layer| layer.name.as_str())
+    }
+
+    /// Return the number of layers.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Return `true` if there are no layers.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Resolve a syn [`Path`] to its canonical type name.
+    ///
+    /// See [`LayeredResolver::resolve_detailed`] for how the layer is chosen.
+    pub fn resolve(&self, path: &Path) -> Option<&str> {
+        self.resolve_detailed(path)
+            .map(|found| found.resolution.canonical)
+    }
+
+    /// Resolve a syn [`Path`] and report which layer answered.
+    ///
+    /// The first layer with a match that is not heuristic answers. Without
+    /// one, the first layer with any match answers.
+    pub fn resolve_detailed(&self, path: &Path) -> Option<LayeredResolution<'_>> {
+        let mut heuristic = None;
+        for (index, layer) in self.layers.iter().enumerate() {
+            let Some(resolution) = layer.resolver.resolve_detailed(path) else {
+                continue;
+            };
+            let found = LayeredResolution {
+                layer: &layer.name,
+                index,
+                resolution,
+            };
+            if !found.resolution.is_heuristic() {
+                return Some(found);
+            }
+            heuristic.get_or_insert(found);
+        }
+        heuristic
+    }
+
+    /// Return `true` if any layer has a mapping for the given path.
+    ///
+    /// See [`PathResolver::has_mapping`].
+    pub fn has_mapping(&self, path: &Path) -> bool {
+        self.layers
+            .iter()
+            .any(|layer| layer.resolver.has_mapping(path))
+    }
+
+    /// Return an iterator over the canonical type names of every layer.
+    ///
+    /// Names known to several layers are returned once per layer.
+    pub fn canonical_types(&self) -> impl Iterator<Item = &str> {
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.resolver.canonical_types())
+    }
+
+    /// Return an iterator over the path patterns of every layer.
+    ///
+    /// Patterns registered in several layers are returned once per layer.
+    pub fn path_patterns(&self) -> impl Iterator<Item = &str> {
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.resolver.path_patterns())
+    }
+}
+
+impl fmt::Debug for LayeredResolver<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LayeredResolver")
+            .field("layers", &self.layer_names().collect::<Vec<_>>())
+            .finish()
+    }
+}
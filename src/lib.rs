@@ -5,7 +5,7 @@
 //! - **Raw identifier handling**: Strip `r#` prefixes from `Ident`, `Path`, and `PathSegment` objects
 //! - **Path resolution**: Map various type path representations to canonical forms
 //! - **Type group support**: Handle Rust primitives, prelude types, and common std types
-//! - **Multiple storage backends**: Use HashMap (dynamic) or phf::Map (static)
+//! - **Multiple storage backends**: Use HashMap or BTreeMap (dynamic), phf::Map or a sorted slice (static)
 //!
 //! # Features
 //!
@@ -56,6 +56,10 @@
 //! - **Primitives**: Language primitives (i8, u32, f64, bool, char, str, etc.)
 //! - **Prelude**: Types in the Rust prelude (String, Vec, Option, Result, Box)
 //! - **Common std**: Frequently used std types (HashMap, HashSet)
+//! - **Concurrency**: Synchronization, cell, atomic, channel and thread types (Mutex, Cell, AtomicUsize, JoinHandle)
+//! - **System**: Filesystem path, OS and C string, network address and time types (PathBuf, OsStr, IpAddr, Duration)
+//! - **Numeric**: Numeric wrapper types (NonZeroU8, NonZero, Wrapping, Saturating)
+//! - **Markers**: Marker and pinning types (PhantomData, PhantomPinned, Pin, ManuallyDrop)
 //!
 //! Use the predefined constants for common configurations:
 //! - [`TypeGroups::NONE`] - No type groups
@@ -63,13 +67,57 @@
 //! - [`TypeGroups::PRELUDE`] - Primitives + prelude types
 //! - [`TypeGroups::ALL`] - All type groups
 
-use std::collections::HashMap;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::rc::Rc;
+use std::sync::Arc;
 
+mod alias;
+mod cache;
+mod canonical;
 mod definitions;
+mod facade;
+mod glob;
+mod layered;
+mod matcher;
+mod normalized;
+mod pattern;
+mod policy;
+mod projection;
+mod resolution;
+mod resolve;
+mod scope;
+mod slice;
+mod storage;
+mod suffix;
+mod value;
 
 #[cfg(feature = "static-resolver")]
 use phf::Map;
-use syn::{Ident, Path, PathArguments, PathSegment};
+use syn::visit::Visit;
+use syn::visit_mut::VisitMut;
+use syn::{Ident, Path, PathSegment};
+
+pub use crate::alias::TypeAliases;
+pub use crate::cache::CachingResolver;
+pub use crate::canonical::{CanonicalPath, CanonicalType};
+pub use crate::definitions::{BUILTIN_TYPES, BuiltinType};
+pub use crate::glob::GlobPattern;
+pub use crate::layered::{LayeredResolution, LayeredResolver};
+pub use crate::matcher::{Matcher, NamedMatcher, PredicateMatcher};
+use crate::normalized::{NormalizedPath, trailing_paths};
+pub use crate::pattern::GenericPattern;
+pub use crate::policy::ResolutionPolicy;
+pub use crate::resolution::{AmbiguityError, Candidate, ProjectionError, Resolution, Strategy};
+use crate::resolution::{Match, MatchedPattern};
+pub use crate::resolve::Resolve;
+pub use crate::scope::Scope;
+pub use crate::slice::{SlicePathResolver, SliceStorage, create_slice_resolver};
+pub use crate::storage::{DynamicStorage, IndexedStorage};
+pub use crate::suffix::SuffixIndex;
+pub use crate::value::{MappingValue, ValueMap, ValueStorage};
 
 /// Storage backend for path-to-canonical-type mappings.
 ///
@@ -93,12 +141,113 @@ pub trait MappingStorage {
 
     /// Returns an iterator over all canonical type names in the storage.
     fn values(&self) -> Box<dyn Iterator<Item = &str> + '_>;
+
+    /// Returns the tie-breaking priority of a path pattern.
+    ///
+    /// When a bare type name matches several patterns, higher priorities win.
+    /// The default implementation gives every pattern priority `0`.
+    fn priority(&self, _path: &str) -> i32 {
+        0
+    }
+
+    /// Returns an iterator over the glob keys, such as `my_crate::*::UserId`.
+    ///
+    /// A glob key has segments that are `*` (one segment), `**` (any number of
+    /// segments) or `{a,b}` (one of the listed segments). The default
+    /// implementation filters [`keys`](MappingStorage::keys); storages may keep
+    /// glob keys in a separate index so exact keys are not scanned.
+    fn glob_keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        if self.is_empty() {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(self.keys().filter(|key| glob::is_glob(key)))
+    }
+
+    /// Returns the glob keys compiled once, if the storage keeps them.
+    ///
+    /// The default implementation returns `None`, and the keys from
+    /// [`glob_keys`](MappingStorage::glob_keys) are compiled on each lookup
+    /// that reaches glob matching. Storages should compile their keys with
+    /// [`GlobPattern::parse`] when they are added, as [`DynamicStorage`] and
+    /// [`IndexedStorage`] do.
+    fn glob_patterns(&self) -> Option<&[GlobPattern]> {
+        None
+    }
+
+    /// Returns an iterator over the pattern keys constraining generic
+    /// arguments, such as `Vec<u8>`.
+    ///
+    /// The default implementation filters [`keys`](MappingStorage::keys);
+    /// storages may keep these keys in a separate index so paths with generic
+    /// arguments do not scan every key.
+    fn generic_pattern_keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        if self.is_empty() {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(self.keys().filter(|key| pattern::is_generic_pattern(key)))
+    }
+
+    /// Returns the generic pattern keys parsed once, if the storage keeps them.
+    ///
+    /// The default implementation returns `None`, and the keys from
+    /// [`generic_pattern_keys`](MappingStorage::generic_pattern_keys) are
+    /// parsed on each lookup of a path with generic arguments. Storages
+    /// should parse their keys with [`GenericPattern::parse`] when they are
+    /// added, as [`DynamicStorage`] and [`IndexedStorage`] do.
+    fn generic_patterns(&self) -> Option<&[GenericPattern]> {
+        None
+    }
+
+    /// Returns an iterator over the path patterns equal to `suffix` or ending
+    /// with `::` followed by it, in no particular order.
+    ///
+    /// This is used to match bare type names against longer patterns, so
+    /// glob keys, which are not paths, are left out. The default
+    /// implementation scans [`keys`](MappingStorage::keys); storages may
+    /// answer from a [`SuffixIndex`] of their other keys instead.
+    fn keys_ending_with<'a: 'b, 'b>(
+        &'a self,
+        suffix: &'b str,
+    ) -> Box<dyn Iterator<Item = &'a str> + 'b> {
+        if self.is_empty() {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(self.keys().filter(move |key| {
+            key.strip_suffix(suffix)
+                .is_some_and(|rest| rest.is_empty() || rest.ends_with("::"))
+                && !glob::is_glob(key)
+        }))
+    }
+
+    /// Returns an iterator over the named [`Matcher`]s, in the order they are consulted.
+    ///
+    /// The default implementation has no matchers.
+    fn matchers(&self) -> Box<dyn Iterator<Item = (&str, &dyn Matcher)> + '_> {
+        Box::new(std::iter::empty())
+    }
+
+    /// Returns an iterator over the facade rules, as `(prefix, target)` pairs
+    /// in the order they are tried.
+    ///
+    /// A path starting with the segments of `prefix` is also looked up with
+    /// them replaced by `target`; see [`DynamicPathResolver::add_facade`].
+    /// The default implementation has no facade rules.
+    fn facades(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        Box::new(std::iter::empty())
+    }
 }
 
 /// Implementation of MappingStorage for HashMap (dynamic mappings).
-impl MappingStorage for HashMap<String, String> {
+///
+/// Keys may be any string type, such as `String`, `&'static str` or `Cow<str>`.
+impl<K, V, S> MappingStorage for HashMap<K, V, S>
+where
+    K: Borrow<str> + Eq + Hash,
+    V: MappingValue,
+    S: BuildHasher,
+{
     fn get(&self, path: &str) -> Option<&str> {
-        self.get(path).map(|s| s.as_str())
+        HashMap::get(self, path).map(MappingValue::canonical)
     }
 
     fn contains_key(&self, path: &str) -> bool {
@@ -114,67 +263,113 @@ impl MappingStorage for HashMap<String, String> {
     }
 
     fn keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
-        Box::new(HashMap::keys(self).map(|s| s.as_str()))
+        Box::new(HashMap::keys(self).map(Borrow::borrow))
     }
 
     fn values(&self) -> Box<dyn Iterator<Item = &str> + '_> {
-        Box::new(HashMap::values(self).map(|s| s.as_str()))
+        Box::new(HashMap::values(self).map(MappingValue::canonical))
     }
 }
 
-/// Implementation of MappingStorage for PHF Map (static mappings).
-#[cfg(feature = "static-resolver")]
-impl MappingStorage for Map<&'static str, &'static str> {
+impl<K, V, S> ValueStorage for HashMap<K, V, S>
+where
+    K: Borrow<str> + Eq + Hash,
+    V: MappingValue,
+    S: BuildHasher,
+{
+    type Value = V;
+
+    fn get_value(&self, path: &str) -> Option<&V> {
+        HashMap::get(self, path)
+    }
+}
+
+/// Implementation of MappingStorage for BTreeMap (dynamic mappings in key order).
+///
+/// [`keys`](MappingStorage::keys) and [`values`](MappingStorage::values)
+/// are returned in key order, for reproducible output.
+impl<K, V> MappingStorage for BTreeMap<K, V>
+where
+    K: Borrow<str> + Ord,
+    V: MappingValue,
+{
     fn get(&self, path: &str) -> Option<&str> {
-        Map::get(self, path).copied()
+        BTreeMap::get(self, path).map(MappingValue::canonical)
     }
 
     fn contains_key(&self, path: &str) -> bool {
-        Map::contains_key(self, path)
+        BTreeMap::contains_key(self, path)
     }
 
     fn len(&self) -> usize {
-        Map::len(self)
+        BTreeMap::len(self)
     }
 
     fn is_empty(&self) -> bool {
-        Map::is_empty(self)
+        BTreeMap::is_empty(self)
     }
 
     fn keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
-        Box::new(Map::keys(self).copied())
+        Box::new(BTreeMap::keys(self).map(Borrow::borrow))
     }
 
     fn values(&self) -> Box<dyn Iterator<Item = &str> + '_> {
-        Box::new(Map::values(self).copied())
+        Box::new(BTreeMap::values(self).map(MappingValue::canonical))
     }
 }
 
-/// Implementation of MappingStorage for &PHF Map (static mappings).
+impl<K, V> ValueStorage for BTreeMap<K, V>
+where
+    K: Borrow<str> + Ord,
+    V: MappingValue,
+{
+    type Value = V;
+
+    fn get_value(&self, path: &str) -> Option<&V> {
+        BTreeMap::get(self, path)
+    }
+}
+
+/// Implementation of MappingStorage for PHF Map (static mappings).
 #[cfg(feature = "static-resolver")]
-impl MappingStorage for &Map<&'static str, &'static str> {
+impl<V> MappingStorage for Map<&'static str, V>
+where
+    V: MappingValue,
+{
     fn get(&self, path: &str) -> Option<&str> {
-        Map::get(*self, path).copied()
+        Map::get(self, path).map(MappingValue::canonical)
     }
 
     fn contains_key(&self, path: &str) -> bool {
-        Map::contains_key(*self, path)
+        Map::contains_key(self, path)
     }
 
     fn len(&self) -> usize {
-        Map::len(*self)
+        Map::len(self)
     }
 
     fn is_empty(&self) -> bool {
-        Map::is_empty(*self)
+        Map::is_empty(self)
     }
 
     fn keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
-        Box::new(Map::keys(*self).copied())
+        Box::new(Map::keys(self).copied())
     }
 
     fn values(&self) -> Box<dyn Iterator<Item = &str> + '_> {
-        Box::new(Map::values(*self).copied())
+        Box::new(Map::values(self).map(MappingValue::canonical))
+    }
+}
+
+#[cfg(feature = "static-resolver")]
+impl<V> ValueStorage for Map<&'static str, V>
+where
+    V: MappingValue,
+{
+    type Value = V;
+
+    fn get_value(&self, path: &str) -> Option<&V> {
+        Map::get(self, path)
     }
 }
 
@@ -211,6 +406,102 @@ impl MappingStorage for EmptyStorage {
     }
 }
 
+impl ValueStorage for EmptyStorage {
+    type Value = str;
+
+    fn get_value(&self, _path: &str) -> Option<&str> {
+        None
+    }
+}
+
+/// Implement [`MappingStorage`] and [`ValueStorage`] for a pointer type by
+/// delegating every method, including the provided ones, to the pointee.
+macro_rules! delegate_storage {
+    ($pointer:ty) => {
+        impl<T> MappingStorage for $pointer
+        where
+            T: MappingStorage + ?Sized,
+        {
+            fn get(&self, path: &str) -> Option<&str> {
+                (**self).get(path)
+            }
+
+            fn contains_key(&self, path: &str) -> bool {
+                (**self).contains_key(path)
+            }
+
+            fn len(&self) -> usize {
+                (**self).len()
+            }
+
+            fn is_empty(&self) -> bool {
+                (**self).is_empty()
+            }
+
+            fn keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+                (**self).keys()
+            }
+
+            fn values(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+                (**self).values()
+            }
+
+            fn priority(&self, path: &str) -> i32 {
+                (**self).priority(path)
+            }
+
+            fn glob_keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+                (**self).glob_keys()
+            }
+
+            fn glob_patterns(&self) -> Option<&[GlobPattern]> {
+                (**self).glob_patterns()
+            }
+
+            fn generic_pattern_keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+                (**self).generic_pattern_keys()
+            }
+
+            fn generic_patterns(&self) -> Option<&[GenericPattern]> {
+                (**self).generic_patterns()
+            }
+
+            fn keys_ending_with<'a: 'b, 'b>(
+                &'a self,
+                suffix: &'b str,
+            ) -> Box<dyn Iterator<Item = &'a str> + 'b> {
+                (**self).keys_ending_with(suffix)
+            }
+
+            fn matchers(&self) -> Box<dyn Iterator<Item = (&str, &dyn Matcher)> + '_> {
+                (**self).matchers()
+            }
+
+            fn facades(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+                (**self).facades()
+            }
+        }
+
+        impl<T> ValueStorage for $pointer
+        where
+            T: ValueStorage + ?Sized,
+        {
+            type Value = T::Value;
+
+            fn get_value(&self, path: &str) -> Option<&T::Value> {
+                (**self).get_value(path)
+            }
+        }
+    };
+}
+
+// Storage behind a shared reference, such as `&'static Map<&str, &str>`, or a
+// smart pointer, such as `Box<dyn MappingStorage>`
+delegate_storage!(&T);
+delegate_storage!(Box<T>);
+delegate_storage!(Rc<T>);
+delegate_storage!(Arc<T>);
+
 /// Strips raw identifier prefixes (`r#`) from syn objects.
 ///
 /// This trait is implemented for [`Ident`], [`Path`], and [`PathSegment`],
@@ -253,20 +544,41 @@ impl HasRaw for Ident {
     }
 }
 
+/// Visitor that removes raw prefixes from every identifier it reaches,
+/// including those nested inside generic arguments.
+struct RawStripper;
+
+impl VisitMut for RawStripper {
+    fn visit_ident_mut(&mut self, ident: &mut Ident) {
+        *ident = ident.strip_raw();
+    }
+}
+
+/// Visitor that records whether any identifier it reaches is raw.
+struct RawFinder(bool);
+
+impl<'ast> Visit<'ast> for RawFinder {
+    fn visit_ident(&mut self, ident: &'ast Ident) {
+        self.0 |= ident.has_raw();
+    }
+}
+
 impl StripRaw for PathSegment {
     type Output = PathSegment;
 
+    /// Strips the segment ident and every ident inside its generic arguments.
     fn strip_raw(&self) -> Self::Output {
-        PathSegment {
-            ident: self.ident.strip_raw(),
-            arguments: self.arguments.clone(),
-        }
+        let mut segment = self.clone();
+        RawStripper.visit_path_segment_mut(&mut segment);
+        segment
     }
 }
 
 impl HasRaw for PathSegment {
     fn has_raw(&self) -> bool {
-        self.ident.has_raw()
+        let mut finder = RawFinder(false);
+        finder.visit_path_segment(self);
+        finder.0
     }
 }
 
@@ -274,10 +586,9 @@ impl StripRaw for Path {
     type Output = Path;
 
     fn strip_raw(&self) -> Self::Output {
-        Path {
-            leading_colon: self.leading_colon,
-            segments: self.segments.iter().map(|seg| seg.strip_raw()).collect(),
-        }
+        let mut path = self.clone();
+        RawStripper.visit_path_mut(&mut path);
+        path
     }
 }
 
@@ -347,80 +658,23 @@ pub mod utils {
     }
 }
 
-/// A path resolver that maps various path representations to canonical types.
-///
-/// This allows users to define a set of canonical type paths and resolve
-/// syn Path objects to those canonical forms, regardless of how they were
-/// referenced in the original code (with or without raw identifiers, different
-/// module prefixes, etc.).
-///
-/// The resolver supports three resolution strategies:
-/// 1. **Exact path matching** - Direct lookup of the full path
-/// 2. **Generic type resolution** - Extracts base type from generics (e.g., `Option<T>` -> `Option`)
-/// 3. **Progressive path resolution** - Tries shorter path variations for standard library types
-///
-/// ## Progressive Path Resolution
-///
-/// This feature automatically handles common path variations without requiring explicit mappings
-/// for every variant. For example, if you map `std::option::Option` -> `Option`, the resolver
-/// will automatically handle:
-/// - `Option<T>` -> `Option` (single segment with generics)
-/// - `option::Option<T>` -> `Option` (shortened qualified path)
-/// - `std::option::Option<T>` -> `Option` (full qualified path with generics)
-///
-/// The progressive resolution is conservative and only applies to:
-/// - Common standard library types (`Option`, `Vec`, `HashMap`, etc.)
-/// - Paths that contain standard library module names (`std`, `core`, `option`, `vec`, etc.)
+/// Specify type groups to include automatically.
 ///
-/// This prevents false matches like resolving `unknown::Option` to `Option` when you only
-/// mapped `std::option::Option`.
-///
-/// The resolver can be created with different storage backends:
-/// - `HashMap<String, String>` for dynamic runtime mappings
-/// - `phf::Map<&'static str, &'static str>` for static compile-time mappings
-/// - `EmptyStorage` for const resolvers with only primitive mappings
+/// New groups may be added in minor releases, so sets are built from the
+/// constants and [`with_group`](TypeGroups::with_group) /
+/// [`without_group`](TypeGroups::without_group) rather than struct literals.
 ///
 /// # Examples
 ///
-/// ## Dynamic Usage with HashMap
-/// ```
-/// use desynt::{TypeGroups, PathResolver};
-/// use std::collections::HashMap;
-/// use syn::Path;
-///
-/// let mut mappings = HashMap::new();
-/// mappings.insert("std::primitive::f64".to_string(), "f64".to_string());
-/// mappings.insert("core::primitive::f64".to_string(), "f64".to_string());
-///
-/// let resolver = PathResolver::new(mappings, TypeGroups::ALL);
-///
-/// let path: Path = syn::parse_str("::std::primitive::f64").unwrap();
-/// if let Some(canonical) = resolver.resolve(&path) {
-///     assert_eq!(canonical, "f64");
-/// }
-/// ```
-///
-/// ## Static Usage with PHF Map
 /// ```
-/// use desynt::{TypeGroups, PathResolver, EmptyStorage};
-/// use phf::{phf_map, Map};
-///
-/// // Define mappings in tests/examples where phf_map is allowed
-/// // static CUSTOM_MAPPINGS: Map<&'static str, &'static str> = phf_map! {
-/// //     "actix_web::HttpResponse" => "HttpResponse",
-/// //     "serde_json::Value" => "JsonValue",
-/// // };
-///
-/// // const RESOLVER: PathResolver<&'static Map<&'static str, &'static str>> =
-/// //     PathResolver::new(&CUSTOM_MAPPINGS, TypeGroups::ALL);
+/// use desynt::{TypeGroup, TypeGroups};
 ///
-/// // Or with only primitives
-/// const PRIMITIVE_RESOLVER: PathResolver<EmptyStorage> =
-///     PathResolver::new(EmptyStorage, TypeGroups::PRIMITIVES);
+/// const GROUPS: TypeGroups = TypeGroups::ALL.without_group(TypeGroup::System);
+/// assert!(GROUPS.prelude);
+/// assert!(!GROUPS.contains(TypeGroup::System));
 /// ```
-
-/// Specify type groups to include automatically.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct TypeGroups {
     /// Whether to include Rust language primitives (i8, u32, f64, bool, char, str, etc.).
     pub primitives: bool,
@@ -428,6 +682,18 @@ pub struct TypeGroups {
     pub prelude: bool,
     /// Whether to include common std library types (HashMap, HashSet, BTreeMap, BTreeSet, LinkedList, Cow, RefCell, Arc, Rc).
     pub common_std: bool,
+    /// Whether to include concurrency and synchronization types (Mutex, RwLock, Condvar, Once,
+    /// OnceLock, LazyLock, Weak, Cell, OnceCell, UnsafeCell, the atomics, mpsc channels, JoinHandle).
+    pub concurrency: bool,
+    /// Whether to include filesystem, OS and C string, networking and time types (PathBuf, Path,
+    /// OsString, OsStr, CString, CStr, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, Duration, Instant,
+    /// SystemTime).
+    pub system: bool,
+    /// Whether to include numeric wrapper types (NonZeroU8 to NonZeroU128, NonZeroI8 to
+    /// NonZeroI128, NonZeroUsize, NonZeroIsize, NonZero, Wrapping, Saturating).
+    pub numeric: bool,
+    /// Whether to include marker and pinning types (PhantomData, PhantomPinned, Pin, ManuallyDrop).
+    pub markers: bool,
 }
 
 impl TypeGroups {
@@ -436,6 +702,10 @@ impl TypeGroups {
         primitives: false,
         prelude: false,
         common_std: false,
+        concurrency: false,
+        system: false,
+        numeric: false,
+        markers: false,
     };
 
     /// Only Rust language primitives.
@@ -443,6 +713,10 @@ impl TypeGroups {
         primitives: true,
         prelude: false,
         common_std: false,
+        concurrency: false,
+        system: false,
+        numeric: false,
+        markers: false,
     };
 
     /// Primitives and prelude types.
@@ -450,18 +724,74 @@ impl TypeGroups {
         primitives: true,
         prelude: true,
         common_std: false,
+        concurrency: false,
+        system: false,
+        numeric: false,
+        markers: false,
     };
 
-    /// All type groups (primitives + prelude + common std types).
+    /// All type groups (primitives + prelude + common std + concurrency + system + numeric +
+    /// marker types).
     pub const ALL: Self = Self {
         primitives: true,
         prelude: true,
         common_std: true,
+        concurrency: true,
+        system: true,
+        numeric: true,
+        markers: true,
     };
 
     /// Check if any type groups are enabled.
     pub const fn is_empty(&self) -> bool {
-        !self.primitives && !self.prelude && !self.common_std
+        !self.primitives
+            && !self.prelude
+            && !self.common_std
+            && !self.concurrency
+            && !self.system
+            && !self.numeric
+            && !self.markers
+    }
+
+    /// Return `true` if a type group is enabled.
+    pub const fn contains(&self, group: TypeGroup) -> bool {
+        definitions::group_enabled(*self, group)
+    }
+
+    /// Enable a type group, builder style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::{TypeGroup, TypeGroups};
+    ///
+    /// const GROUPS: TypeGroups = TypeGroups::PRIMITIVES
+    ///     .with_group(TypeGroup::Numeric)
+    ///     .with_group(TypeGroup::Markers);
+    /// assert!(GROUPS.contains(TypeGroup::Numeric));
+    /// assert!(!GROUPS.contains(TypeGroup::Prelude));
+    /// ```
+    pub const fn with_group(self, group: TypeGroup) -> Self {
+        self.set_group(group, true)
+    }
+
+    /// Disable a type group, builder style.
+    pub const fn without_group(self, group: TypeGroup) -> Self {
+        self.set_group(group, false)
+    }
+
+    const fn set_group(self, group: TypeGroup, enabled: bool) -> Self {
+        let mut groups = self;
+        match group {
+            TypeGroup::Primitives => groups.primitives = enabled,
+            TypeGroup::Prelude => groups.prelude = enabled,
+            TypeGroup::CommonStd => groups.common_std = enabled,
+            TypeGroup::Concurrency => groups.concurrency = enabled,
+            TypeGroup::System => groups.system = enabled,
+            TypeGroup::Numeric => groups.numeric = enabled,
+            TypeGroup::Markers => groups.markers = enabled,
+        }
+        groups
     }
 }
 
@@ -471,14 +801,39 @@ impl Default for TypeGroups {
     }
 }
 
-/// Type alias for dynamic path resolvers using [`HashMap`] storage.
+/// A single built-in type group, as reported by [`Resolution::group`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeGroup {
+    /// Rust language primitives (i8, u32, f64, bool, char, str, etc.).
+    Primitives,
+    /// Rust prelude types (String, Vec, Option, Result, Box).
+    Prelude,
+    /// Common std library types (HashMap, HashSet, BTreeMap, etc.).
+    CommonStd,
+    /// Concurrency and synchronization types (Mutex, Cell, AtomicUsize, JoinHandle, etc.).
+    Concurrency,
+    /// Filesystem, OS and C string, networking and time types (PathBuf, OsStr, IpAddr, Duration, etc.).
+    System,
+    /// Numeric wrapper types (NonZeroU8, NonZero, Wrapping, Saturating, etc.).
+    Numeric,
+    /// Marker and pinning types (PhantomData, PhantomPinned, Pin, ManuallyDrop).
+    Markers,
+}
+
+/// Type alias for dynamic path resolvers using [`DynamicStorage`].
 ///
-/// This resolver allows adding and removing mappings at runtime.
-pub type DynamicPathResolver = PathResolver<HashMap<String, String>>;
+/// This resolver allows adding and removing mappings at runtime. Build one
+/// from an existing map with [`from_map`](PathResolver::from_map), and read
+/// the map back with `resolver.storage().as_map()`.
+pub type DynamicPathResolver = PathResolver<DynamicStorage>;
 
 /// Type alias for static path resolvers using PHF [`Map`] storage.
 ///
-/// This resolver uses compile-time static mappings for zero-cost lookups.
+/// This resolver uses compile-time static mappings and can be built in a
+/// `const`. Exact lookups do not allocate, but a phf map keeps no index of
+/// its glob, generic pattern or trailing-segment keys, so other lookups scan
+/// its keys and allocate. Wrap the map in an [`IndexedStorage`] once, for
+/// example behind a `OnceLock`, for allocation-free lookups of every kind.
 #[cfg(feature = "static-resolver")]
 pub type StaticPathResolver<'a> = PathResolver<&'a Map<&'static str, &'static str>>;
 
@@ -505,15 +860,87 @@ pub const PRELUDE_RESOLVER: PrimitivePathResolver =
 /// This includes primitives, prelude types, and common std types.
 pub const ALL_RESOLVER: PrimitivePathResolver = PathResolver::new(EmptyStorage, TypeGroups::ALL);
 
-/// Path resolver that maps various path representations to canonical types.
+/// A path resolver that maps various path representations to canonical types.
+///
+/// This allows users to define a set of canonical type paths and resolve
+/// syn Path objects to those canonical forms, regardless of how they were
+/// referenced in the original code (with or without raw identifiers, different
+/// module prefixes, etc.).
+///
+/// The resolver supports three resolution strategies:
+/// 1. **Exact path matching** - Direct lookup of the full path
+/// 2. **Generic type resolution** - Extracts base type from generics (e.g., `Option<T>` -> `Option`)
+/// 3. **Progressive path resolution** - Tries shorter path variations for standard library types
+///
+/// ## Progressive Path Resolution
+///
+/// This feature automatically handles common path variations without requiring explicit mappings
+/// for every variant. For example, if you map `std::option::Option` -> `Option`, the resolver
+/// will automatically handle:
+/// - `Option<T>` -> `Option` (single segment with generics)
+/// - `option::Option<T>` -> `Option` (shortened qualified path)
+/// - `std::option::Option<T>` -> `Option` (full qualified path with generics)
+///
+/// The progressive resolution is conservative and only applies to:
+/// - Common standard library types (`Option`, `Vec`, `HashMap`, etc.)
+/// - Paths that contain standard library module names (`std`, `core`, `option`, `vec`, etc.)
+///
+/// This prevents false matches like resolving `unknown::Option` to `Option` when you only
+/// mapped `std::option::Option`.
+///
+/// Each of these fallbacks can be switched on or off with a [`ResolutionPolicy`].
+///
+/// The resolver can be created with different storage backends:
+/// - `HashMap<String, String>` for dynamic runtime mappings
+/// - `phf::Map<&'static str, &'static str>` for static compile-time mappings
+/// - `EmptyStorage` for const resolvers with only primitive mappings
+///
+/// # Examples
+///
+/// ## Dynamic Usage with HashMap
+/// ```
+/// use desynt::{TypeGroups, PathResolver};
+/// use std::collections::HashMap;
+/// use syn::Path;
+///
+/// let mut mappings = HashMap::new();
+/// mappings.insert("std::primitive::f64".to_string(), "f64".to_string());
+/// mappings.insert("core::primitive::f64".to_string(), "f64".to_string());
+///
+/// let resolver = PathResolver::new(mappings, TypeGroups::ALL);
+///
+/// let path: Path = syn::parse_str("::std::primitive::f64").unwrap();
+/// if let Some(canonical) = resolver.resolve(&path) {
+///     assert_eq!(canonical, "f64");
+/// }
+/// ```
 ///
-/// See the module-level documentation for detailed usage examples.
+/// ## Static Usage with PHF Map
+/// ```
+/// use desynt::{TypeGroups, PathResolver, EmptyStorage};
+/// use phf::{phf_map, Map};
+///
+/// // Define mappings in tests/examples where phf_map is allowed
+/// // static CUSTOM_MAPPINGS: Map<&'static str, &'static str> = phf_map! {
+/// //     "actix_web::HttpResponse" => "HttpResponse",
+/// //     "serde_json::Value" => "JsonValue",
+/// // };
+///
+/// // const RESOLVER: PathResolver<&'static Map<&'static str, &'static str>> =
+/// //     PathResolver::new(&CUSTOM_MAPPINGS, TypeGroups::ALL);
+///
+/// // Or with only primitives
+/// const PRIMITIVE_RESOLVER: PathResolver<EmptyStorage> =
+///     PathResolver::new(EmptyStorage, TypeGroups::PRIMITIVES);
+/// ```
 #[derive(Debug, Clone)]
 pub struct PathResolver<M> {
     /// Maps normalized path strings to canonical type names.
     mappings: M,
     /// Which type group mappings to include.
     groups: TypeGroups,
+    /// Which resolution strategies are enabled.
+    policy: ResolutionPolicy,
 }
 
 impl<M> PathResolver<M>
@@ -531,7 +958,30 @@ where
     ///     PathResolver::new(EmptyStorage, TypeGroups::PRIMITIVES);
     /// ```
     pub const fn new(mappings: M, groups: TypeGroups) -> Self {
-        Self { mappings, groups }
+        Self {
+            mappings,
+            groups,
+            policy: ResolutionPolicy::DEFAULT,
+        }
+    }
+
+    /// Return this resolver with a different resolution policy.
+    ///
+    /// This can be used in const context, including on resolvers made with
+    /// [`create_static_resolver`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::{PathResolver, EmptyStorage, ResolutionPolicy, TypeGroups};
+    ///
+    /// const RESOLVER: PathResolver<EmptyStorage> =
+    ///     PathResolver::new(EmptyStorage, TypeGroups::ALL).with_policy(ResolutionPolicy::STRICT);
+    /// ```
+    pub const fn with_policy(self, policy: ResolutionPolicy) -> Self {
+        let mut resolver = self;
+        resolver.policy = policy;
+        resolver
     }
 
     /// Return the current type groups configuration.
@@ -539,6 +989,16 @@ where
         self.groups
     }
 
+    /// Return the current resolution policy.
+    pub const fn policy(&self) -> ResolutionPolicy {
+        self.policy
+    }
+
+    /// Return the mapping storage.
+    pub const fn storage(&self) -> &M {
+        &self.mappings
+    }
+
     /// Return `true` if any type group mappings are enabled.
     pub const fn uses_groups(&self) -> bool {
         !self.groups.is_empty()
@@ -559,13 +1019,41 @@ where
         self.groups.common_std
     }
 
+    /// Return `true` if concurrency type mappings are enabled.
+    pub const fn uses_concurrency(&self) -> bool {
+        self.groups.concurrency
+    }
+
+    /// Return `true` if system type mappings are enabled.
+    pub const fn uses_system(&self) -> bool {
+        self.groups.system
+    }
+
+    /// Return `true` if numeric wrapper type mappings are enabled.
+    pub const fn uses_numeric(&self) -> bool {
+        self.groups.numeric
+    }
+
+    /// Return `true` if marker and pinning type mappings are enabled.
+    pub const fn uses_markers(&self) -> bool {
+        self.groups.markers
+    }
+
     /// Resolve a syn [`Path`] to its canonical type name.
     ///
     /// This method uses multiple resolution strategies:
+    /// 0. Generic pattern matching - Pattern keys such as `Vec<u8>` matching the generic arguments
     /// 1. Exact path matching - Direct lookup of the full path
+    ///    and glob keys such as `my_crate::*::UserId` (see [`MappingStorage::glob_keys`])
+    ///    then custom [`Matcher`]s (see [`MappingStorage::matchers`])
     /// 2. Generic type resolution - Extracts base type from generics (e.g., `Option<T>` -> `Option`)
     /// 3. Progressive path resolution - Tries shorter path variations for standard library types
     ///
+    /// Paths starting with `crate::`, `self::` or `super::` are looked up as
+    /// written. To rewrite them into absolute paths, resolve them with
+    /// [`resolve_in_scope`](PathResolver::resolve_in_scope) and a [`Scope`]
+    /// given the module path with [`Scope::with_module`].
+    ///
     /// # Returns
     ///
     /// Returns `Some(&str)` with the canonical type name if a mapping exists,
@@ -582,38 +1070,191 @@ where
     /// assert_eq!(resolver.resolve(&path), Some("Option"));
     /// ```
     pub fn resolve(&self, path: &Path) -> Option<&str> {
-        let stripped = path.strip_raw();
+        if let Some(resolution) = self.resolve_generic_pattern(path) {
+            return Some(resolution.canonical);
+        }
+        let normalized = NormalizedPath::new(path);
+        self.resolve_normalized(normalized.as_str())
+            .map(|found| found.canonical)
+    }
 
-        // Strategy 1: Try the full normalized path first (existing behavior)
-        let full_normalized = self.normalize_path(path);
-        if let Some(result) = self.try_resolve_base_type(&full_normalized) {
-            return Some(result);
+    /// Resolve a syn [`Path`] and describe how the match was made.
+    ///
+    /// This uses the same strategies as [`PathResolver::resolve`], but returns a
+    /// [`Resolution`] carrying the matched pattern key, the builtin type group
+    /// (if any) and the [`Strategy`] that produced the match. This can be used to
+    /// warn when a type was only matched heuristically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::{DynamicPathResolver, Strategy, TypeGroup};
+    /// use syn::Path;
+    ///
+    /// let resolver = DynamicPathResolver::with_all_groups();
+    ///
+    /// let path: Path = syn::parse_str("std::option::Option<u8>").unwrap();
+    /// let resolution = resolver.resolve_detailed(&path).unwrap();
+    /// assert_eq!(resolution.canonical, "Option");
+    /// assert_eq!(resolution.pattern, "std::option::Option");
+    /// assert_eq!(resolution.group, Some(TypeGroup::Prelude));
+    /// assert!(resolution.is_exact());
+    ///
+    /// let path: Path = syn::parse_str("Option<u8>").unwrap();
+    /// let resolution = resolver.resolve_detailed(&path).unwrap();
+    /// assert_eq!(resolution.strategy, Strategy::BuiltinPattern);
+    /// assert!(resolution.is_heuristic());
+    /// ```
+    pub fn resolve_detailed(&self, path: &Path) -> Option<Resolution<'_>> {
+        if let Some(resolution) = self.resolve_generic_pattern(path) {
+            return Some(resolution);
         }
+        self.resolve_path_detailed(path)
+    }
 
-        // Strategy 2: For generic types, try progressive path resolution
-        if let Some(last_segment) = stripped.segments.last() {
-            // Check if this is a generic type (has type arguments)
-            let has_generics = !matches!(last_segment.arguments, PathArguments::None);
+    /// Resolve a path by its segments alone, without generic pattern keys.
+    fn resolve_path_detailed(&self, path: &Path) -> Option<Resolution<'_>> {
+        let normalized = NormalizedPath::new(path);
+        self.resolve_normalized(normalized.as_str())
+            .map(Match::into_resolution)
+    }
 
-            if has_generics {
-                // Extract the base type name (e.g., "Option" from "Option<T>")
-                let base_type = last_segment.ident.to_string();
+    /// Resolve a normalized path, borrowing the matched pattern where possible.
+    fn resolve_normalized<'p>(&self, normalized: &'p str) -> Option<Match<'_, 'p>> {
+        if let Some(found) = self.resolve_full_path(normalized) {
+            return Some(found);
+        }
 
-                // Try resolving with the base type using all possible path combinations
-                if let Some(result) = self.resolve_with_progressive_paths(&stripped, &base_type) {
-                    return Some(result);
-                }
-            }
+        // Strategy 1d: Look up the full path rewritten by a facade rule
+        if let Some(found) = self.resolve_facade(normalized) {
+            return Some(found);
         }
 
-        // Strategy 3: Even for non-generic types, try progressive path resolution
-        if let Some(result) = self
-            .resolve_with_progressive_paths(&stripped, &stripped.segments.last()?.ident.to_string())
-        {
-            return Some(result);
+        // Strategy 2: Try progressive path resolution using the base type name
+        // (e.g., "Option" from "Option<T>"), with or without generics
+        if normalized.is_empty() {
+            return None;
+        }
+        self.resolve_with_progressive_paths(normalized)
+    }
+
+    /// Resolve the full normalized path, without shortening it.
+    fn resolve_full_path<'p>(&self, normalized: &'p str) -> Option<Match<'_, 'p>> {
+        // Strategy 1: Try the full normalized path first
+        if let Some((canonical, group)) = self.try_resolve_base_type(normalized) {
+            return Some(Match {
+                canonical,
+                pattern: MatchedPattern::Path(normalized),
+                group,
+                strategy: Strategy::Exact,
+            });
         }
 
-        None
+        // Strategy 1b: Try glob keys such as `my_crate::*::UserId` against the full path
+        if let Some(found) = self.resolve_glob(normalized) {
+            return Some(found);
+        }
+
+        // Strategy 1c: Ask custom matchers for the full path
+        self.resolve_matcher(normalized)
+    }
+
+    /// Return every custom mapping that a bare use of the path's type name could refer to.
+    ///
+    /// Candidates are the custom patterns equal to the last segment of `path`,
+    /// or ending with `::` followed by it, ordered from most to least preferred
+    /// using the same tie-breaking as [`PathResolver::resolve`]. Built-in type
+    /// group patterns are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::DynamicPathResolver;
+    /// use syn::Path;
+    ///
+    /// let mut resolver = DynamicPathResolver::default();
+    /// resolver.add_mapping("crate_a::UserId", "UserIdA");
+    /// resolver.add_mapping("crate_b::UserId", "UserIdB");
+    ///
+    /// let path: Path = syn::parse_str("UserId").unwrap();
+    /// let candidates = resolver.resolve_candidates(&path);
+    /// assert_eq!(candidates.len(), 2);
+    /// assert_eq!(candidates[0].pattern, "crate_a::UserId");
+    /// ```
+    pub fn resolve_candidates(&self, path: &Path) -> Vec<Candidate<'_>> {
+        match path.segments.last() {
+            Some(segment) => self.suffix_candidates(&segment.ident.strip_raw().to_string()),
+            None => Vec::new(),
+        }
+    }
+
+    /// Resolve a syn [`Path`], refusing to guess between equally ranked candidates.
+    ///
+    /// This behaves like [`PathResolver::resolve`], except that when a bare type
+    /// name matches several custom patterns with different canonical names, only
+    /// an explicit priority (see [`MappingStorage::priority`]) may break the tie.
+    /// Otherwise an [`AmbiguityError`] listing the competing patterns is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::DynamicPathResolver;
+    /// use syn::Path;
+    ///
+    /// let mut resolver = DynamicPathResolver::default();
+    /// resolver.add_mapping("crate_a::UserId", "UserIdA");
+    /// resolver.add_mapping("crate_b::UserId", "UserIdB");
+    ///
+    /// let path: Path = syn::parse_str("UserId").unwrap();
+    /// assert!(resolver.resolve_strict(&path).is_err());
+    ///
+    /// resolver.set_priority("crate_b::UserId", 10);
+    /// assert_eq!(resolver.resolve_strict(&path), Ok(Some("UserIdB")));
+    /// ```
+    pub fn resolve_strict(&self, path: &Path) -> Result<Option<&str>, AmbiguityError> {
+        let Some(resolution) = self.resolve_detailed(path) else {
+            return Ok(None);
+        };
+
+        if resolution.strategy == Strategy::Suffix {
+            let candidates = self.resolve_candidates(path);
+            let top_priority = candidates.first().map_or(0, |candidate| candidate.priority);
+            let contenders: Vec<&Candidate<'_>> = candidates
+                .iter()
+                .take_while(|candidate| candidate.priority == top_priority)
+                .collect();
+
+            if contenders
+                .iter()
+                .any(|candidate| candidate.canonical != resolution.canonical)
+            {
+                let name = path
+                    .segments
+                    .last()
+                    .map_or_else(String::new, |segment| segment.ident.strip_raw().to_string());
+                return Err(AmbiguityError {
+                    name,
+                    candidates: contenders
+                        .iter()
+                        .map(|candidate| {
+                            (
+                                candidate.pattern.to_string(),
+                                candidate.canonical.to_string(),
+                            )
+                        })
+                        .collect(),
+                });
+            }
+        }
+
+        Ok(Some(resolution.canonical))
+    }
+
+    /// Return the tie-breaking priority of a normalized pattern.
+    ///
+    /// See [`MappingStorage::priority`].
+    pub fn priority(&self, path_pattern: &str) -> i32 {
+        self.mappings.priority(path_pattern)
     }
 
     /// Try to resolve a path using progressive path shortening.
@@ -621,16 +1262,18 @@ where
     /// This handles cases like:
     /// - "std::option::Option" -> looks for "std::option::Option", "option::Option", "Option"
     /// - "string::String" -> looks for "string::String", "String"
-    fn resolve_with_progressive_paths(&self, path: &Path, base_type: &str) -> Option<&str> {
-        let segments: Vec<String> = path
-            .segments
-            .iter()
-            .map(|seg| seg.ident.to_string())
-            .collect();
+    fn resolve_with_progressive_paths<'p>(&self, normalized: &'p str) -> Option<Match<'_, 'p>> {
+        let segment_count = normalized.matches("::").count() + 1;
+        let base_type = normalized.rsplit("::").next()?;
+
+        let min_segments = self.policy.min_matching_segments;
 
         // For a single segment with generics (like "Option<T>"), we need to check
         // if there are any mappings that end with this base type
-        if segments.len() == 1 {
+        if segment_count == 1 {
+            if min_segments > 1 {
+                return None;
+            }
             // Try to find any mapping that ends with this base type
             return self.find_mapping_ending_with(base_type);
         }
@@ -641,31 +1284,41 @@ where
         // - "b::c::Type"
         // - "c::Type"
         // - "Type"
-        for start_idx in 0..segments.len() {
-            let candidate_segments: Vec<String> = segments[start_idx..]
-                .iter()
-                .take(segments.len() - start_idx - 1) // Exclude the last segment
-                .cloned()
-                .collect();
+        // Each candidate is a trailing slice of the normalized path
+        for (start_idx, candidate_path) in trailing_paths(normalized).enumerate() {
+            if segment_count - start_idx < min_segments {
+                break;
+            }
 
-            if candidate_segments.is_empty() {
+            if start_idx + 1 == segment_count {
                 // Just the base type - try exact match first
-                if let Some(result) = self.try_resolve_base_type(base_type) {
-                    return Some(result);
+                if self.policy.progressive {
+                    if let Some((canonical, group)) = self.try_resolve_base_type(base_type) {
+                        return Some(Match {
+                            canonical,
+                            pattern: MatchedPattern::Path(base_type),
+                            group,
+                            strategy: Strategy::Progressive,
+                        });
+                    }
                 }
                 // For multi-segment paths that reduce to just the base type,
-                // only apply suffix matching if it could be a reasonable std library shortening
-                if self.could_be_stdlib_shortening(&segments, base_type) {
+                // only apply suffix matching if the policy allows it, by default
+                // only when it could be a reasonable std library shortening
+                if self.policy.unrestricted_heuristic
+                    || (self.policy.stdlib_heuristic
+                        && definitions::could_be_stdlib_shortening(normalized, base_type))
+                {
                     return self.find_mapping_ending_with(base_type);
                 }
-            } else {
-                // Build candidate path ending with base type
-                let mut full_candidate = candidate_segments;
-                full_candidate.push(base_type.to_string());
-                let candidate_path = full_candidate.join("::");
-
-                if let Some(result) = self.try_resolve_base_type(&candidate_path) {
-                    return Some(result);
+            } else if self.policy.progressive {
+                if let Some((canonical, group)) = self.try_resolve_base_type(candidate_path) {
+                    return Some(Match {
+                        canonical,
+                        pattern: MatchedPattern::Path(candidate_path),
+                        group,
+                        strategy: Strategy::Progressive,
+                    });
                 }
             }
         }
@@ -673,206 +1326,105 @@ where
         None
     }
 
-    /// Check if a path could reasonably be a shortening of a standard library path.
-    ///
-    /// This is used to determine when suffix matching should be applied.
-    fn could_be_stdlib_shortening(&self, segments: &[String], base_type: &str) -> bool {
-        // Only allow suffix matching for prelude types and common std library types
-        let common_types = [
-            "Option",
-            "Vec",
-            "HashMap",
-            "HashSet",
-            "Result",
-            "String",
-            "Box",
-            "BTreeMap",
-            "BTreeSet",
-            "LinkedList",
-            "Cow",
-            "RefCell",
-            "Arc",
-            "Rc",
-        ];
-        if !common_types.contains(&base_type) {
-            return false;
-        }
-
-        // Check if any segment could be a standard library module
-        let stdlib_modules = [
-            "std",
-            "core",
-            "alloc",
-            "option",
-            "vec",
-            "collections",
-            "string",
-            "result",
-            "borrow",
-            "boxed",
-            "cell",
-            "sync",
-            "rc",
-            "hash_map",
-            "hash_set",
-            "btree_map",
-            "btree_set",
-            "linked_list",
-        ];
-
-        for segment in segments {
-            if stdlib_modules.contains(&segment.as_str()) {
-                return true;
-            }
-        }
-
-        false
-    }
-
     /// Find a mapping that ends with the given base type.
     ///
     /// For example, if base_type is "Option", this will find "std::option::Option" -> "Option"
     /// Prefers shorter paths and standard library paths over longer/custom paths.
-    fn find_mapping_ending_with(&self, base_type: &str) -> Option<&str> {
-        // First try exact match
-        if let Some(result) = self.try_resolve_base_type(base_type) {
-            return Some(result);
-        }
-
-        // For any base type, try to find mappings that end with this type
-        let suffix = format!("::{}", base_type);
-        let mut candidates: Vec<(&str, &str)> = Vec::new();
-
-        for key in self.mappings.keys() {
-            if key.ends_with(&suffix) {
-                if let Some(result) = self.try_resolve_base_type(key) {
-                    candidates.push((key, result));
-                }
+    fn find_mapping_ending_with<'p>(&self, base_type: &'p str) -> Option<Match<'_, 'p>> {
+        // First try exact match of the base type, a progressive shortening
+        if self.policy.progressive {
+            if let Some((canonical, group)) = self.try_resolve_base_type(base_type) {
+                return Some(Match {
+                    canonical,
+                    pattern: MatchedPattern::Path(base_type),
+                    group,
+                    strategy: Strategy::Progressive,
+                });
             }
         }
 
-        if candidates.is_empty() {
-            // No candidates found, check built-in mappings if enabled
-            if !self.groups.is_empty() {
-                #[cfg(feature = "static-resolver")]
-                {
-                    // Check if any built-in mapping matches this base type
-                    if definitions::get_builtin_mapping_static(base_type, self.groups).is_some() {
-                        return definitions::get_builtin_mapping_static(base_type, self.groups);
-                    }
-                    // For built-in mappings, check common patterns
-                    return self.check_builtin_patterns(base_type);
-                }
-                #[cfg(not(feature = "static-resolver"))]
-                {
-                    if definitions::get_builtin_mapping(base_type, self.groups).is_some() {
-                        return definitions::get_builtin_mapping(base_type, self.groups);
-                    }
-                    // For built-in mappings, check common patterns
-                    return self.check_builtin_patterns(base_type);
-                }
+        // For any base type, try to find mappings that end with this type
+        if self.policy.suffix {
+            if let Some(best) = self
+                .suffix_candidate_iter(base_type)
+                .min_by(compare_candidates)
+            {
+                return Some(Match {
+                    canonical: best.canonical,
+                    pattern: MatchedPattern::Key(best.pattern),
+                    group: None,
+                    strategy: Strategy::Suffix,
+                });
             }
-            return None;
         }
 
-        // If we have candidates, choose the best one using prioritization
-        // Priority order:
-        // 1. Standard library paths (std::, core::, alloc::)
-        // 2. Shorter paths (fewer segments)
-        // 3. Alphabetical order for tie-breaking
-
-        let mut stdlib_candidates: Vec<(&str, &str)> = Vec::new();
-        let mut other_candidates: Vec<(&str, &str)> = Vec::new();
-
-        for (key, result) in candidates {
-            if key.starts_with("std::") || key.starts_with("core::") || key.starts_with("alloc::") {
-                stdlib_candidates.push((key, result));
-            } else {
-                other_candidates.push((key, result));
-            }
+        // No candidates found, check built-in patterns if enabled
+        if self.policy.builtin_patterns && !self.groups.is_empty() {
+            return self.check_builtin_patterns(base_type);
         }
+        None
+    }
 
-        // Prefer standard library candidates
-        let preferred_candidates = if !stdlib_candidates.is_empty() {
-            stdlib_candidates
-        } else {
-            other_candidates
-        };
-
-        // Sort by path length (shorter is better), then alphabetically
-        let mut sorted_candidates = preferred_candidates;
-        sorted_candidates.sort_by(|a, b| {
-            let len_cmp = a.0.matches("::").count().cmp(&b.0.matches("::").count());
-            if len_cmp == std::cmp::Ordering::Equal {
-                a.0.cmp(b.0)
-            } else {
-                len_cmp
-            }
-        });
-
-        // Return the best candidate
-        sorted_candidates.first().map(|(_, result)| *result)
+    /// Collect the custom mappings whose pattern is `base_type` or ends with
+    /// `::base_type`, best candidate first.
+    ///
+    /// Priority order:
+    /// 1. Explicit priorities from [`MappingStorage::priority`] (higher first)
+    /// 2. Standard library paths (std::, core::, alloc::)
+    /// 3. Shorter paths (fewer segments)
+    /// 4. Alphabetical order for tie-breaking
+    fn suffix_candidates(&self, base_type: &str) -> Vec<Candidate<'_>> {
+        let mut candidates: Vec<Candidate<'_>> = self.suffix_candidate_iter(base_type).collect();
+        candidates.sort_by(compare_candidates);
+        candidates
+    }
+
+    /// The custom mappings whose pattern is `base_type` or ends with `::base_type`, unordered.
+    fn suffix_candidate_iter<'a: 'b, 'b>(
+        &'a self,
+        base_type: &'b str,
+    ) -> impl Iterator<Item = Candidate<'a>> + 'b {
+        self.mappings
+            .keys_ending_with(base_type)
+            // Projection keys such as `<_ as Trait>::Assoc` and glob keys
+            // such as `my_crate::*::UserId` are not paths
+            .filter(|key| !key.starts_with('<') && !glob::is_glob(key))
+            .filter_map(|key| {
+                Some(Candidate {
+                    pattern: key,
+                    canonical: self.mappings.get(key)?,
+                    priority: self.priority(key),
+                })
+            })
     }
 
     /// Check common primitive type patterns for a base type.
-    fn check_builtin_patterns(&self, base_type: &str) -> Option<&str> {
-        // Check common prefixes for type groups
-        for prefix in &["std", "core", "alloc"] {
-            let patterns = [
-                format!("{}::{}", prefix, base_type),
-                format!("{}::primitive::{}", prefix, base_type),
-                format!("{}::string::{}", prefix, base_type),
-                format!("{}::vec::{}", prefix, base_type),
-                format!("{}::collections::{}", prefix, base_type),
-                format!("{}::collections::hash_map::{}", prefix, base_type),
-                format!("{}::collections::hash_set::{}", prefix, base_type),
-                format!("{}::collections::btree_map::{}", prefix, base_type),
-                format!("{}::collections::btree_set::{}", prefix, base_type),
-                format!("{}::collections::linked_list::{}", prefix, base_type),
-                format!("{}::option::{}", prefix, base_type),
-                format!("{}::result::{}", prefix, base_type),
-                format!("{}::boxed::{}", prefix, base_type),
-                format!("{}::borrow::{}", prefix, base_type),
-                format!("{}::cell::{}", prefix, base_type),
-                format!("{}::sync::{}", prefix, base_type),
-                format!("{}::rc::{}", prefix, base_type),
-            ];
-
-            for candidate in &patterns {
-                #[cfg(feature = "static-resolver")]
-                {
-                    if let Some(result) =
-                        definitions::get_builtin_mapping_static(candidate, self.groups)
-                    {
-                        return Some(result);
-                    }
-                }
-                #[cfg(not(feature = "static-resolver"))]
-                {
-                    if let Some(result) = definitions::get_builtin_mapping(candidate, self.groups) {
-                        return Some(result);
-                    }
-                }
-            }
-        }
-        None
+    ///
+    /// The builtin paths under `std`, `core` and `alloc` are looked up in a
+    /// precomputed table, so no candidate paths are built.
+    fn check_builtin_patterns<'p>(&self, base_type: &str) -> Option<Match<'_, 'p>> {
+        let (pattern, canonical, group) = definitions::get_builtin_pattern(base_type, self.groups)?;
+        Some(Match {
+            canonical,
+            pattern: MatchedPattern::Key(pattern),
+            group: Some(group),
+            strategy: Strategy::BuiltinPattern,
+        })
     }
 
     /// Try resolving a base type against both custom and built-in mappings.
-    fn try_resolve_base_type(&self, base_type: &str) -> Option<&str> {
+    ///
+    /// Returns the canonical name and, for built-in matches, the type group.
+    fn try_resolve_base_type(&self, base_type: &str) -> Option<(&str, Option<TypeGroup>)> {
         // Check custom mappings
         if let Some(canonical) = self.mappings.get(base_type) {
-            return Some(canonical);
+            return Some((canonical, None));
         }
 
         // Check built-in mappings if enabled
         if !self.groups.is_empty() {
-            #[cfg(feature = "static-resolver")]
-            let builtin_result = definitions::get_builtin_mapping_static(base_type, self.groups);
-            #[cfg(not(feature = "static-resolver"))]
-            let builtin_result = definitions::get_builtin_mapping(base_type, self.groups);
-
-            return builtin_result;
+            return builtin_mapping(base_type, self.groups)
+                .map(|(canonical, group)| (canonical, Some(group)));
         }
 
         None
@@ -881,42 +1433,10 @@ where
     /// Return an iterator over all canonical type names known to this resolver.
     ///
     /// This includes both custom mappings and type group mappings (if enabled).
-    pub fn canonical_types(&self) -> impl Iterator<Item = &str> {
-        let custom_types: Vec<&str> = self.mappings.values().collect();
-
-        if !self.groups.is_empty() {
-            let mut builtin_types = Vec::new();
-
-            if self.groups.primitives {
-                builtin_types.extend_from_slice(&[
-                    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128",
-                    "usize", "f32", "f64", "bool", "char", "str",
-                ]);
-            }
-
-            if self.groups.prelude {
-                builtin_types.extend_from_slice(&["String", "Vec", "Option", "Result", "Box"]);
-            }
-
-            if self.groups.common_std {
-                builtin_types.extend_from_slice(&[
-                    "HashMap",
-                    "HashSet",
-                    "BTreeMap",
-                    "BTreeSet",
-                    "LinkedList",
-                    "Cow",
-                    "RefCell",
-                    "Arc",
-                    "Rc",
-                ]);
-            }
-
-            let all_types: Vec<&str> = custom_types.into_iter().chain(builtin_types).collect();
-            Box::new(all_types.into_iter()) as Box<dyn Iterator<Item = &str>>
-        } else {
-            Box::new(custom_types.into_iter()) as Box<dyn Iterator<Item = &str>>
-        }
+    pub fn canonical_types(&self) -> impl Iterator<Item = &str> + '_ {
+        // Shorten the builtin names' `'static` lifetime to that of the custom names
+        let builtin = definitions::canonical_names(self.groups).map(|name| -> &str { name });
+        self.mappings.values().chain(builtin)
     }
 
     /// Return an iterator over all registered path patterns.
@@ -934,18 +1454,13 @@ where
     ///
     /// This checks both custom mappings and type group mappings (if enabled).
     pub fn has_mapping(&self, path: &Path) -> bool {
-        let normalized = self.normalize_path(path);
-        self.mappings.contains_key(&normalized)
-            || (!self.groups.is_empty() && {
-                #[cfg(feature = "static-resolver")]
-                {
-                    definitions::get_builtin_mapping_static(&normalized, self.groups).is_some()
-                }
-                #[cfg(not(feature = "static-resolver"))]
-                {
-                    definitions::get_builtin_mapping(&normalized, self.groups).is_some()
-                }
-            })
+        let normalized = NormalizedPath::new(path);
+        let normalized = normalized.as_str();
+        self.mappings.contains_key(normalized)
+            || (!self.groups.is_empty() && builtin_mapping(normalized, self.groups).is_some())
+            || self.resolve_glob(normalized).is_some()
+            || self.resolve_matcher(normalized).is_some()
+            || self.resolve_facade(normalized).is_some()
     }
 
     /// Return the total number of custom mappings in this resolver.
@@ -1000,28 +1515,28 @@ where
 impl DynamicPathResolver {
     /// Create a new dynamic path resolver with all type groups enabled.
     ///
-    /// This is equivalent to calling `new(HashMap::new(), TypeGroups::ALL)`.
+    /// This is equivalent to calling `new(DynamicStorage::new(), TypeGroups::ALL)`.
     pub fn with_all_groups() -> Self {
-        Self::new(HashMap::new(), TypeGroups::ALL)
+        Self::new(DynamicStorage::new(), TypeGroups::ALL)
     }
 
     /// Create a new dynamic path resolver with only primitive type mappings.
     ///
-    /// This is equivalent to calling `new(HashMap::new(), TypeGroups::PRIMITIVES)`.
+    /// This is equivalent to calling `new(DynamicStorage::new(), TypeGroups::PRIMITIVES)`.
     pub fn with_primitives() -> Self {
-        Self::new(HashMap::new(), TypeGroups::PRIMITIVES)
+        Self::new(DynamicStorage::new(), TypeGroups::PRIMITIVES)
     }
 
     /// Create a new dynamic path resolver with primitives and prelude types.
     ///
-    /// This is equivalent to calling `new(HashMap::new(), TypeGroups::PRELUDE)`.
+    /// This is equivalent to calling `new(DynamicStorage::new(), TypeGroups::PRELUDE)`.
     pub fn with_prelude() -> Self {
-        Self::new(HashMap::new(), TypeGroups::PRELUDE)
+        Self::new(DynamicStorage::new(), TypeGroups::PRELUDE)
     }
 
     /// Create a new dynamic path resolver from an existing HashMap with specified type groups.
     pub fn from_map(mappings: HashMap<String, String>, groups: TypeGroups) -> Self {
-        Self::new(mappings, groups)
+        Self::new(DynamicStorage::from(mappings), groups)
     }
 
     /// Set which type groups to use for this resolver.
@@ -1029,6 +1544,11 @@ impl DynamicPathResolver {
         self.groups = groups;
     }
 
+    /// Set which resolution strategies this resolver may use.
+    pub fn set_policy(&mut self, policy: ResolutionPolicy) {
+        self.policy = policy;
+    }
+
     /// Enable or disable the use of built-in primitive mappings.
     ///
     /// Deprecated: Use `set_groups` with `TypeGroups::PRIMITIVES` instead.
@@ -1045,6 +1565,16 @@ impl DynamicPathResolver {
     ///
     /// The path pattern will be normalized (raw prefixes and leading `::` removed)
     /// before being stored.
+    ///
+    /// The pattern may constrain generic arguments, as in `Vec<u8>` or
+    /// `Result<$T, my_crate::Error>`, where `_` matches any type and `$name`
+    /// matches any type and captures it. A matching generic pattern takes
+    /// precedence over plain path patterns, and the pattern with the most
+    /// concrete types wins. Captures are returned in [`Resolution::captures`].
+    ///
+    /// Path segments may also be globs: `*` matches one segment, `**` any
+    /// number of segments and `{a,b}` one of the listed segments, as in
+    /// `my_crate::**::UserId`. Exact keys take precedence over glob keys.
     pub fn add_mapping<S1, S2>(&mut self, path_pattern: S1, canonical_type: S2)
     where
         S1: Into<String>,
@@ -1055,7 +1585,101 @@ impl DynamicPathResolver {
             .insert(normalized_pattern, canonical_type.into());
     }
 
-    /// Remove all custom mappings from this resolver.
+    /// Add a custom mapping together with its tie-breaking priority.
+    ///
+    /// See [`set_priority`](Self::set_priority).
+    pub fn add_mapping_with_priority<S1, S2>(
+        &mut self,
+        path_pattern: S1,
+        canonical_type: S2,
+        priority: i32,
+    ) where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        let normalized_pattern = self.normalize_path_string(&path_pattern.into());
+        self.mappings
+            .set_priority(normalized_pattern.clone(), priority);
+        self.mappings
+            .insert(normalized_pattern, canonical_type.into());
+    }
+
+    /// Set the priority of a pattern when breaking ties between suffix matches.
+    ///
+    /// Higher priorities win. Patterns without an explicit priority have priority `0`.
+    /// The pattern is normalized (raw prefixes and leading `::` removed) before being stored.
+    pub fn set_priority<S>(&mut self, path_pattern: S, priority: i32)
+    where
+        S: Into<String>,
+    {
+        let normalized_pattern = self.normalize_path_string(&path_pattern.into());
+        self.mappings.set_priority(normalized_pattern, priority);
+    }
+
+    /// Add a named [`Matcher`] consulted after exact and glob keys.
+    ///
+    /// Matchers are consulted in the order they were added. Adding a matcher
+    /// with the name of an existing one replaces it in place.
+    pub fn add_matcher<S, T>(&mut self, name: S, matcher: T)
+    where
+        S: Into<String>,
+        T: Matcher + Send + Sync + 'static,
+    {
+        self.mappings.add_matcher(NamedMatcher::new(name, matcher));
+    }
+
+    /// Remove a named matcher, returning `true` if it was present.
+    pub fn remove_matcher(&mut self, name: &str) -> bool {
+        self.mappings.remove_matcher(name)
+    }
+
+    /// Add a facade rule, treating paths under `prefix` as paths under `target`.
+    ///
+    /// A path that does not match exactly, by a glob key or by a matcher, and
+    /// that starts with the segments of `prefix`, is looked up again with them
+    /// replaced by `target`. This lets a crate re-exporting another crate's
+    /// items, such as `hashbrown` for `std::collections`, share its mappings.
+    /// A match through a facade reports the pattern and strategy of the
+    /// rewritten path.
+    ///
+    /// Rules are tried in the order they were added. Adding a rule with the
+    /// prefix of an existing one replaces it in place.
+    ///
+    /// `std`, `core` and `alloc` need no rule: builtin types are known under
+    /// each crate that provides them (see [`BuiltinType::origin`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::DynamicPathResolver;
+    /// use syn::Path;
+    ///
+    /// let mut resolver = DynamicPathResolver::with_all_groups();
+    /// resolver.add_facade("hashbrown", "std::collections");
+    ///
+    /// let path: Path = syn::parse_str("hashbrown::hash_map::HashMap").unwrap();
+    /// let resolution = resolver.resolve_detailed(&path).unwrap();
+    /// assert_eq!(resolution.canonical, "HashMap");
+    /// assert_eq!(resolution.pattern, "std::collections::hash_map::HashMap");
+    /// assert!(resolution.is_exact());
+    /// ```
+    pub fn add_facade<S1, S2>(&mut self, prefix: S1, target: S2)
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        let prefix = self.normalize_path_string(&prefix.into());
+        let target = self.normalize_path_string(&target.into());
+        self.mappings.add_facade(prefix, target);
+    }
+
+    /// Remove the facade rule for a prefix, returning `true` if it was present.
+    pub fn remove_facade(&mut self, prefix: &str) -> bool {
+        let prefix = self.normalize_path_string(prefix);
+        self.mappings.remove_facade(&prefix)
+    }
+
+    /// Remove all custom mappings, their priorities, matchers and facade rules from this resolver.
     ///
     /// Type group mappings (if enabled) are not affected.
     pub fn clear(&mut self) {
@@ -1065,7 +1689,7 @@ impl DynamicPathResolver {
 
 impl Default for DynamicPathResolver {
     fn default() -> Self {
-        Self::new(HashMap::new(), TypeGroups::NONE)
+        Self::new(DynamicStorage::new(), TypeGroups::NONE)
     }
 }
 
@@ -1100,8 +1724,35 @@ impl PathResolver<EmptyStorage> {
     }
 }
 
+/// Order suffix candidates from most to least preferred.
+///
+/// See [`PathResolver::resolve_candidates`].
+fn compare_candidates(a: &Candidate<'_>, b: &Candidate<'_>) -> Ordering {
+    b.priority
+        .cmp(&a.priority)
+        .then_with(|| is_stdlib_path(b.pattern).cmp(&is_stdlib_path(a.pattern)))
+        .then_with(|| {
+            let a_len = a.pattern.matches("::").count();
+            a_len.cmp(&b.pattern.matches("::").count())
+        })
+        .then_with(|| a.pattern.cmp(b.pattern))
+}
+
+/// Return `true` if a normalized path is rooted in `std`, `core` or `alloc`.
+fn is_stdlib_path(path: &str) -> bool {
+    path.starts_with("std::") || path.starts_with("core::") || path.starts_with("alloc::")
+}
+
+/// Look up a normalized path in the built-in type group tables.
+fn builtin_mapping(path: &str, groups: TypeGroups) -> Option<(&'static str, TypeGroup)> {
+    definitions::get_builtin_mapping(path, groups)
+}
+
 /// Create a static resolver with custom PHF mappings.
 ///
+/// The resolver can be built in a `const`, but lookups that do not match a
+/// key exactly scan the map's keys; see [`StaticPathResolver`].
+///
 /// # Examples
 ///
 /// ```
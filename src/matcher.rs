@@ -0,0 +1,175 @@
+//! Programmatic mapping rules for paths that cannot be listed as fixed keys.
+//!
+//! A [`Matcher`] is given the segments of a path, with raw prefixes and
+//! generic arguments stripped, and may return a canonical type name. Matchers
+//! are consulted after exact and glob keys, and before the shortening and
+//! suffix heuristics. When several matchers are registered, the first one
+//! registered that returns a name wins.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::resolution::{Match, MatchedPattern};
+use crate::{MappingStorage, PathResolver, Strategy};
+
+/// A programmatic rule mapping path segments to a canonical type name.
+///
+/// Closures taking the segment list and returning `Option<&'static str>`
+/// implement this trait, as does [`PredicateMatcher`].
+///
+/// # Examples
+///
+/// ```
+/// use desynt::DynamicPathResolver;
+/// use syn::Path;
+///
+/// let mut resolver = DynamicPathResolver::default();
+/// resolver.add_matcher("model ids", |segments: &[&str]| match segments {
+///     ["my_crate", "model", .., name] if name.ends_with("Id") => Some("Identifier"),
+///     _ => None,
+/// });
+///
+/// let path: Path = syn::parse_str("my_crate::model::UserId").unwrap();
+/// assert_eq!(resolver.resolve(&path), Some("Identifier"));
+/// ```
+pub trait Matcher {
+    /// Return the canonical type name for the segments of a path, if this rule applies.
+    fn canonical(&self, segments: &[&str]) -> Option<&str>;
+}
+
+impl<F> Matcher for F
+where
+    F: Fn(&[&str]) -> Option<&'static str>,
+{
+    fn canonical(&self, segments: &[&str]) -> Option<&str> {
+        self(segments)
+    }
+}
+
+/// A [`Matcher`] that maps every path accepted by a predicate to one canonical type name.
+///
+/// # Examples
+///
+/// ```
+/// use desynt::{DynamicPathResolver, PredicateMatcher};
+/// use syn::Path;
+///
+/// let mut resolver = DynamicPathResolver::default();
+/// resolver.add_matcher(
+///     "proto requests",
+///     PredicateMatcher::new("Request", |segments: &[&str]| match segments {
+///         [package, .., name] => package.ends_with("_proto") && name.ends_with("Request"),
+///         _ => false,
+///     }),
+/// );
+///
+/// let path: Path = syn::parse_str("users_proto::v1::GetUserRequest").unwrap();
+/// assert_eq!(resolver.resolve(&path), Some("Request"));
+/// ```
+#[derive(Clone)]
+pub struct PredicateMatcher<F> {
+    canonical: String,
+    predicate: F,
+}
+
+impl<F> PredicateMatcher<F>
+where
+    F: Fn(&[&str]) -> bool,
+{
+    /// Create a matcher mapping every path accepted by `predicate` to `canonical`.
+    pub fn new<S: Into<String>>(canonical: S, predicate: F) -> Self {
+        Self {
+            canonical: canonical.into(),
+            predicate,
+        }
+    }
+}
+
+impl<F> Matcher for PredicateMatcher<F>
+where
+    F: Fn(&[&str]) -> bool,
+{
+    fn canonical(&self, segments: &[&str]) -> Option<&str> {
+        (self.predicate)(segments).then_some(self.canonical.as_str())
+    }
+}
+
+impl<F> fmt::Debug for PredicateMatcher<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PredicateMatcher")
+            .field("canonical", &self.canonical)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A named [`Matcher`] registered with a storage.
+///
+/// The name identifies the matcher in [`Resolution::pattern`](crate::Resolution::pattern) and when
+/// removing it. Two named matchers are equal if they have the same name and
+/// share the same matcher.
+#[derive(Clone)]
+pub struct NamedMatcher {
+    name: String,
+    matcher: Arc<dyn Matcher + Send + Sync>,
+}
+
+impl NamedMatcher {
+    /// Create a named matcher.
+    pub fn new<S, T>(name: S, matcher: T) -> Self
+    where
+        S: Into<String>,
+        T: Matcher + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            matcher: Arc::new(matcher),
+        }
+    }
+
+    /// Return the name of the matcher.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the matcher.
+    pub fn matcher(&self) -> &(dyn Matcher + Send + Sync) {
+        &*self.matcher
+    }
+}
+
+impl fmt::Debug for NamedMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NamedMatcher")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for NamedMatcher {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && Arc::ptr_eq(&self.matcher, &other.matcher)
+    }
+}
+
+impl Eq for NamedMatcher {}
+
+impl<M> PathResolver<M>
+where
+    M: MappingStorage,
+{
+    /// Ask the storage's matchers for a normalized path, in registration order.
+    pub(crate) fn resolve_matcher<'p>(&self, normalized: &str) -> Option<Match<'_, 'p>> {
+        let mut matchers = self.mappings.matchers().peekable();
+        matchers.peek()?;
+
+        let segments: Vec<&str> = normalized.split("::").collect();
+        matchers.find_map(|(name, matcher)| {
+            Some(Match {
+                canonical: matcher.canonical(&segments)?,
+                pattern: MatchedPattern::Key(name),
+                group: None,
+                strategy: Strategy::Matcher,
+            })
+        })
+    }
+}
@@ -0,0 +1,108 @@
+//! Normalized path text, built without heap allocation for typical paths.
+
+use std::fmt::{self, Write};
+
+use syn::punctuated::Pair;
+use syn::{Path, PathSegment};
+
+/// Paths up to this many bytes long are normalized on the stack.
+const INLINE_CAPACITY: usize = 192;
+
+/// The normalized form of a path, such as `std::collections::HashMap`.
+///
+/// Raw prefixes, a leading `::` and generic arguments are dropped. The text
+/// is written to an inline buffer, and only spills to the heap for paths too
+/// long to fit. Every trailing part of the path, such as
+/// `collections::HashMap`, is a slice of the same text.
+pub(crate) struct NormalizedPath {
+    inline: [u8; INLINE_CAPACITY],
+    len: usize,
+    spilled: Option<String>,
+}
+
+impl NormalizedPath {
+    /// Normalize the segments of a path.
+    pub(crate) fn new(path: &Path) -> Self {
+        let mut text = Self {
+            inline: [0; INLINE_CAPACITY],
+            len: 0,
+            spilled: None,
+        };
+        // `Punctuated::iter` boxes its iterator, `pairs` does not
+        for (index, segment) in path.segments.pairs().map(Pair::into_value).enumerate() {
+            if index > 0 {
+                text.push_str("::");
+            }
+            let start = text.byte_len();
+            // Writing an ident never fails
+            let _ = write!(text, "{}", segment.ident);
+            if text.as_str()[start..].starts_with("r#") {
+                text.remove(start..start + 2);
+            }
+        }
+        text
+    }
+
+    /// Return the normalized text.
+    pub(crate) fn as_str(&self) -> &str {
+        match &self.spilled {
+            Some(text) => text,
+            // Only whole `str`s are written and only ASCII is removed
+            None => std::str::from_utf8(&self.inline[..self.len]).unwrap_or_default(),
+        }
+    }
+
+    fn byte_len(&self) -> usize {
+        self.spilled.as_ref().map_or(self.len, String::len)
+    }
+
+    fn push_str(&mut self, text: &str) {
+        if let Some(spilled) = &mut self.spilled {
+            spilled.push_str(text);
+            return;
+        }
+        let end = self.len + text.len();
+        if end <= INLINE_CAPACITY {
+            self.inline[self.len..end].copy_from_slice(text.as_bytes());
+            self.len = end;
+        } else {
+            let mut spilled = String::with_capacity(end);
+            spilled.push_str(self.as_str());
+            spilled.push_str(text);
+            self.spilled = Some(spilled);
+        }
+    }
+
+    fn remove(&mut self, range: std::ops::Range<usize>) {
+        match &mut self.spilled {
+            Some(spilled) => {
+                spilled.replace_range(range, "");
+            }
+            None => {
+                self.inline.copy_within(range.end..self.len, range.start);
+                self.len -= range.len();
+            }
+        }
+    }
+}
+
+impl Write for NormalizedPath {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.push_str(text);
+        Ok(())
+    }
+}
+
+/// Return the last segment of a path, without allocating.
+pub(crate) fn last_segment(path: &Path) -> Option<&PathSegment> {
+    path.segments.pairs().next_back().map(Pair::into_value)
+}
+
+/// The trailing parts of a normalized path, longest first.
+///
+/// For `a::b::Type` these are `a::b::Type`, `b::Type` and `Type`.
+pub(crate) fn trailing_paths(text: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(text), |rest| {
+        rest.split_once("::").map(|(_, rest)| rest)
+    })
+}
@@ -0,0 +1,418 @@
+//! Mapping patterns that constrain generic arguments, such as `Vec<u8>`.
+//!
+//! A pattern key is a path with angle bracketed arguments. Each argument is
+//! itself a pattern: a path (optionally with its own arguments), `_` to match
+//! any type, `$name` to match any type and capture it, or a reference, slice
+//! or tuple of patterns. For example `Result<$T, my_crate::Error>`.
+//! Lifetime arguments are ignored.
+//!
+//! Keys are parsed into a [`GenericPattern`] once, when a storage that keeps
+//! them indexed adds them; see [`MappingStorage::generic_patterns`].
+
+use std::borrow::Cow;
+
+use syn::{GenericArgument, Path, PathArguments, Type};
+
+use crate::normalized::last_segment;
+use crate::{CanonicalType, MappingStorage, PathResolver, Resolution, Strategy};
+
+/// Generic arguments captured by name, in the order they were matched.
+type Captures = Vec<(String, CanonicalType)>;
+
+/// A parsed generic mapping pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TypePattern {
+    /// `_`, matching any type.
+    Any,
+    /// `$name`, matching any type and capturing it.
+    Capture(String),
+    /// A normalized path, with argument patterns if they were written.
+    Path {
+        path: String,
+        args: Option<Vec<TypePattern>>,
+    },
+    /// `&T` or `&mut T`.
+    Reference {
+        mutable: bool,
+        elem: Box<TypePattern>,
+    },
+    /// `[T]`.
+    Slice(Box<TypePattern>),
+    /// `(A, B)`.
+    Tuple(Vec<TypePattern>),
+}
+
+/// A generic pattern key, such as `Result<$T, my_crate::Error>`, parsed once.
+///
+/// [`DynamicStorage`](crate::DynamicStorage) and
+/// [`IndexedStorage`](crate::IndexedStorage) parse their generic pattern keys
+/// when they are added and return them from
+/// [`MappingStorage::generic_patterns`], so resolving a path with generic
+/// arguments does not parse any key.
+///
+/// # Examples
+///
+/// ```
+/// use desynt::GenericPattern;
+///
+/// let pattern = GenericPattern::parse("Result<$T, my_crate::Error>").unwrap();
+/// assert_eq!(pattern.key(), "Result<$T, my_crate::Error>");
+/// assert!(GenericPattern::parse("my_crate::Error").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenericPattern {
+    key: String,
+    /// The normalized path the pattern applies to, such as `Result`.
+    path: String,
+    args: Vec<TypePattern>,
+    specificity: usize,
+}
+
+impl GenericPattern {
+    /// Parse a normalized key, returning `None` unless it is a path with generic arguments.
+    pub fn parse(key: &str) -> Option<Self> {
+        if !is_generic_pattern(key) {
+            return None;
+        }
+        let TypePattern::Path {
+            path,
+            args: Some(args),
+        } = TypePattern::parse_key(key)?
+        else {
+            return None;
+        };
+        let specificity = 1 + args.iter().map(TypePattern::specificity).sum::<usize>();
+        Some(Self {
+            key: key.to_string(),
+            path,
+            args,
+            specificity,
+        })
+    }
+
+    /// Return the key the pattern was parsed from.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+/// Return `true` if a normalized key constrains generic arguments, such as `Vec<u8>`.
+///
+/// Projection keys such as `<T as Trait>::Assoc` are not generic patterns.
+pub(crate) fn is_generic_pattern(key: &str) -> bool {
+    !key.starts_with('<') && key.contains('<')
+}
+
+impl TypePattern {
+    /// Parse a pattern key, returning `None` unless it is a path with generic arguments.
+    fn parse_key(key: &str) -> Option<Self> {
+        let mut parser = Parser { input: key, pos: 0 };
+        let pattern = parser.pattern()?;
+        parser.skip_whitespace();
+        match pattern {
+            Self::Path { args: Some(_), .. } if parser.pos == key.len() => Some(pattern),
+            _ => None,
+        }
+    }
+
+    /// The number of concrete nodes in the pattern; more specific patterns win.
+    fn specificity(&self) -> usize {
+        match self {
+            Self::Any | Self::Capture(_) => 0,
+            Self::Path { args, .. } => {
+                1 + args.iter().flatten().map(Self::specificity).sum::<usize>()
+            }
+            Self::Reference { elem, .. } | Self::Slice(elem) => 1 + elem.specificity(),
+            Self::Tuple(elems) => 1 + elems.iter().map(Self::specificity).sum::<usize>(),
+        }
+    }
+}
+
+/// A small recursive descent parser for pattern keys.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.input.len() - trimmed.len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn ident(&mut self) -> Option<&str> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let raw = if rest.starts_with("r#") { 2 } else { 0 };
+        let len = rest[raw..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len() - raw);
+        if len == 0 {
+            return None;
+        }
+        let start = self.pos;
+        self.pos += raw + len;
+        Some(&self.input[start..self.pos])
+    }
+
+    /// Parse a comma separated list of patterns up to the closing token.
+    fn list(&mut self, close: &str) -> Option<Vec<TypePattern>> {
+        let mut items = Vec::new();
+        while !self.eat(close) {
+            // Lifetimes are ignored, as they are at use sites
+            if self.eat("'") {
+                self.ident()?;
+            } else {
+                items.push(self.pattern()?);
+            }
+            if !self.eat(",") {
+                return self.eat(close).then_some(items);
+            }
+        }
+        Some(items)
+    }
+
+    fn pattern(&mut self) -> Option<TypePattern> {
+        if self.eat("$") {
+            return Some(TypePattern::Capture(self.ident()?.to_string()));
+        }
+        if self.eat("&") {
+            let start = self.pos;
+            let mutable = self.ident() == Some("mut");
+            if !mutable {
+                self.pos = start;
+            }
+            return Some(TypePattern::Reference {
+                mutable,
+                elem: Box::new(self.pattern()?),
+            });
+        }
+        if self.eat("[") {
+            let elem = self.pattern()?;
+            return self.eat("]").then(|| TypePattern::Slice(Box::new(elem)));
+        }
+        if self.eat("(") {
+            let start = self.pos;
+            let mut elems = self.list(")")?;
+            // `(T)` is a parenthesized type, `(T,)` a one element tuple
+            let written = &self.input[start..self.pos - 1];
+            if elems.len() == 1 && !written.trim_end().ends_with(',') {
+                return elems.pop();
+            }
+            return Some(TypePattern::Tuple(elems));
+        }
+
+        self.eat("::");
+        let first = self.ident()?;
+        if first == "_" {
+            return Some(TypePattern::Any);
+        }
+        let mut path = first.strip_prefix("r#").unwrap_or(first).to_string();
+        while self.rest().trim_start().starts_with("::") {
+            self.eat("::");
+            let ident = self.ident()?;
+            path.push_str("::");
+            path.push_str(ident.strip_prefix("r#").unwrap_or(ident));
+        }
+        let args = if self.eat("<") {
+            Some(self.list(">")?)
+        } else {
+            None
+        };
+        Some(TypePattern::Path { path, args })
+    }
+}
+
+impl<M> PathResolver<M>
+where
+    M: MappingStorage,
+{
+    /// Find the most specific generic pattern key matching a path with generic arguments.
+    pub(crate) fn resolve_generic_pattern(&self, path: &Path) -> Option<Resolution<'_>> {
+        let Some(PathArguments::AngleBracketed(generic)) =
+            last_segment(path).map(|segment| &segment.arguments)
+        else {
+            return None;
+        };
+        // Storages without a parsed index have their keys parsed on each lookup
+        let patterns: Cow<'_, [GenericPattern]> = match self.mappings.generic_patterns() {
+            Some(patterns) => Cow::Borrowed(patterns),
+            None => Cow::Owned(
+                self.mappings
+                    .generic_pattern_keys()
+                    .filter_map(GenericPattern::parse)
+                    .collect(),
+            ),
+        };
+        if patterns.is_empty() {
+            return None;
+        }
+        let args: Vec<&GenericArgument> = generic
+            .args
+            .iter()
+            .filter(|arg| !matches!(arg, GenericArgument::Lifetime(_)))
+            .collect();
+
+        let mut best: Option<(usize, Captures)> = None;
+        let mut use_site_name = None;
+        for (index, pattern) in patterns.iter().enumerate() {
+            if pattern.args.len() != args.len() {
+                continue;
+            }
+            let name = use_site_name.get_or_insert_with(|| self.path_name(path));
+            if self.pattern_name(&pattern.path) != name.as_ref() {
+                continue;
+            }
+
+            let mut captures = Vec::new();
+            let matched = pattern
+                .args
+                .iter()
+                .zip(&args)
+                .all(|(pattern, arg)| self.match_argument(pattern, arg, &mut captures));
+            if !matched {
+                continue;
+            }
+
+            let better = match &best {
+                Some((best, _)) => {
+                    let best = &patterns[*best];
+                    pattern.specificity > best.specificity
+                        || (pattern.specificity == best.specificity && pattern.key < best.key)
+                }
+                None => true,
+            };
+            if better {
+                best = Some((index, captures));
+            }
+        }
+
+        let (index, captures) = best?;
+        let pattern = match patterns {
+            Cow::Borrowed(patterns) => Cow::Borrowed(patterns[index].key()),
+            Cow::Owned(mut patterns) => Cow::Owned(patterns.swap_remove(index).key),
+        };
+        let canonical = self.mappings.get(&pattern)?;
+        Some(Resolution {
+            canonical,
+            pattern,
+            group: None,
+            strategy: Strategy::GenericPattern,
+            captures,
+        })
+    }
+
+    /// The name a path is compared by: its canonical name, or its normalized path.
+    ///
+    /// Generic patterns are not applied, so `Vec` in a pattern and at a use
+    /// site compare equal however either is written.
+    fn path_name(&self, path: &Path) -> Cow<'_, str> {
+        match self.resolve_path_detailed(path) {
+            Some(resolution) => Cow::Borrowed(resolution.canonical),
+            None => Cow::Owned(self.normalize_path(path)),
+        }
+    }
+
+    /// The name a normalized pattern path is compared by, as for [`Self::path_name`].
+    fn pattern_name<'a>(&'a self, normalized: &'a str) -> &'a str {
+        match self.resolve_normalized(normalized) {
+            Some(found) => found.canonical,
+            None => normalized,
+        }
+    }
+
+    /// Match a generic argument against a pattern, recording captures.
+    fn match_argument(
+        &self,
+        pattern: &TypePattern,
+        arg: &GenericArgument,
+        captures: &mut Captures,
+    ) -> bool {
+        match (pattern, arg) {
+            (TypePattern::Any, _) => true,
+            (TypePattern::Capture(name), GenericArgument::Const(expr)) => {
+                capture(captures, name, CanonicalType::Const(expr.clone()))
+            }
+            (_, GenericArgument::Type(ty)) => self.match_type(pattern, ty, captures),
+            _ => false,
+        }
+    }
+
+    /// Match a type against a pattern, recording captures.
+    fn match_type(&self, pattern: &TypePattern, ty: &Type, captures: &mut Captures) -> bool {
+        match (pattern, ty) {
+            (_, Type::Paren(paren)) => self.match_type(pattern, &paren.elem, captures),
+            (_, Type::Group(group)) => self.match_type(pattern, &group.elem, captures),
+            (TypePattern::Any, _) => true,
+            (TypePattern::Capture(name), _) => capture(captures, name, self.resolve_type(ty)),
+            (TypePattern::Path { path, args }, Type::Path(type_path))
+                if type_path.qself.is_none() =>
+            {
+                if self.pattern_name(path) != self.path_name(&type_path.path) {
+                    return false;
+                }
+                let Some(pattern_args) = args else {
+                    return true;
+                };
+                let written: Vec<&GenericArgument> = match type_path
+                    .path
+                    .segments
+                    .last()
+                    .map(|segment| &segment.arguments)
+                {
+                    Some(PathArguments::AngleBracketed(generic)) => generic
+                        .args
+                        .iter()
+                        .filter(|arg| !matches!(arg, GenericArgument::Lifetime(_)))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                written.len() == pattern_args.len()
+                    && pattern_args
+                        .iter()
+                        .zip(written)
+                        .all(|(pattern, arg)| self.match_argument(pattern, arg, captures))
+            }
+            (TypePattern::Reference { mutable, elem }, Type::Reference(reference)) => {
+                *mutable == reference.mutability.is_some()
+                    && self.match_type(elem, &reference.elem, captures)
+            }
+            (TypePattern::Slice(elem), Type::Slice(slice)) => {
+                self.match_type(elem, &slice.elem, captures)
+            }
+            (TypePattern::Tuple(elems), Type::Tuple(tuple)) => {
+                elems.len() == tuple.elems.len()
+                    && elems
+                        .iter()
+                        .zip(&tuple.elems)
+                        .all(|(pattern, ty)| self.match_type(pattern, ty, captures))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Record a capture, requiring repeated names to capture equal types.
+fn capture(captures: &mut Captures, name: &str, ty: CanonicalType) -> bool {
+    match captures.iter().find(|(captured, _)| captured == name) {
+        Some((_, existing)) => *existing == ty,
+        None => {
+            captures.push((name.to_string(), ty));
+            true
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Configuration of the fallback strategies used by [`PathResolver`](crate::PathResolver).
+
+/// Controls which resolution strategies a [`PathResolver`](crate::PathResolver) may use.
+///
+/// Looking up the full normalized path is always enabled. Every fallback can be
+/// switched off individually, from the permissive matching used by linting tools
+/// down to exact-only lookups for strict proc macros.
+///
+/// New strategies may be added in minor releases, so policies are built from
+/// the constants and the `with_*` methods rather than struct literals.
+///
+/// # Examples
+///
+/// ```
+/// use desynt::{DynamicPathResolver, ResolutionPolicy};
+/// use syn::Path;
+///
+/// let mut resolver = DynamicPathResolver::with_all_groups();
+/// let path: Path = syn::parse_str("option::Option<u8>").unwrap();
+/// assert_eq!(resolver.resolve(&path), Some("Option"));
+///
+/// resolver.set_policy(ResolutionPolicy::EXACT);
+/// assert_eq!(resolver.resolve(&path), None);
+///
+/// let bare: Path = syn::parse_str("Option<u8>").unwrap();
+/// resolver.set_policy(ResolutionPolicy::EXACT.with_builtin_patterns(true));
+/// assert_eq!(resolver.resolve(&bare), Some("Option"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ResolutionPolicy {
+    /// Whether to try shortened forms of multi-segment paths (e.g. `option::Option` -> `Option`).
+    pub progressive: bool,
+    /// Whether bare type names may match the last segment of longer custom patterns.
+    pub suffix: bool,
+    /// Whether bare type names may match built-in type group paths (e.g. `Option` -> `std::option::Option`).
+    pub builtin_patterns: bool,
+    /// Whether multi-segment paths that look like standard library shortenings
+    /// (e.g. `collections::HashMap`) may fall back to bare type name matching.
+    pub stdlib_heuristic: bool,
+    /// Whether every multi-segment path may fall back to bare type name matching,
+    /// regardless of [`stdlib_heuristic`](Self::stdlib_heuristic).
+    pub unrestricted_heuristic: bool,
+    /// Minimum number of trailing path segments that must match a pattern when
+    /// shortening a path or matching a bare type name. Exact matches are always accepted.
+    pub min_matching_segments: usize,
+}
+
+impl ResolutionPolicy {
+    /// Only exact lookups of the full normalized path.
+    pub const EXACT: Self = Self {
+        progressive: false,
+        suffix: false,
+        builtin_patterns: false,
+        stdlib_heuristic: false,
+        unrestricted_heuristic: false,
+        min_matching_segments: 1,
+    };
+
+    /// Exact lookups, plus bare type names matching built-in type group paths.
+    pub const STRICT: Self = Self {
+        builtin_patterns: true,
+        ..Self::EXACT
+    };
+
+    /// The default behavior: every strategy, with suffix matching of multi-segment
+    /// paths limited to standard library shortenings.
+    pub const DEFAULT: Self = Self {
+        progressive: true,
+        suffix: true,
+        builtin_patterns: true,
+        stdlib_heuristic: true,
+        unrestricted_heuristic: false,
+        min_matching_segments: 1,
+    };
+
+    /// The most permissive matching: any multi-segment path may fall back to
+    /// matching its bare type name.
+    pub const PERMISSIVE: Self = Self {
+        unrestricted_heuristic: true,
+        ..Self::DEFAULT
+    };
+
+    /// Set [`progressive`](Self::progressive), builder style.
+    pub const fn with_progressive(self, enabled: bool) -> Self {
+        let mut policy = self;
+        policy.progressive = enabled;
+        policy
+    }
+
+    /// Set [`suffix`](Self::suffix), builder style.
+    pub const fn with_suffix(self, enabled: bool) -> Self {
+        let mut policy = self;
+        policy.suffix = enabled;
+        policy
+    }
+
+    /// Set [`builtin_patterns`](Self::builtin_patterns), builder style.
+    pub const fn with_builtin_patterns(self, enabled: bool) -> Self {
+        let mut policy = self;
+        policy.builtin_patterns = enabled;
+        policy
+    }
+
+    /// Set [`stdlib_heuristic`](Self::stdlib_heuristic), builder style.
+    pub const fn with_stdlib_heuristic(self, enabled: bool) -> Self {
+        let mut policy = self;
+        policy.stdlib_heuristic = enabled;
+        policy
+    }
+
+    /// Set [`unrestricted_heuristic`](Self::unrestricted_heuristic), builder style.
+    pub const fn with_unrestricted_heuristic(self, enabled: bool) -> Self {
+        let mut policy = self;
+        policy.unrestricted_heuristic = enabled;
+        policy
+    }
+
+    /// Set [`min_matching_segments`](Self::min_matching_segments), builder style.
+    pub const fn with_min_matching_segments(self, segments: usize) -> Self {
+        let mut policy = self;
+        policy.min_matching_segments = segments;
+        policy
+    }
+}
+
+impl Default for ResolutionPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -0,0 +1,370 @@
+//! Associated type projections such as `<T as Trait>::Assoc` and `Self::Item`.
+//!
+//! Projections are stored as ordinary mappings, keyed as
+//! `<SelfType as path::to::Trait>::Assoc` with `_` standing for any type.
+
+use std::borrow::Cow;
+
+use syn::{Path, PathSegment, QSelf, Type, TypePath};
+
+use crate::{
+    CanonicalPath, CanonicalType, DynamicPathResolver, MappingStorage, PathResolver,
+    ProjectionError, Resolution, Scope, Strategy, StripRaw,
+};
+
+/// A projection written in source, with `Self` and generic bounds already applied.
+pub(crate) struct Projection {
+    /// The type the associated type is projected from.
+    qself: Type,
+    /// The traits that may declare the associated type; empty if unknown.
+    traits: Vec<Path>,
+    /// The associated type name, segments joined with `::`.
+    assoc: String,
+}
+
+impl Projection {
+    /// Build the projection a type path refers to, if it is one.
+    ///
+    /// Besides explicit `<T as Trait>::Assoc` paths, with a scope this covers
+    /// `Self::Assoc` inside an impl block and `T::Assoc` for bounded parameters.
+    pub(crate) fn from_type_path(type_path: &TypePath, scope: Option<&Scope>) -> Option<Self> {
+        let segments = &type_path.path.segments;
+        if let Some(QSelf { ty, position, .. }) = &type_path.qself {
+            let qself = match (scope.and_then(Scope::self_type), ty.as_ref()) {
+                (Some(self_type), Type::Path(path)) if is_self(&path.path) => self_type.clone(),
+                _ => (**ty).clone(),
+            };
+            let traits = if *position > 0 {
+                vec![Path {
+                    leading_colon: type_path.path.leading_colon,
+                    segments: segments.iter().take(*position).cloned().collect(),
+                }]
+            } else {
+                Vec::new()
+            };
+            return Some(Self {
+                qself,
+                traits,
+                assoc: join_segments(segments.iter().skip(*position)),
+            });
+        }
+
+        Self::from_scoped_path(&type_path.path, scope?)
+    }
+
+    /// Build the projection a `Self::Assoc` or `T::Assoc` path refers to in a scope.
+    pub(crate) fn from_scoped_path(path: &Path, scope: &Scope) -> Option<Self> {
+        let segments = &path.segments;
+        let first = segments.first()?;
+        if path.leading_colon.is_some() || segments.len() < 2 || !first.arguments.is_none() {
+            return None;
+        }
+        let assoc = join_segments(segments.iter().skip(1));
+        let name = first.ident.strip_raw().to_string();
+
+        if name == "Self" {
+            let qself = scope
+                .self_type()
+                .cloned()
+                .unwrap_or_else(|| self_path(first));
+            return Some(Self {
+                qself,
+                traits: scope.self_trait().cloned().into_iter().collect(),
+                assoc,
+            });
+        }
+
+        let bounds = scope.bounds(&name);
+        if bounds.is_empty() {
+            return None;
+        }
+        Some(Self {
+            qself: self_path(first),
+            traits: bounds.to_vec(),
+            assoc,
+        })
+    }
+
+    /// The mapping keys this projection may be registered under, in lookup order.
+    fn keys(&self) -> Vec<String> {
+        let self_type = type_key(&self.qself);
+        if self.traits.is_empty() {
+            return vec![format!("<{}>::{}", self_type, self.assoc)];
+        }
+        self.traits
+            .iter()
+            .map(|trait_path| {
+                format!(
+                    "<{} as {}>::{}",
+                    self_type,
+                    path_key(trait_path),
+                    self.assoc
+                )
+            })
+            .collect()
+    }
+}
+
+impl<M> PathResolver<M>
+where
+    M: MappingStorage,
+{
+    /// Resolve an associated type projection such as `<Vec<T> as IntoIterator>::Item`.
+    ///
+    /// Projections are registered with [`DynamicPathResolver::add_projection`]
+    /// or stored directly under keys of the form `<Type as path::to::Trait>::Assoc`,
+    /// where `_` as the type matches any type. A written projection matches a
+    /// key with the same or a `_` type, the same associated type and the same
+    /// trait, where a trait written by its bare name matches any path ending
+    /// with it. Exact keys win over `_`, and full trait paths over bare names.
+    ///
+    /// Returns a [`ProjectionError`] if the path is not a projection or no
+    /// mapping matches it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::DynamicPathResolver;
+    /// use syn::TypePath;
+    ///
+    /// let mut resolver = DynamicPathResolver::default();
+    /// resolver
+    ///     .add_projection("<_ as frame_system::Config>::AccountId", "AccountId")
+    ///     .unwrap();
+    ///
+    /// let ty: TypePath = syn::parse_str("<T as frame_system::Config>::AccountId").unwrap();
+    /// assert_eq!(resolver.resolve_projection(&ty), Ok("AccountId"));
+    ///
+    /// let ty: TypePath = syn::parse_str("<T as Config>::Balance").unwrap();
+    /// assert!(resolver.resolve_projection(&ty).is_err());
+    /// ```
+    pub fn resolve_projection(&self, type_path: &TypePath) -> Result<&str, ProjectionError> {
+        self.projection(type_path, None)
+    }
+
+    /// Resolve an associated type projection written inside `scope`.
+    ///
+    /// In addition to [`PathResolver::resolve_projection`], this resolves
+    /// `Self::Assoc` and `<Self as Trait>::Assoc` using the impl block of the
+    /// scope (see [`Scope::with_impl`]), and `T::Assoc` using the trait bounds
+    /// of the generic parameter `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::{DynamicPathResolver, Scope};
+    /// use syn::{ItemImpl, TypePath};
+    ///
+    /// let mut resolver = DynamicPathResolver::default();
+    /// resolver.add_projection("<Counter as Iterator>::Item", "u32").unwrap();
+    /// resolver
+    ///     .add_projection("<_ as frame_system::Config>::AccountId", "AccountId")
+    ///     .unwrap();
+    ///
+    /// let item: ItemImpl = syn::parse_str(
+    ///     "impl<T: frame_system::Config> Iterator for Counter { type Item = u32; }",
+    /// )
+    /// .unwrap();
+    /// let scope = Scope::new().with_impl(&item);
+    ///
+    /// let ty: TypePath = syn::parse_str("Self::Item").unwrap();
+    /// assert_eq!(resolver.resolve_projection_in_scope(&ty, &scope), Ok("u32"));
+    ///
+    /// let ty: TypePath = syn::parse_str("T::AccountId").unwrap();
+    /// assert_eq!(resolver.resolve_projection_in_scope(&ty, &scope), Ok("AccountId"));
+    /// ```
+    pub fn resolve_projection_in_scope(
+        &self,
+        type_path: &TypePath,
+        scope: &Scope,
+    ) -> Result<&str, ProjectionError> {
+        self.projection(type_path, Some(scope))
+    }
+
+    /// Resolve a type path as a projection, reporting failures.
+    fn projection(
+        &self,
+        type_path: &TypePath,
+        scope: Option<&Scope>,
+    ) -> Result<&str, ProjectionError> {
+        let Some(projection) = Projection::from_type_path(type_path, scope) else {
+            return Err(ProjectionError {
+                projection: self.normalize_path(&type_path.path),
+            });
+        };
+        match self.resolve_projection_detailed(&projection) {
+            Some(resolution) => Ok(resolution.canonical),
+            None => Err(ProjectionError {
+                projection: projection.keys().swap_remove(0),
+            }),
+        }
+    }
+
+    /// Find the best mapping for a projection.
+    pub(crate) fn resolve_projection_detailed(
+        &self,
+        projection: &Projection,
+    ) -> Option<Resolution<'_>> {
+        let keys = projection.keys();
+        for key in &keys {
+            if let Some(canonical) = self.mappings.get(key) {
+                return Some(Resolution {
+                    canonical,
+                    pattern: Cow::Owned(key.clone()),
+                    group: None,
+                    strategy: Strategy::Exact,
+                    captures: Vec::new(),
+                });
+            }
+        }
+
+        // Rank the compatible keys: exact type before `_`, full trait path
+        // before a bare trait name, then lexicographic order
+        let written: Vec<(&str, Option<&str>, &str)> =
+            keys.iter().filter_map(|key| split_key(key)).collect();
+        self.mappings
+            .keys()
+            .filter_map(|key| {
+                let (self_type, trait_path, assoc) = split_key(key)?;
+                let rank = written.iter().find_map(|written| {
+                    if assoc != written.2 || (self_type != "_" && self_type != written.0) {
+                        return None;
+                    }
+                    let trait_rank = match (trait_path, written.1) {
+                        (_, None) => 1,
+                        (Some(registered), Some(written)) if registered == written => 0,
+                        (Some(registered), Some(written))
+                            if is_path_suffix(registered, written) =>
+                        {
+                            1
+                        }
+                        _ => return None,
+                    };
+                    Some((self_type == "_", trait_rank))
+                })?;
+                Some((rank, key))
+            })
+            .min()
+            .and_then(|(_, key)| {
+                Some(Resolution {
+                    canonical: self.mappings.get(key)?,
+                    pattern: Cow::Borrowed(key),
+                    group: None,
+                    strategy: Strategy::ProjectionFallback,
+                    captures: Vec::new(),
+                })
+            })
+    }
+
+    /// Resolve a projection type into a [`CanonicalType`].
+    ///
+    /// Projections without a mapping are kept as [`CanonicalType::Projection`].
+    pub(crate) fn canonical_projection(
+        &self,
+        projection: &Projection,
+        scope: Option<&Scope>,
+        expand: &mut dyn FnMut(&Path) -> Option<CanonicalType>,
+    ) -> CanonicalType {
+        if let Some(resolution) = self.resolve_projection_detailed(projection) {
+            return CanonicalType::Path(CanonicalPath {
+                name: resolution.canonical.to_string(),
+                resolved: true,
+                args: Vec::new(),
+                bindings: Vec::new(),
+            });
+        }
+
+        CanonicalType::Projection {
+            qself: Box::new(self.canonical_type(&projection.qself, scope, expand)),
+            trait_path: match projection.traits.as_slice() {
+                [trait_path] => Some(self.canonical_path(trait_path, scope, expand)),
+                _ => None,
+            },
+            assoc: projection.assoc.clone(),
+        }
+    }
+}
+
+impl DynamicPathResolver {
+    /// Add a mapping for an associated type projection.
+    ///
+    /// The projection is written as a qualified path, such as
+    /// `<_ as frame_system::Config>::AccountId`, where `_` matches any type.
+    /// Generic arguments and raw prefixes are ignored. See
+    /// [`PathResolver::resolve_projection`] for how projections are matched.
+    ///
+    /// Returns an error if the projection cannot be parsed as a qualified path.
+    pub fn add_projection<S>(&mut self, projection: &str, canonical_type: S) -> syn::Result<()>
+    where
+        S: Into<String>,
+    {
+        let type_path: TypePath = syn::parse_str(projection)?;
+        let Some(projection) = Projection::from_type_path(&type_path, None) else {
+            return Err(syn::Error::new_spanned(
+                type_path,
+                "expected a qualified path such as `<_ as Trait>::Assoc`",
+            ));
+        };
+        let key = projection.keys().swap_remove(0);
+        self.mappings.insert(key, canonical_type.into());
+        Ok(())
+    }
+}
+
+/// Return `true` if a path is the bare `Self` type.
+fn is_self(path: &Path) -> bool {
+    path.leading_colon.is_none() && path.segments.len() == 1 && path.segments[0].ident == "Self"
+}
+
+/// A path type consisting of a single segment, without its arguments.
+fn self_path(segment: &PathSegment) -> Type {
+    Type::Path(TypePath {
+        qself: None,
+        path: PathSegment::from(segment.ident.clone()).into(),
+    })
+}
+
+/// Join the identifiers of path segments with `::`, without raw prefixes.
+fn join_segments<'a>(segments: impl Iterator<Item = &'a PathSegment>) -> String {
+    segments
+        .map(|segment| segment.ident.strip_raw().to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// The normalized form of a path in a projection key.
+fn path_key(path: &Path) -> String {
+    join_segments(path.segments.iter())
+}
+
+/// The normalized form of a type in a projection key.
+///
+/// Types other than `_` and plain paths have no key and only match `_`.
+fn type_key(ty: &Type) -> String {
+    match ty {
+        Type::Infer(_) => "_".to_string(),
+        Type::Path(type_path) if type_path.qself.is_none() => path_key(&type_path.path),
+        Type::Paren(paren) => type_key(&paren.elem),
+        Type::Group(group) => type_key(&group.elem),
+        _ => "?".to_string(),
+    }
+}
+
+/// Split a projection key into its type, trait and associated type.
+pub(crate) fn split_key(key: &str) -> Option<(&str, Option<&str>, &str)> {
+    let inner = key.strip_prefix('<')?;
+    let (qualified, assoc) = inner.rsplit_once(">::")?;
+    Some(match qualified.split_once(" as ") {
+        Some((self_type, trait_path)) => (self_type, Some(trait_path), assoc),
+        None => (qualified, None, assoc),
+    })
+}
+
+/// Return `true` if one path is the other with leading segments removed.
+fn is_path_suffix(a: &str, b: &str) -> bool {
+    let ends_with = |long: &str, short: &str| {
+        long.strip_suffix(short)
+            .is_some_and(|prefix| prefix.ends_with("::"))
+    };
+    ends_with(a, b) || ends_with(b, a)
+}
@@ -0,0 +1,186 @@
+//! Detailed results describing how a path was resolved.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::{CanonicalType, TypeGroup};
+
+/// The resolution strategy that produced a match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    /// The full normalized path matched a pattern exactly.
+    Exact,
+    /// The full normalized path matched a glob key such as `my_crate::*::UserId`.
+    Glob,
+    /// A custom [`Matcher`](crate::Matcher) accepted the full normalized path.
+    Matcher,
+    /// A shortened form of the path, with leading segments removed, matched a pattern.
+    Progressive,
+    /// The bare type name matched the last segment of a longer custom pattern.
+    Suffix,
+    /// The bare type name matched a builtin type group path under `std`, `core` or `alloc`.
+    BuiltinPattern,
+    /// A pattern key constraining generic arguments, such as `Vec<u8>`, matched.
+    GenericPattern,
+    /// A projection key with a `_` self type or a shorter trait path, such as
+    /// `<_ as Config>::AccountId`, matched an associated type projection.
+    ProjectionFallback,
+}
+
+/// A detailed description of how a path was resolved.
+///
+/// Returned by [`PathResolver::resolve_detailed`](crate::PathResolver::resolve_detailed).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution<'a> {
+    /// The canonical type name.
+    pub canonical: &'a str,
+    /// The pattern key that matched, e.g. `std::option::Option`.
+    pub pattern: Cow<'a, str>,
+    /// The builtin type group that provided the match, or `None` for custom mappings.
+    pub group: Option<TypeGroup>,
+    /// The strategy that produced the match.
+    pub strategy: Strategy,
+    /// The generic arguments captured by `$name` in a generic pattern key,
+    /// in the order they were matched. Empty for other strategies.
+    pub captures: Vec<(String, CanonicalType)>,
+}
+
+impl Resolution<'_> {
+    /// Return `true` if the full path matched a pattern exactly.
+    pub const fn is_exact(&self) -> bool {
+        matches!(self.strategy, Strategy::Exact)
+    }
+
+    /// Return `true` if the match relied on shortening or suffix heuristics.
+    ///
+    /// Glob, matcher and generic pattern matches are not heuristic: they
+    /// come from rules the user registered for the full path.
+    pub const fn is_heuristic(&self) -> bool {
+        matches!(
+            self.strategy,
+            Strategy::Progressive
+                | Strategy::Suffix
+                | Strategy::BuiltinPattern
+                | Strategy::ProjectionFallback
+        )
+    }
+
+    /// Return `true` if the match came from a builtin type group.
+    pub const fn is_builtin(&self) -> bool {
+        self.group.is_some()
+    }
+
+    /// Return the generic argument captured under a name, if any.
+    pub fn capture(&self, name: &str) -> Option<&CanonicalType> {
+        self.captures
+            .iter()
+            .find(|(captured, _)| captured == name)
+            .map(|(_, ty)| ty)
+    }
+}
+
+/// Where the pattern of a [`Match`] is borrowed from.
+pub(crate) enum MatchedPattern<'a, 'p> {
+    /// A key of the storage, a matcher name or a builtin path.
+    Key(&'a str),
+    /// Part of the normalized path being resolved.
+    Path(&'p str),
+    /// A path built while resolving, such as one rewritten by a facade rule.
+    Owned(String),
+}
+
+/// A match made while resolving a path, before its pattern is copied out of the path.
+///
+/// Resolving through a `Match` lets [`PathResolver::resolve`](crate::PathResolver::resolve)
+/// return the canonical name without allocating a [`Resolution`] pattern.
+pub(crate) struct Match<'a, 'p> {
+    pub(crate) canonical: &'a str,
+    pub(crate) pattern: MatchedPattern<'a, 'p>,
+    pub(crate) group: Option<TypeGroup>,
+    pub(crate) strategy: Strategy,
+}
+
+impl<'a> Match<'a, '_> {
+    /// Describe the match, copying its pattern if it is part of the resolved path.
+    pub(crate) fn into_resolution(self) -> Resolution<'a> {
+        Resolution {
+            canonical: self.canonical,
+            pattern: match self.pattern {
+                MatchedPattern::Key(key) => Cow::Borrowed(key),
+                MatchedPattern::Path(path) => Cow::Owned(path.to_string()),
+                MatchedPattern::Owned(path) => Cow::Owned(path),
+            },
+            group: self.group,
+            strategy: self.strategy,
+            captures: Vec::new(),
+        }
+    }
+
+    /// Copy the pattern out of the resolved path, so it may outlive the path.
+    pub(crate) fn into_owned_path<'q>(self) -> Match<'a, 'q> {
+        Match {
+            canonical: self.canonical,
+            pattern: match self.pattern {
+                MatchedPattern::Key(key) => MatchedPattern::Key(key),
+                MatchedPattern::Path(path) => MatchedPattern::Owned(path.to_string()),
+                MatchedPattern::Owned(path) => MatchedPattern::Owned(path),
+            },
+            group: self.group,
+            strategy: self.strategy,
+        }
+    }
+}
+
+/// A custom mapping that a bare type name could refer to.
+///
+/// Returned by [`PathResolver::resolve_candidates`](crate::PathResolver::resolve_candidates).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Candidate<'a> {
+    /// The pattern key of the mapping.
+    pub pattern: &'a str,
+    /// The canonical type name the pattern maps to.
+    pub canonical: &'a str,
+    /// The tie-breaking priority of the pattern.
+    pub priority: i32,
+}
+
+/// Error returned by [`PathResolver::resolve_strict`](crate::PathResolver::resolve_strict)
+/// when a type name matches several patterns that cannot be told apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmbiguityError {
+    /// The bare type name that was looked up.
+    pub name: String,
+    /// The equally ranked `(pattern, canonical)` pairs, best first.
+    pub candidates: Vec<(String, String)>,
+}
+
+impl fmt::Display for AmbiguityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is ambiguous between ", self.name)?;
+        for (index, (pattern, canonical)) in self.candidates.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}` ({})", pattern, canonical)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for AmbiguityError {}
+
+/// Error returned by [`PathResolver::resolve_projection`](crate::PathResolver::resolve_projection)
+/// when an associated type projection has no mapping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectionError {
+    /// The projection that was looked up, e.g. `<T as Config>::AccountId`.
+    pub projection: String,
+}
+
+impl fmt::Display for ProjectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no mapping for projection `{}`", self.projection)
+    }
+}
+
+impl std::error::Error for ProjectionError {}
@@ -0,0 +1,122 @@
+//! An object-safe interface over resolvers of any storage type.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use syn::Path;
+
+use crate::{LayeredResolver, MappingStorage, PathResolver};
+
+/// The lookup operations of a resolver, independent of its storage.
+///
+/// Every [`PathResolver`] implements this trait, so resolvers built from
+/// different storage backends can be kept together as `Box<dyn Resolve>`
+/// or taken as `&dyn Resolve`. Tests can implement it with a mock.
+///
+/// # Examples
+///
+/// ```
+/// use desynt::{DynamicPathResolver, PRIMITIVE_RESOLVER, Resolve};
+/// use syn::Path;
+///
+/// let mut custom = DynamicPathResolver::default();
+/// custom.add_mapping("my_crate::types::UserId", "UserId");
+///
+/// let resolvers: Vec<Box<dyn Resolve>> = vec![Box::new(custom), Box::new(PRIMITIVE_RESOLVER)];
+///
+/// let path: Path = syn::parse_str("u8").unwrap();
+/// let found = resolvers.iter().find_map(|resolver| resolver.resolve(&path));
+/// assert_eq!(found, Some("u8"));
+/// ```
+pub trait Resolve {
+    /// Resolve a syn [`Path`] to its canonical type name.
+    ///
+    /// See [`PathResolver::resolve`].
+    fn resolve(&self, path: &Path) -> Option<&str>;
+
+    /// Return `true` if the resolver has a mapping for the given path.
+    ///
+    /// See [`PathResolver::has_mapping`].
+    fn has_mapping(&self, path: &Path) -> bool;
+
+    /// Return an iterator over all canonical type names known to the resolver.
+    ///
+    /// See [`PathResolver::canonical_types`].
+    fn canonical_types(&self) -> Box<dyn Iterator<Item = &str> + '_>;
+
+    /// Return an iterator over all registered path patterns.
+    ///
+    /// See [`PathResolver::path_patterns`].
+    fn path_patterns(&self) -> Box<dyn Iterator<Item = &str> + '_>;
+}
+
+impl<M> Resolve for PathResolver<M>
+where
+    M: MappingStorage,
+{
+    fn resolve(&self, path: &Path) -> Option<&str> {
+        PathResolver::resolve(self, path)
+    }
+
+    fn has_mapping(&self, path: &Path) -> bool {
+        PathResolver::has_mapping(self, path)
+    }
+
+    fn canonical_types(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(PathResolver::canonical_types(self))
+    }
+
+    fn path_patterns(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(PathResolver::path_patterns(self))
+    }
+}
+
+/// Canonical types and path patterns are those of every layer, in order of precedence.
+impl Resolve for LayeredResolver<'_> {
+    fn resolve(&self, path: &Path) -> Option<&str> {
+        LayeredResolver::resolve(self, path)
+    }
+
+    fn has_mapping(&self, path: &Path) -> bool {
+        LayeredResolver::has_mapping(self, path)
+    }
+
+    fn canonical_types(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(LayeredResolver::canonical_types(self))
+    }
+
+    fn path_patterns(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(LayeredResolver::path_patterns(self))
+    }
+}
+
+/// Implement [`Resolve`] for a pointer type by delegating to the pointee.
+macro_rules! delegate_resolve {
+    ($pointer:ty) => {
+        impl<T> Resolve for $pointer
+        where
+            T: Resolve + ?Sized,
+        {
+            fn resolve(&self, path: &Path) -> Option<&str> {
+                (**self).resolve(path)
+            }
+
+            fn has_mapping(&self, path: &Path) -> bool {
+                (**self).has_mapping(path)
+            }
+
+            fn canonical_types(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+                (**self).canonical_types()
+            }
+
+            fn path_patterns(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+                (**self).path_patterns()
+            }
+        }
+    };
+}
+
+delegate_resolve!(&T);
+delegate_resolve!(Box<T>);
+delegate_resolve!(Rc<T>);
+delegate_resolve!(Arc<T>);
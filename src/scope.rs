@@ -0,0 +1,530 @@
+//! Name resolution through the `use` declarations of a module.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use proc_macro2::Span;
+use syn::ext::IdentExt;
+use syn::parse::Parser;
+use syn::visit::Visit;
+use syn::{
+    Ident, Item, ItemImpl, ItemUse, Path, PathSegment, Type, TypeParamBound, UseTree,
+    WherePredicate,
+};
+
+use crate::projection::Projection;
+use crate::{MappingStorage, PathResolver, Resolution, Strategy, StripRaw};
+
+/// The imports visible at a point in Rust source, built from `use` declarations.
+///
+/// A scope records every name brought in by `use` items, including renames,
+/// groups, glob imports and `self` imports. [`PathResolver::resolve_in_scope`]
+/// expands the first segment of a path through the scope before running the
+/// regular lookup, so `Map<K, V>` resolves after `use std::collections::HashMap as Map;`.
+///
+/// A scope can also carry the absolute path of its module, so that
+/// `crate::`, `self::` and `super::` paths resolve the same way from every
+/// module of a crate.
+///
+/// [`PathResolver::resolve_in_scope`]: crate::PathResolver::resolve_in_scope
+///
+/// # Examples
+///
+/// ```
+/// use desynt::{DynamicPathResolver, Scope};
+/// use syn::{ItemUse, Path};
+///
+/// let item: ItemUse = syn::parse_str("use std::collections::HashMap as Map;").unwrap();
+/// let scope = Scope::from_uses([&item]);
+///
+/// let resolver = DynamicPathResolver::with_all_groups();
+/// let path: Path = syn::parse_str("Map<K, V>").unwrap();
+/// assert_eq!(resolver.resolve_in_scope(&path, &scope), Some("HashMap"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scope {
+    /// Names brought into scope, mapped to the segments of the path they import.
+    imports: HashMap<String, Vec<String>>,
+    /// Module paths whose items are glob imported, in declaration order.
+    globs: Vec<Vec<String>>,
+    /// The absolute path of the current module, starting with the crate name.
+    module: Vec<String>,
+    /// The `Self` type of the enclosing impl block.
+    self_type: Option<Type>,
+    /// The trait implemented by the enclosing impl block.
+    self_trait: Option<Path>,
+    /// Trait bounds of generic parameters, by parameter name.
+    bounds: HashMap<String, Vec<Path>>,
+}
+
+impl Scope {
+    /// Create an empty scope.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a scope from `use` declarations.
+    pub fn from_uses<'a, I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = &'a ItemUse>,
+    {
+        let mut scope = Self::new();
+        for item in items {
+            scope.add_use(item);
+        }
+        scope
+    }
+
+    /// Build a scope from the `use` declarations among a list of items.
+    ///
+    /// Items other than `use` declarations, including nested modules, are ignored.
+    pub fn from_items<'a, I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Item>,
+    {
+        Self::from_uses(items.into_iter().filter_map(|item| match item {
+            Item::Use(item_use) => Some(item_use),
+            _ => None,
+        }))
+    }
+
+    /// Build a scope from the top-level `use` declarations of a file.
+    pub fn from_file(file: &syn::File) -> Self {
+        Self::from_items(&file.items)
+    }
+
+    /// Set the absolute path of the module the scope belongs to, builder style.
+    ///
+    /// The first segment is the crate name, e.g. `my_crate::api::v1`. It is
+    /// used to rewrite paths starting with `crate::`, `self::` or `super::`
+    /// into absolute paths before lookup by
+    /// [`PathResolver::resolve_in_scope`] and the other `*_in_scope` methods.
+    /// [`PathResolver::resolve`] looks such paths up as written.
+    ///
+    /// [`PathResolver::resolve_in_scope`]: crate::PathResolver::resolve_in_scope
+    /// [`PathResolver::resolve`]: crate::PathResolver::resolve
+    ///
+    /// Returns an error if a segment of the path is not an identifier.
+    pub fn with_module(mut self, module_path: &str) -> syn::Result<Self> {
+        self.set_module(module_path)?;
+        Ok(self)
+    }
+
+    /// Set the absolute path of the module the scope belongs to.
+    ///
+    /// Returns an error, leaving the scope unchanged, if a segment of the
+    /// path is not an identifier. See [`Scope::with_module`].
+    pub fn set_module(&mut self, module_path: &str) -> syn::Result<()> {
+        self.module = parse_segments(module_path)?;
+        Ok(())
+    }
+
+    /// Return the absolute path of the current module, if one was set.
+    pub fn module(&self) -> Option<String> {
+        (!self.module.is_empty()).then(|| self.module.join("::"))
+    }
+
+    /// Return the crate name of the current module, if one was set.
+    pub fn crate_name(&self) -> Option<&str> {
+        self.module.first().map(String::as_str)
+    }
+
+    /// Set the impl block the scope is inside of, builder style.
+    ///
+    /// The `Self` type, the implemented trait and the trait bounds of the
+    /// impl's generic parameters are used to resolve `Self`, `Self::Assoc`
+    /// and `T::Assoc` paths. See [`PathResolver::resolve_projection_in_scope`].
+    ///
+    /// [`PathResolver::resolve_projection_in_scope`]: crate::PathResolver::resolve_projection_in_scope
+    pub fn with_impl(mut self, item: &ItemImpl) -> Self {
+        self.set_impl(item);
+        self
+    }
+
+    /// Set the impl block the scope is inside of.
+    ///
+    /// Replaces any previous `Self` type and trait, and adds the bounds of the
+    /// impl's generic parameters and `where` clause. See [`Scope::with_impl`].
+    ///
+    /// A `Self` type that itself mentions `Self`, as in the invalid
+    /// `impl Trait for Self` or `impl Trait for Box<Self>`, is not recorded,
+    /// so resolving `Self` cannot expand forever.
+    pub fn set_impl(&mut self, item: &ItemImpl) {
+        self.self_type = Some((*item.self_ty).clone()).filter(|ty| !mentions_self(ty));
+        self.self_trait = item.trait_.as_ref().map(|(_, path, _)| path.clone());
+
+        for param in item.generics.type_params() {
+            for bound in &param.bounds {
+                if let TypeParamBound::Trait(bound) = bound {
+                    self.add_bound(&param.ident.strip_raw().to_string(), bound.path.clone());
+                }
+            }
+        }
+        for predicate in item
+            .generics
+            .where_clause
+            .iter()
+            .flat_map(|clause| &clause.predicates)
+        {
+            let WherePredicate::Type(predicate) = predicate else {
+                continue;
+            };
+            let Type::Path(bounded) = &predicate.bounded_ty else {
+                continue;
+            };
+            let Some(param) = bounded.path.get_ident() else {
+                continue;
+            };
+            for bound in &predicate.bounds {
+                if let TypeParamBound::Trait(bound) = bound {
+                    self.add_bound(&param.strip_raw().to_string(), bound.path.clone());
+                }
+            }
+        }
+    }
+
+    /// Add a trait bound on a generic parameter, as `T: Trait` would.
+    pub fn add_bound(&mut self, param: &str, trait_path: Path) {
+        self.bounds
+            .entry(param.to_string())
+            .or_default()
+            .push(trait_path);
+    }
+
+    /// Return the `Self` type of the enclosing impl block, if one was set.
+    pub fn self_type(&self) -> Option<&Type> {
+        self.self_type.as_ref()
+    }
+
+    /// Return the trait implemented by the enclosing impl block, if any.
+    pub fn self_trait(&self) -> Option<&Path> {
+        self.self_trait.as_ref()
+    }
+
+    /// Return the `Self` type a path stands for, if it is the bare `Self`.
+    pub(crate) fn self_type_of(&self, path: &Path) -> Option<&Type> {
+        let is_self = path.leading_colon.is_none()
+            && path.segments.len() == 1
+            && path.segments[0].ident == "Self"
+            && path.segments[0].arguments.is_none();
+        self.self_type.as_ref().filter(|_| is_self)
+    }
+
+    /// Return the trait bounds of a generic parameter.
+    pub fn bounds(&self, param: &str) -> &[Path] {
+        self.bounds.get(param).map_or(&[], Vec::as_slice)
+    }
+
+    /// Add the names imported by a `use` declaration.
+    pub fn add_use(&mut self, item: &ItemUse) {
+        self.add_use_tree(Vec::new(), &item.tree);
+    }
+
+    /// Import a path under a local name, as `use path as name;` would.
+    ///
+    /// The path is normalized (raw prefixes and leading `::` removed).
+    /// Returns an error, leaving the scope unchanged, if the name or a
+    /// segment of the path is not an identifier.
+    pub fn add_import<S>(&mut self, name: S, path: &str) -> syn::Result<()>
+    where
+        S: Into<String>,
+    {
+        let name = name.into();
+        let name = match parse_segments(&name)?.as_slice() {
+            [name] => name.clone(),
+            _ => return Err(not_an_ident(&name)),
+        };
+        let segments = parse_segments(path)?;
+        self.imports.insert(name, segments);
+        Ok(())
+    }
+
+    /// Add a glob import of a module path, as `use path::*;` would.
+    ///
+    /// Returns an error, leaving the scope unchanged, if a segment of the
+    /// path is not an identifier.
+    pub fn add_glob(&mut self, path: &str) -> syn::Result<()> {
+        self.globs.push(parse_segments(path)?);
+        Ok(())
+    }
+
+    /// Return the path a local name was imported from, joined with `::`.
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.imports.get(name).map(|segments| segments.join("::"))
+    }
+
+    /// Return an iterator over the glob imported module paths, joined with `::`.
+    pub fn globs(&self) -> impl Iterator<Item = String> + '_ {
+        self.globs.iter().map(|segments| segments.join("::"))
+    }
+
+    /// Return `true` if the scope contains no imports, module path or impl context.
+    pub fn is_empty(&self) -> bool {
+        self.imports.is_empty()
+            && self.globs.is_empty()
+            && self.module.is_empty()
+            && self.self_type.is_none()
+            && self.bounds.is_empty()
+    }
+
+    /// Expand the first segment of a path through the named imports.
+    ///
+    /// Returns `None` if the path has a leading `::` or its first segment
+    /// was not imported by name. Generic arguments are preserved.
+    pub fn expand(&self, path: &Path) -> Option<Path> {
+        if path.leading_colon.is_some() {
+            return None;
+        }
+        let first = path.segments.first()?;
+        let target = self.imports.get(&first.ident.strip_raw().to_string())?;
+        Some(replace_leading_segments(path, 1, target))
+    }
+
+    /// Rewrite a path starting with `crate`, `self` or `super` into an absolute path.
+    ///
+    /// Returns `None` if no module path is set, the path is not relative, or
+    /// `super` would go above the crate root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::Scope;
+    /// use syn::Path;
+    ///
+    /// let scope = Scope::new().with_module("my_crate::api::v1").unwrap();
+    ///
+    /// let path: Path = syn::parse_str("super::types::UserId").unwrap();
+    /// let expected: Path = syn::parse_str("my_crate::api::types::UserId").unwrap();
+    /// assert_eq!(scope.absolutize(&path), Some(expected));
+    /// ```
+    pub fn absolutize(&self, path: &Path) -> Option<Path> {
+        if path.leading_colon.is_some() || self.module.is_empty() {
+            return None;
+        }
+
+        let mut prefix_len = 0;
+        let mut depth = self.module.len();
+        for segment in &path.segments {
+            match segment.ident.to_string().as_str() {
+                "crate" if prefix_len == 0 => depth = 1,
+                "self" if prefix_len == 0 => {}
+                "super" if depth > 1 => depth -= 1,
+                "super" => return None,
+                _ => break,
+            }
+            prefix_len += 1;
+        }
+
+        if prefix_len == 0 {
+            return None;
+        }
+        Some(replace_leading_segments(
+            path,
+            prefix_len,
+            &self.module[..depth],
+        ))
+    }
+
+    /// Return the paths a path could refer to through glob imports, in declaration order.
+    pub(crate) fn glob_expansions<'a>(&'a self, path: &'a Path) -> impl Iterator<Item = Path> + 'a {
+        let applicable = path.leading_colon.is_none() && !path.segments.is_empty();
+        self.globs
+            .iter()
+            .filter(move |_| applicable)
+            .map(move |prefix| {
+                let expanded = replace_leading_segments(path, 0, prefix);
+                self.absolutize(&expanded).unwrap_or(expanded)
+            })
+    }
+
+    /// Walk a use tree, accumulating the module prefix.
+    fn add_use_tree(&mut self, mut prefix: Vec<String>, tree: &UseTree) {
+        match tree {
+            UseTree::Path(use_path) => {
+                prefix.push(use_path.ident.strip_raw().to_string());
+                self.add_use_tree(prefix, &use_path.tree);
+            }
+            UseTree::Name(use_name) => {
+                let name = use_name.ident.strip_raw().to_string();
+                if name == "self" {
+                    // `use a::b::{self}` imports `b`
+                    if let Some(last) = prefix.last().cloned() {
+                        self.imports.insert(last, prefix);
+                    }
+                } else {
+                    prefix.push(name.clone());
+                    self.imports.insert(name, prefix);
+                }
+            }
+            UseTree::Rename(use_rename) => {
+                let rename = use_rename.rename.strip_raw().to_string();
+                // `use Trait as _` brings no name into scope
+                if rename == "_" {
+                    return;
+                }
+                let name = use_rename.ident.strip_raw().to_string();
+                if name != "self" {
+                    prefix.push(name);
+                }
+                self.imports.insert(rename, prefix);
+            }
+            UseTree::Glob(_) => self.globs.push(prefix),
+            UseTree::Group(group) => {
+                for tree in &group.items {
+                    self.add_use_tree(prefix.clone(), tree);
+                }
+            }
+        }
+    }
+}
+
+impl<M> PathResolver<M>
+where
+    M: MappingStorage,
+{
+    /// Resolve a syn [`Path`] as written in a module with the imports of `scope`.
+    ///
+    /// If the first segment of the path was imported by name, including
+    /// through a rename or a `self` import, it is replaced by the imported
+    /// path and the result is resolved with [`PathResolver::resolve`].
+    /// Otherwise each glob imported module is tried as a prefix, accepting
+    /// only exact matches, before falling back to resolving the path as written.
+    ///
+    /// When the scope has a module path (see [`Scope::with_module`]), paths
+    /// starting with `crate::`, `self::` or `super::`, whether written directly
+    /// or reached through an import, are made absolute before lookup.
+    ///
+    /// When the scope has an impl context (see [`Scope::with_impl`]), `Self`
+    /// resolves as the impl's `Self` type, and `Self::Assoc` and `T::Assoc` as
+    /// associated type projections (see [`PathResolver::resolve_projection_in_scope`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::{DynamicPathResolver, Scope};
+    /// use syn::Path;
+    ///
+    /// let mut resolver = DynamicPathResolver::default();
+    /// resolver.add_mapping("my_crate::types::UserId", "UserId");
+    ///
+    /// let file: syn::File = syn::parse_str("use my_crate::types::*;").unwrap();
+    /// let scope = Scope::from_file(&file);
+    ///
+    /// let path: Path = syn::parse_str("UserId").unwrap();
+    /// assert_eq!(resolver.resolve_in_scope(&path, &scope), Some("UserId"));
+    /// ```
+    pub fn resolve_in_scope(&self, path: &Path, scope: &Scope) -> Option<&str> {
+        self.resolve_detailed_in_scope(path, scope)
+            .map(|resolution| resolution.canonical)
+    }
+
+    /// Resolve a syn [`Path`] through the imports of `scope` and describe how
+    /// the match was made.
+    ///
+    /// See [`PathResolver::resolve_in_scope`] and [`PathResolver::resolve_detailed`].
+    /// Matches through a glob import are reported as [`Strategy::Exact`] with the
+    /// expanded pattern.
+    pub fn resolve_detailed_in_scope(&self, path: &Path, scope: &Scope) -> Option<Resolution<'_>> {
+        if let Some(projection) = Projection::from_scoped_path(path, scope) {
+            return self.resolve_projection_detailed(&projection);
+        }
+        if let Some(Type::Path(self_type)) = scope.self_type_of(path) {
+            if self_type.qself.is_none() {
+                return self.resolve_detailed_in_scope(&self_type.path, scope);
+            }
+        }
+
+        let expanded = scope.expand(path);
+        let path = expanded.as_ref().unwrap_or(path);
+        if let Some(absolute) = scope.absolutize(path) {
+            return self.resolve_detailed(&absolute);
+        }
+        if expanded.is_some() {
+            return self.resolve_detailed(path);
+        }
+
+        for expanded in scope.glob_expansions(path) {
+            let normalized = self.normalize_path(&expanded);
+            if let Some((canonical, group)) = self.try_resolve_base_type(&normalized) {
+                return Some(Resolution {
+                    canonical,
+                    pattern: Cow::Owned(normalized),
+                    group,
+                    strategy: Strategy::Exact,
+                    captures: Vec::new(),
+                });
+            }
+        }
+
+        self.resolve_detailed(path)
+    }
+}
+
+/// Split a path string into segments, removing a leading `::` and raw prefixes.
+///
+/// An empty string has no segments. Returns an error if a segment is empty
+/// or not an identifier, so the segments can later be turned back into a [`Path`].
+fn parse_segments(path: &str) -> syn::Result<Vec<String>> {
+    let path = path.trim();
+    let path = path.strip_prefix("::").unwrap_or(path);
+    if path.is_empty() {
+        return Ok(Vec::new());
+    }
+    path.split("::")
+        .map(str::trim)
+        .map(|segment| match Ident::parse_any.parse_str(segment) {
+            Ok(ident) => Ok(crate::utils::strip_raw_prefix(&ident.to_string()).to_string()),
+            Err(_) => Err(not_an_ident(segment)),
+        })
+        .collect()
+}
+
+/// The error for a string that should have been an identifier.
+/// Return `true` if a type mentions `Self` anywhere.
+fn mentions_self(ty: &Type) -> bool {
+    struct FindSelf(bool);
+
+    impl Visit<'_> for FindSelf {
+        fn visit_ident(&mut self, ident: &Ident) {
+            self.0 |= ident == "Self";
+        }
+    }
+
+    let mut find = FindSelf(false);
+    find.visit_type(ty);
+    find.0
+}
+
+fn not_an_ident(segment: &str) -> syn::Error {
+    syn::Error::new(
+        Span::call_site(),
+        format!("expected an identifier, found `{}`", segment),
+    )
+}
+
+/// Replace the first `count` segments of a path with the given segments.
+///
+/// The segments must be identifiers, as checked by [`parse_segments`].
+///
+/// If every segment is replaced, the arguments of the last replaced segment
+/// are moved onto the last new segment.
+fn replace_leading_segments(path: &Path, count: usize, replacement: &[String]) -> Path {
+    let span = path.segments[0].ident.span();
+    let mut rewritten = Path {
+        leading_colon: None,
+        segments: replacement
+            .iter()
+            .map(|segment| PathSegment::from(Ident::new(segment, span)))
+            .collect(),
+    };
+    if count == path.segments.len() {
+        if let (Some(last), Some(replaced)) = (rewritten.segments.last_mut(), path.segments.last())
+        {
+            last.arguments = replaced.arguments.clone();
+        }
+    }
+    rewritten
+        .segments
+        .extend(path.segments.iter().skip(count).cloned());
+    rewritten
+}
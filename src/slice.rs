@@ -0,0 +1,179 @@
+//! Static storage in a sorted slice, for const resolvers without `phf`.
+
+use std::fmt;
+
+use crate::{MappingStorage, MappingValue, PathResolver, TypeGroups, ValueStorage};
+
+/// Storage backed by a `&'static` slice of `(pattern, value)` pairs sorted by pattern.
+///
+/// Lookups use binary search. The slice is checked when the storage is
+/// created, so an unsorted table fails to compile when the storage is built
+/// in a `const`.
+///
+/// # Examples
+///
+/// ```
+/// use desynt::{PathResolver, SliceStorage, TypeGroups};
+/// use syn::Path;
+///
+/// const RESOLVER: PathResolver<SliceStorage> = PathResolver::new(
+///     SliceStorage::new(&[
+///         ("actix_web::HttpResponse", "HttpResponse"),
+///         ("serde_json::Value", "JsonValue"),
+///     ]),
+///     TypeGroups::ALL,
+/// );
+///
+/// let path: Path = syn::parse_str("serde_json::Value").unwrap();
+/// assert_eq!(RESOLVER.resolve(&path), Some("JsonValue"));
+/// ```
+///
+/// An unsorted table is rejected:
+///
+/// ```compile_fail
+/// use desynt::SliceStorage;
+///
+/// const STORAGE: SliceStorage = SliceStorage::new(&[("b::B", "B"), ("a::A", "A")]);
+/// # let _ = STORAGE;
+/// ```
+pub struct SliceStorage<V: 'static = &'static str> {
+    entries: &'static [(&'static str, V)],
+}
+
+impl<V> SliceStorage<V> {
+    /// Create a storage from pairs sorted by pattern.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the patterns are not sorted in ascending byte order, or if a
+    /// pattern appears twice.
+    pub const fn new(entries: &'static [(&'static str, V)]) -> Self {
+        let mut index = 1;
+        while index < entries.len() {
+            if !is_less(entries[index - 1].0, entries[index].0) {
+                panic!("SliceStorage patterns must be sorted and unique");
+            }
+            index += 1;
+        }
+        Self { entries }
+    }
+
+    /// Return the `(pattern, value)` pairs, sorted by pattern.
+    pub const fn entries(&self) -> &'static [(&'static str, V)] {
+        self.entries
+    }
+
+    fn find(&self, path: &str) -> Option<&'static V> {
+        let index = self
+            .entries
+            .binary_search_by(|(key, _)| (*key).cmp(path))
+            .ok()?;
+        Some(&self.entries[index].1)
+    }
+}
+
+/// Compare two strings in const context, by bytes as `str`'s `Ord` does.
+const fn is_less(left: &str, right: &str) -> bool {
+    let (left, right) = (left.as_bytes(), right.as_bytes());
+    let mut index = 0;
+    while index < left.len() && index < right.len() {
+        if left[index] != right[index] {
+            return left[index] < right[index];
+        }
+        index += 1;
+    }
+    left.len() < right.len()
+}
+
+impl<V> MappingStorage for SliceStorage<V>
+where
+    V: MappingValue,
+{
+    fn get(&self, path: &str) -> Option<&str> {
+        self.find(path).map(MappingValue::canonical)
+    }
+
+    fn contains_key(&self, path: &str) -> bool {
+        self.find(path).is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.entries.iter().map(|(key, _)| *key))
+    }
+
+    fn values(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.entries.iter().map(|(_, value)| value.canonical()))
+    }
+}
+
+impl<V> ValueStorage for SliceStorage<V>
+where
+    V: MappingValue,
+{
+    type Value = V;
+
+    fn get_value(&self, path: &str) -> Option<&V> {
+        self.find(path)
+    }
+}
+
+impl<V> Clone for SliceStorage<V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for SliceStorage<V> {}
+
+impl<V> fmt::Debug for SliceStorage<V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SliceStorage")
+            .field("entries", &self.entries)
+            .finish()
+    }
+}
+
+/// Type alias for const path resolvers with a sorted slice of mappings.
+///
+/// This resolver needs no `phf` dependency; see [`create_slice_resolver`].
+pub type SlicePathResolver = PathResolver<SliceStorage>;
+
+/// Create a const path resolver from a sorted slice of mappings.
+///
+/// This is the equivalent of [`create_static_resolver`](crate::create_static_resolver)
+/// for builds without the `static-resolver` feature.
+///
+/// # Panics
+///
+/// Panics, failing compilation in a `const`, if the patterns are not sorted
+/// or not unique. See [`SliceStorage::new`].
+///
+/// # Examples
+///
+/// ```
+/// use desynt::{SlicePathResolver, TypeGroups, create_slice_resolver};
+///
+/// const CUSTOM_MAPPINGS: &[(&str, &str)] = &[
+///     ("actix_web::HttpResponse", "HttpResponse"),
+///     ("serde_json::Value", "JsonValue"),
+/// ];
+///
+/// const RESOLVER: SlicePathResolver = create_slice_resolver(CUSTOM_MAPPINGS, TypeGroups::ALL);
+/// ```
+pub const fn create_slice_resolver(
+    custom_mappings: &'static [(&'static str, &'static str)],
+    groups: TypeGroups,
+) -> SlicePathResolver {
+    PathResolver::new(SliceStorage::new(custom_mappings), groups)
+}
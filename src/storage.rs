@@ -0,0 +1,425 @@
+//! Storage backends that keep extra per-pattern metadata.
+
+use std::collections::HashMap;
+
+use crate::{
+    GenericPattern, GlobPattern, MappingStorage, Matcher, NamedMatcher, SuffixIndex, ValueStorage,
+    glob,
+};
+
+/// HashMap-backed storage used by [`DynamicPathResolver`](crate::DynamicPathResolver).
+///
+/// Alongside the path-to-canonical-type mappings, this keeps explicit
+/// tie-breaking priorities for patterns, indexes of its glob keys and of its
+/// keys by trailing segments, and the registered [`Matcher`]s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DynamicStorage {
+    /// Maps normalized path strings to canonical type names.
+    mappings: HashMap<String, String>,
+    /// Explicit tie-breaking priorities, keyed by normalized path pattern.
+    priorities: HashMap<String, i32>,
+    /// The keys of `mappings` that are glob keys, compiled and sorted by key.
+    globs: Vec<GlobPattern>,
+    /// The keys of `mappings` that are generic pattern keys, parsed and sorted by key.
+    generic_patterns: Vec<GenericPattern>,
+    /// The keys of `mappings`, indexed by their trailing segments.
+    suffixes: SuffixIndex,
+    /// Custom matchers, in the order they are consulted.
+    matchers: Vec<NamedMatcher>,
+    /// Facade rules as `(prefix, target)` pairs, in the order they are tried.
+    facades: Vec<(String, String)>,
+}
+
+impl DynamicStorage {
+    /// Create an empty storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a mapping, returning the previous canonical type for the pattern.
+    ///
+    /// The pattern is stored as given; callers are expected to normalize it.
+    pub fn insert(&mut self, path_pattern: String, canonical_type: String) -> Option<String> {
+        match GlobPattern::parse(&path_pattern) {
+            Some(pattern) => insert_pattern(&mut self.globs, pattern),
+            None => {
+                self.suffixes.insert(path_pattern.as_str());
+            }
+        }
+        if let Some(pattern) = GenericPattern::parse(&path_pattern) {
+            insert_pattern(&mut self.generic_patterns, pattern);
+        }
+        self.mappings.insert(path_pattern, canonical_type)
+    }
+
+    /// Remove a mapping and its priority, returning the canonical type it mapped to.
+    pub fn remove(&mut self, path_pattern: &str) -> Option<String> {
+        self.priorities.remove(path_pattern);
+        self.globs.retain(|glob| glob.key() != path_pattern);
+        self.generic_patterns
+            .retain(|pattern| pattern.key() != path_pattern);
+        self.suffixes.remove(path_pattern);
+        self.mappings.remove(path_pattern)
+    }
+
+    /// Set the tie-breaking priority of a pattern.
+    pub fn set_priority(&mut self, path_pattern: String, priority: i32) {
+        self.priorities.insert(path_pattern, priority);
+    }
+
+    /// Add a named matcher, replacing any matcher with the same name in place.
+    pub fn add_matcher(&mut self, matcher: NamedMatcher) {
+        match self
+            .matchers
+            .iter_mut()
+            .find(|existing| existing.name() == matcher.name())
+        {
+            Some(existing) => *existing = matcher,
+            None => self.matchers.push(matcher),
+        }
+    }
+
+    /// Remove a named matcher, returning `true` if it was present.
+    pub fn remove_matcher(&mut self, name: &str) -> bool {
+        let len = self.matchers.len();
+        self.matchers.retain(|matcher| matcher.name() != name);
+        self.matchers.len() != len
+    }
+
+    /// Add a facade rule, replacing any rule with the same prefix in place.
+    ///
+    /// The prefix and target are stored as given; callers are expected to
+    /// normalize them.
+    pub fn add_facade(&mut self, prefix: String, target: String) {
+        match self
+            .facades
+            .iter_mut()
+            .find(|(existing, _)| *existing == prefix)
+        {
+            Some(existing) => existing.1 = target,
+            None => self.facades.push((prefix, target)),
+        }
+    }
+
+    /// Remove the facade rule for a prefix, returning `true` if it was present.
+    pub fn remove_facade(&mut self, prefix: &str) -> bool {
+        let len = self.facades.len();
+        self.facades.retain(|(existing, _)| existing != prefix);
+        self.facades.len() != len
+    }
+
+    /// Remove all mappings, priorities, matchers and facade rules.
+    pub fn clear(&mut self) {
+        self.mappings.clear();
+        self.priorities.clear();
+        self.globs.clear();
+        self.generic_patterns.clear();
+        self.suffixes.clear();
+        self.matchers.clear();
+        self.facades.clear();
+    }
+
+    /// Return the underlying path-to-canonical-type map.
+    pub fn as_map(&self) -> &HashMap<String, String> {
+        &self.mappings
+    }
+}
+
+impl From<HashMap<String, String>> for DynamicStorage {
+    fn from(mappings: HashMap<String, String>) -> Self {
+        let globs = parse_patterns(mappings.keys().map(String::as_str), GlobPattern::parse);
+        let generic_patterns =
+            parse_patterns(mappings.keys().map(String::as_str), GenericPattern::parse);
+        Self {
+            suffixes: path_index(mappings.keys().map(String::as_str)),
+            mappings,
+            priorities: HashMap::new(),
+            globs,
+            generic_patterns,
+            matchers: Vec::new(),
+            facades: Vec::new(),
+        }
+    }
+}
+
+impl MappingStorage for DynamicStorage {
+    fn get(&self, path: &str) -> Option<&str> {
+        self.mappings.get(path).map(|s| s.as_str())
+    }
+
+    fn contains_key(&self, path: &str) -> bool {
+        self.mappings.contains_key(path)
+    }
+
+    fn len(&self) -> usize {
+        self.mappings.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    fn keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.mappings.keys().map(|s| s.as_str()))
+    }
+
+    fn values(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.mappings.values().map(|s| s.as_str()))
+    }
+
+    fn priority(&self, path: &str) -> i32 {
+        self.priorities.get(path).copied().unwrap_or(0)
+    }
+
+    fn glob_keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        boxed_pattern_keys(&self.globs)
+    }
+
+    fn glob_patterns(&self) -> Option<&[GlobPattern]> {
+        Some(&self.globs)
+    }
+
+    fn generic_pattern_keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        boxed_pattern_keys(&self.generic_patterns)
+    }
+
+    fn generic_patterns(&self) -> Option<&[GenericPattern]> {
+        Some(&self.generic_patterns)
+    }
+
+    fn keys_ending_with<'a: 'b, 'b>(
+        &'a self,
+        suffix: &'b str,
+    ) -> Box<dyn Iterator<Item = &'a str> + 'b> {
+        self.suffixes.boxed_keys_ending_with(suffix)
+    }
+
+    fn matchers(&self) -> Box<dyn Iterator<Item = (&str, &dyn Matcher)> + '_> {
+        if self.matchers.is_empty() {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(
+            self.matchers
+                .iter()
+                .map(|matcher| (matcher.name(), matcher.matcher() as &dyn Matcher)),
+        )
+    }
+
+    fn facades(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        if self.facades.is_empty() {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(
+            self.facades
+                .iter()
+                .map(|(prefix, target)| (prefix.as_str(), target.as_str())),
+        )
+    }
+}
+
+impl ValueStorage for DynamicStorage {
+    type Value = String;
+
+    fn get_value(&self, path: &str) -> Option<&String> {
+        self.mappings.get(path)
+    }
+}
+
+/// A storage paired with a [`SuffixIndex`] of its keys.
+///
+/// This speeds up matching bare type names, glob keys and generic pattern
+/// keys for storages that cannot keep indexes themselves, such as a static
+/// phf map. The indexes are built once when the storage is wrapped, so the
+/// wrapped storage should not change.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "static-resolver")]
+/// # fn main() {
+/// use std::sync::OnceLock;
+///
+/// use desynt::{IndexedStorage, PathResolver, TypeGroups};
+/// use phf::{Map, phf_map};
+/// use syn::Path;
+///
+/// type IndexedResolver = PathResolver<IndexedStorage<&'static Map<&'static str, &'static str>>>;
+///
+/// static MAPPINGS: Map<&'static str, &'static str> = phf_map! {
+///     "my_crate::types::UserId" => "UserId",
+/// };
+///
+/// fn resolver() -> &'static IndexedResolver {
+///     static RESOLVER: OnceLock<IndexedResolver> = OnceLock::new();
+///     RESOLVER.get_or_init(|| PathResolver::new(IndexedStorage::new(&MAPPINGS), TypeGroups::ALL))
+/// }
+///
+/// let path: Path = syn::parse_str("UserId").unwrap();
+/// assert_eq!(resolver().resolve(&path), Some("UserId"));
+/// # }
+/// # #[cfg(not(feature = "static-resolver"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedStorage<S> {
+    storage: S,
+    index: SuffixIndex,
+    globs: Vec<GlobPattern>,
+    generic_patterns: Vec<GenericPattern>,
+}
+
+impl<S> IndexedStorage<S>
+where
+    S: MappingStorage,
+{
+    /// Wrap a storage, indexing its keys.
+    pub fn new(storage: S) -> Self {
+        let index = path_index(storage.keys());
+        let globs = parse_patterns(storage.glob_keys(), GlobPattern::parse);
+        let generic_patterns =
+            parse_patterns(storage.generic_pattern_keys(), GenericPattern::parse);
+        Self {
+            storage,
+            index,
+            globs,
+            generic_patterns,
+        }
+    }
+
+    /// Return the wrapped storage.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Return the index of the wrapped storage's keys.
+    pub fn index(&self) -> &SuffixIndex {
+        &self.index
+    }
+
+    /// Unwrap the storage, discarding the index.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+}
+
+impl<S> MappingStorage for IndexedStorage<S>
+where
+    S: MappingStorage,
+{
+    fn get(&self, path: &str) -> Option<&str> {
+        self.storage.get(path)
+    }
+
+    fn contains_key(&self, path: &str) -> bool {
+        self.storage.contains_key(path)
+    }
+
+    fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    fn keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        self.storage.keys()
+    }
+
+    fn values(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        self.storage.values()
+    }
+
+    fn priority(&self, path: &str) -> i32 {
+        self.storage.priority(path)
+    }
+
+    fn glob_keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        boxed_pattern_keys(&self.globs)
+    }
+
+    fn glob_patterns(&self) -> Option<&[GlobPattern]> {
+        Some(&self.globs)
+    }
+
+    fn generic_pattern_keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        boxed_pattern_keys(&self.generic_patterns)
+    }
+
+    fn generic_patterns(&self) -> Option<&[GenericPattern]> {
+        Some(&self.generic_patterns)
+    }
+
+    fn keys_ending_with<'a: 'b, 'b>(
+        &'a self,
+        suffix: &'b str,
+    ) -> Box<dyn Iterator<Item = &'a str> + 'b> {
+        self.index.boxed_keys_ending_with(suffix)
+    }
+
+    fn matchers(&self) -> Box<dyn Iterator<Item = (&str, &dyn Matcher)> + '_> {
+        self.storage.matchers()
+    }
+
+    fn facades(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        self.storage.facades()
+    }
+}
+
+impl<S> ValueStorage for IndexedStorage<S>
+where
+    S: ValueStorage,
+{
+    type Value = S::Value;
+
+    fn get_value(&self, path: &str) -> Option<&S::Value> {
+        self.storage.get_value(path)
+    }
+}
+
+/// Index the keys that are paths by their trailing segments, leaving out glob keys.
+fn path_index<'a>(keys: impl Iterator<Item = &'a str>) -> SuffixIndex {
+    SuffixIndex::from_keys(keys.filter(|key| !glob::is_glob(key)))
+}
+
+/// A pattern parsed from a mapping key.
+trait ParsedKey {
+    fn key(&self) -> &str;
+}
+
+impl ParsedKey for GlobPattern {
+    fn key(&self) -> &str {
+        GlobPattern::key(self)
+    }
+}
+
+impl ParsedKey for GenericPattern {
+    fn key(&self) -> &str {
+        GenericPattern::key(self)
+    }
+}
+
+/// Insert a parsed pattern into an index sorted by key, unless its key is present.
+fn insert_pattern<P: ParsedKey>(index: &mut Vec<P>, pattern: P) {
+    if let Err(position) = index.binary_search_by(|existing| existing.key().cmp(pattern.key())) {
+        index.insert(position, pattern);
+    }
+}
+
+/// Parse the keys among `keys` that `parse` accepts, sorted by key.
+fn parse_patterns<'a, P: ParsedKey>(
+    keys: impl Iterator<Item = &'a str>,
+    parse: fn(&str) -> Option<P>,
+) -> Vec<P> {
+    let mut patterns: Vec<P> = keys.filter_map(parse).collect();
+    patterns.sort_unstable_by(|a, b| a.key().cmp(b.key()));
+    patterns
+}
+
+/// Iterate the keys of an index of parsed patterns, without allocating if it is empty.
+fn boxed_pattern_keys<P: ParsedKey>(patterns: &[P]) -> Box<dyn Iterator<Item = &str> + '_> {
+    if patterns.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+    Box::new(patterns.iter().map(P::key))
+}
@@ -0,0 +1,173 @@
+//! An index of mapping keys by their trailing path segments.
+//!
+//! Bare type names such as `Option` are matched against every key ending in
+//! `::Option`. Rather than scanning every key, the index stores keys in a trie
+//! keyed on their segments in reverse order, so finding the keys with a given
+//! suffix costs the length of the suffix plus the number of keys found.
+
+use std::collections::HashMap;
+
+/// A trie of mapping keys keyed on their reversed path segments.
+///
+/// [`DynamicStorage`](crate::DynamicStorage) keeps one up to date as mappings
+/// are added and removed. For other storages, such as a static phf map, an
+/// index can be built once with [`SuffixIndex::from_keys`] and paired with
+/// the storage using [`IndexedStorage`](crate::IndexedStorage).
+///
+/// # Examples
+///
+/// ```
+/// use desynt::SuffixIndex;
+///
+/// let index = SuffixIndex::from_keys(["std::option::Option", "my::Option", "my::Other"]);
+///
+/// let mut keys: Vec<&str> = index.keys_ending_with("Option").collect();
+/// keys.sort_unstable();
+/// assert_eq!(keys, ["my::Option", "std::option::Option"]);
+///
+/// let keys: Vec<&str> = index.keys_ending_with("option::Option").collect();
+/// assert_eq!(keys, ["std::option::Option"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SuffixIndex {
+    root: Node,
+    len: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Node {
+    /// Child nodes, keyed on the segment before this one.
+    children: HashMap<String, Node>,
+    /// The key whose segments end at this node, if any.
+    key: Option<String>,
+}
+
+impl Node {
+    fn is_empty(&self) -> bool {
+        self.key.is_none() && self.children.is_empty()
+    }
+
+    /// Remove a key by its remaining reversed segments, pruning emptied nodes.
+    fn remove<'a>(&mut self, mut segments: impl Iterator<Item = &'a str>) -> bool {
+        match segments.next() {
+            None => self.key.take().is_some(),
+            Some(segment) => {
+                let Some(child) = self.children.get_mut(segment) else {
+                    return false;
+                };
+                let removed = child.remove(segments);
+                if child.is_empty() {
+                    self.children.remove(segment);
+                }
+                removed
+            }
+        }
+    }
+}
+
+impl SuffixIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build an index of the given keys.
+    pub fn from_keys<I, S>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut index = Self::new();
+        for key in keys {
+            index.insert(key);
+        }
+        index
+    }
+
+    /// Add a key, returning `false` if it was already present.
+    pub fn insert<S: Into<String>>(&mut self, key: S) -> bool {
+        let key = key.into();
+        let mut node = &mut self.root;
+        for segment in key.rsplit("::") {
+            node = node.children.entry(segment.to_string()).or_default();
+        }
+        if node.key.is_some() {
+            return false;
+        }
+        node.key = Some(key);
+        self.len += 1;
+        true
+    }
+
+    /// Remove a key, returning `true` if it was present.
+    pub fn remove(&mut self, key: &str) -> bool {
+        let removed = self.root.remove(key.rsplit("::"));
+        if removed {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Remove every key.
+    pub fn clear(&mut self) {
+        self.root = Node::default();
+        self.len = 0;
+    }
+
+    /// Return the number of keys in the index.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the index has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the keys equal to `suffix` or ending with `::` followed by it, in no particular order.
+    ///
+    /// The suffix is one or more whole segments, such as `Option` or `option::Option`.
+    pub fn keys_ending_with<'a>(&'a self, suffix: &str) -> impl Iterator<Item = &'a str> + 'a {
+        Keys {
+            stack: self.find(suffix).into_iter().collect(),
+        }
+    }
+
+    /// Return the keys ending with `suffix` as a boxed iterator, which only
+    /// allocates if there are any.
+    pub(crate) fn boxed_keys_ending_with<'a>(
+        &'a self,
+        suffix: &str,
+    ) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        match self.find(suffix) {
+            Some(node) => Box::new(Keys { stack: vec![node] }),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    /// Find the node below which every key ends with `suffix`.
+    fn find(&self, suffix: &str) -> Option<&Node> {
+        suffix
+            .rsplit("::")
+            .try_fold(&self.root, |node, segment| node.children.get(segment))
+    }
+}
+
+/// A depth-first walk over the keys below a node.
+struct Keys<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for Keys<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            self.stack.extend(node.children.values());
+            if let Some(key) = &node.key {
+                return Some(key);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,362 @@
+//! Mapping values carrying metadata alongside the canonical type name.
+//!
+//! A storage such as `HashMap<String, V>` or a phf `Map<&str, V>` may hold any
+//! value implementing [`MappingValue`], such as a struct with a SQL column
+//! type or a serializer function. Resolution still works on canonical names,
+//! and [`PathResolver::resolve_value`] returns the stored value of the
+//! mapping that matched. Values that do not name a type can be wrapped in a
+//! [`ValueMap`], which names each mapping after its pattern instead.
+
+use std::borrow::{Borrow, Cow};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[cfg(feature = "static-resolver")]
+use phf::Map;
+use syn::Path;
+
+use crate::{MappingStorage, PathResolver, Resolution, Strategy};
+
+/// A mapping value that names a canonical type.
+///
+/// Implemented for the string types, so `HashMap<String, String>` and phf
+/// `Map<&str, &str>` storages map to their canonical names directly.
+///
+/// # Examples
+///
+/// ```
+/// use desynt::{MappingValue, PathResolver, TypeGroups};
+/// use std::collections::HashMap;
+/// use syn::Path;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Column {
+///     name: &'static str,
+///     sql: &'static str,
+/// }
+///
+/// impl MappingValue for Column {
+///     fn canonical(&self) -> &str {
+///         self.name
+///     }
+/// }
+///
+/// let mut columns = HashMap::new();
+/// columns.insert(
+///     "uuid::Uuid".to_string(),
+///     Column { name: "Uuid", sql: "UUID" },
+/// );
+/// let resolver = PathResolver::new(columns, TypeGroups::ALL);
+///
+/// let path: Path = syn::parse_str("uuid::Uuid").unwrap();
+/// assert_eq!(resolver.resolve(&path), Some("Uuid"));
+/// assert_eq!(resolver.resolve_value(&path).map(|column| column.sql), Some("UUID"));
+/// ```
+pub trait MappingValue {
+    /// Return the canonical type name of the value.
+    fn canonical(&self) -> &str;
+}
+
+impl MappingValue for str {
+    fn canonical(&self) -> &str {
+        self
+    }
+}
+
+impl MappingValue for String {
+    fn canonical(&self) -> &str {
+        self
+    }
+}
+
+impl MappingValue for Cow<'_, str> {
+    fn canonical(&self) -> &str {
+        self
+    }
+}
+
+impl<T> MappingValue for &T
+where
+    T: MappingValue + ?Sized,
+{
+    fn canonical(&self) -> &str {
+        (**self).canonical()
+    }
+}
+
+impl<T> MappingValue for Box<T>
+where
+    T: MappingValue + ?Sized,
+{
+    fn canonical(&self) -> &str {
+        (**self).canonical()
+    }
+}
+
+impl<T> MappingValue for Rc<T>
+where
+    T: MappingValue + ?Sized,
+{
+    fn canonical(&self) -> &str {
+        (**self).canonical()
+    }
+}
+
+impl<T> MappingValue for Arc<T>
+where
+    T: MappingValue + ?Sized,
+{
+    fn canonical(&self) -> &str {
+        (**self).canonical()
+    }
+}
+
+/// A map from path patterns to values of any type.
+///
+/// Wraps a `HashMap<K, V>`, `BTreeMap<K, V>` or phf `Map<&str, V>` whose values
+/// do not implement [`MappingValue`], so [`PathResolver::resolve_value`]
+/// returns the stored `&V` without the value having to carry a type name.
+/// The canonical name of each mapping is the last segment of its pattern,
+/// without generic arguments: `Uuid` for `uuid::Uuid`, `Money` for
+/// `my_crate::*::Money` and `Vec` for `Vec<u8>`. A pattern ending in a
+/// wildcard is named after the whole pattern.
+///
+/// # Examples
+///
+/// ```
+/// use desynt::{PathResolver, TypeGroups, ValueMap};
+/// use std::collections::HashMap;
+/// use syn::Path;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum SqlType {
+///     Uuid,
+///     Timestamp,
+/// }
+///
+/// let mut sql_types = HashMap::new();
+/// sql_types.insert("uuid::Uuid", SqlType::Uuid);
+/// sql_types.insert("chrono::DateTime", SqlType::Timestamp);
+/// let resolver = PathResolver::new(ValueMap(sql_types), TypeGroups::ALL);
+///
+/// let path: Path = syn::parse_str("chrono::DateTime<Utc>").unwrap();
+/// assert_eq!(resolver.resolve(&path), Some("DateTime"));
+/// assert_eq!(resolver.resolve_value(&path), Some(&SqlType::Timestamp));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValueMap<T>(pub T);
+
+impl<K, V, S> MappingStorage for ValueMap<HashMap<K, V, S>>
+where
+    K: Borrow<str> + Eq + Hash,
+    S: BuildHasher,
+{
+    fn get(&self, path: &str) -> Option<&str> {
+        let (key, _) = self.0.get_key_value(path)?;
+        Some(pattern_name(key.borrow()))
+    }
+
+    fn contains_key(&self, path: &str) -> bool {
+        self.0.contains_key(path)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.0.keys().map(Borrow::borrow))
+    }
+
+    fn values(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.0.keys().map(|key| pattern_name(key.borrow())))
+    }
+}
+
+impl<K, V, S> ValueStorage for ValueMap<HashMap<K, V, S>>
+where
+    K: Borrow<str> + Eq + Hash,
+    S: BuildHasher,
+{
+    type Value = V;
+
+    fn get_value(&self, path: &str) -> Option<&V> {
+        self.0.get(path)
+    }
+}
+
+impl<K, V> MappingStorage for ValueMap<BTreeMap<K, V>>
+where
+    K: Borrow<str> + Ord,
+{
+    fn get(&self, path: &str) -> Option<&str> {
+        let (key, _) = self.0.get_key_value(path)?;
+        Some(pattern_name(key.borrow()))
+    }
+
+    fn contains_key(&self, path: &str) -> bool {
+        self.0.contains_key(path)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.0.keys().map(Borrow::borrow))
+    }
+
+    fn values(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.0.keys().map(|key| pattern_name(key.borrow())))
+    }
+}
+
+impl<K, V> ValueStorage for ValueMap<BTreeMap<K, V>>
+where
+    K: Borrow<str> + Ord,
+{
+    type Value = V;
+
+    fn get_value(&self, path: &str) -> Option<&V> {
+        self.0.get(path)
+    }
+}
+
+#[cfg(feature = "static-resolver")]
+impl<V> MappingStorage for ValueMap<Map<&'static str, V>> {
+    fn get(&self, path: &str) -> Option<&str> {
+        let (key, _) = self.0.get_entry(path)?;
+        Some(pattern_name(key))
+    }
+
+    fn contains_key(&self, path: &str) -> bool {
+        self.0.contains_key(path)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.0.keys().copied())
+    }
+
+    fn values(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.0.keys().map(|key| pattern_name(key)))
+    }
+}
+
+#[cfg(feature = "static-resolver")]
+impl<V> ValueStorage for ValueMap<Map<&'static str, V>> {
+    type Value = V;
+
+    fn get_value(&self, path: &str) -> Option<&V> {
+        self.0.get(path)
+    }
+}
+
+/// Return the canonical name of a [`ValueMap`] pattern.
+fn pattern_name(pattern: &str) -> &str {
+    let path = match pattern.find('<') {
+        Some(index) if index > 0 => &pattern[..index],
+        _ => pattern,
+    };
+    match path.rsplit("::").next() {
+        Some(name) if !name.is_empty() && name != "*" => name,
+        _ => pattern,
+    }
+}
+
+/// A [`MappingStorage`] that can return the value stored for a pattern.
+///
+/// [`MappingStorage::get`] returns the canonical type name of the value.
+pub trait ValueStorage: MappingStorage {
+    /// The value stored for each pattern.
+    type Value: ?Sized;
+
+    /// Returns the value stored for the given normalized path pattern.
+    fn get_value(&self, path: &str) -> Option<&Self::Value>;
+}
+
+impl<M> PathResolver<M>
+where
+    M: ValueStorage,
+{
+    /// Resolve a syn [`Path`] to the value of the custom mapping that matched.
+    ///
+    /// The path is resolved as by [`PathResolver::resolve_detailed`]. Matches
+    /// from a builtin type group or a [`Matcher`](crate::Matcher) have no
+    /// stored value and return `None`; see [`PathResolver::resolve_value_with`].
+    pub fn resolve_value(&self, path: &Path) -> Option<&M::Value> {
+        let resolution = self.resolve_detailed(path)?;
+        if !has_stored_value(&resolution) {
+            return None;
+        }
+        self.mappings.get_value(&resolution.pattern)
+    }
+
+    /// Resolve a syn [`Path`] to a value, converting matches without a stored value.
+    ///
+    /// Custom mappings return their stored value. Matches from a builtin type
+    /// group or a [`Matcher`](crate::Matcher) are passed to `convert`, which
+    /// may produce an owned value from the [`Resolution`], for example from
+    /// its canonical name and group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use desynt::{PathResolver, TypeGroups};
+    /// use std::collections::HashMap;
+    /// use syn::Path;
+    ///
+    /// let mut sql_types = HashMap::new();
+    /// sql_types.insert("uuid::Uuid".to_string(), "UUID".to_string());
+    /// let resolver = PathResolver::new(sql_types, TypeGroups::ALL);
+    ///
+    /// let sql_type = |path: &str| {
+    ///     let path: Path = syn::parse_str(path).unwrap();
+    ///     let value = resolver.resolve_value_with(&path, |resolution| match resolution.canonical {
+    ///         "i64" => Some("BIGINT".to_string()),
+    ///         "String" => Some("TEXT".to_string()),
+    ///         _ => None,
+    ///     });
+    ///     value.map(|value| value.into_owned())
+    /// };
+    ///
+    /// assert_eq!(sql_type("uuid::Uuid").as_deref(), Some("UUID"));
+    /// assert_eq!(sql_type("std::string::String").as_deref(), Some("TEXT"));
+    /// assert_eq!(sql_type("bool"), None);
+    /// ```
+    pub fn resolve_value_with<F>(&self, path: &Path, convert: F) -> Option<Cow<'_, M::Value>>
+    where
+        M::Value: ToOwned,
+        F: FnOnce(&Resolution<'_>) -> Option<<M::Value as ToOwned>::Owned>,
+    {
+        let resolution = self.resolve_detailed(path)?;
+        if !has_stored_value(&resolution) {
+            return convert(&resolution).map(Cow::Owned);
+        }
+        self.mappings
+            .get_value(&resolution.pattern)
+            .map(Cow::Borrowed)
+    }
+}
+
+/// Return `true` if a resolution's pattern is a key of the storage.
+fn has_stored_value(resolution: &Resolution<'_>) -> bool {
+    resolution.group.is_none() && resolution.strategy != Strategy::Matcher
+}
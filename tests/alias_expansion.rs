@@ -0,0 +1,131 @@
+#![cfg(test)]
+
+use desynt::{CanonicalPath, CanonicalType, DynamicPathResolver, Scope, TypeAliases};
+use rstest::rstest;
+use syn::{File, ItemType, Type, parse_str};
+
+const SOURCE: &str = r#"
+    use std::collections::HashMap as Map;
+
+    pub struct MyError;
+
+    pub type MyResult<T, E = MyError> = std::result::Result<T, E>;
+    pub type UserResult = MyResult<User>;
+    pub type Lookup<V> = Map<String, V>;
+    pub type Nested<T> = Option<MyResult<Vec<T>>>;
+    pub type Buffer<const N: usize> = [u8; N];
+    pub type Pair<A, B = A> = (A, B);
+
+    pub mod handlers {
+        pub type Reply = super::MyResult<crate::models::User, Rejection>;
+    }
+"#;
+
+fn aliases() -> TypeAliases {
+    let file: File = parse_str(SOURCE).unwrap();
+    TypeAliases::from_file(&file, "my_crate").unwrap()
+}
+
+fn resolver() -> DynamicPathResolver {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("my_crate::models::User", "User");
+    resolver.add_mapping("my_crate::MyError", "MyError");
+    resolver
+}
+
+fn named(name: &str, args: Vec<CanonicalType>) -> CanonicalType {
+    CanonicalType::Path(CanonicalPath {
+        name: name.to_string(),
+        resolved: true,
+        args,
+        bindings: vec![],
+    })
+}
+
+fn unresolved(name: &str) -> CanonicalType {
+    CanonicalType::Path(CanonicalPath {
+        name: name.to_string(),
+        resolved: false,
+        args: vec![],
+        bindings: vec![],
+    })
+}
+
+#[rstest]
+#[case::default_parameter("MyResult<u8>", named("Result", vec![named("u8", vec![]), named("MyError", vec![])]))]
+#[case::explicit_parameter("MyResult<u8, String>", named("Result", vec![named("u8", vec![]), named("String", vec![])]))]
+#[case::partial_application("UserResult", named("Result", vec![named("User", vec![]), named("MyError", vec![])]))]
+#[case::import_in_body("Lookup<bool>", named("HashMap", vec![named("String", vec![]), named("bool", vec![])]))]
+#[case::nested("Nested<u8>", named("Option", vec![named("Result", vec![named("Vec", vec![named("u8", vec![])]), named("MyError", vec![])])]))]
+#[case::default_refers_to_earlier("Pair<u8>", CanonicalType::Tuple(vec![named("u8", vec![]), named("u8", vec![])]))]
+#[case::missing_argument("MyResult", named("Result", vec![CanonicalType::Infer, named("MyError", vec![])]))]
+#[case::absolute_pattern("my_crate::Lookup<u8>", named("HashMap", vec![named("String", vec![]), named("u8", vec![])]))]
+#[case::alias_in_argument("Vec<MyResult<()>>", named("Vec", vec![named("Result", vec![CanonicalType::Tuple(vec![]), named("MyError", vec![])])]))]
+#[case::not_an_alias("Option<u8>", named("Option", vec![named("u8", vec![])]))]
+fn expand(#[case] input: &str, #[case] expected: CanonicalType) {
+    let ty: Type = parse_str(input).unwrap();
+    assert_eq!(aliases().resolve_type(&resolver(), &ty), expected);
+}
+
+#[test]
+fn handler_error_type() {
+    // The error type of a result alias declared in another module
+    let ty: Type = parse_str("handlers::Reply").unwrap();
+    let scope = Scope::new().with_module("my_crate").unwrap();
+
+    let CanonicalType::Path(result) = aliases().resolve_type_in_scope(&resolver(), &ty, &scope)
+    else {
+        panic!("expected a path");
+    };
+    assert_eq!(result.name, "Result");
+    assert_eq!(result.args[0], named("User", vec![]));
+    assert_eq!(result.args[1], unresolved("Rejection"));
+}
+
+#[test]
+fn const_parameter() {
+    let ty: Type = parse_str("Buffer<16>").unwrap();
+
+    assert_eq!(
+        aliases().resolve_type(&resolver(), &ty),
+        CanonicalType::Array {
+            elem: Box::new(named("u8", vec![])),
+            len: parse_str("16").unwrap(),
+        }
+    );
+}
+
+#[test]
+fn cycles_stop_expanding() {
+    let a: ItemType = parse_str("type A<T> = Vec<B<T>>;").unwrap();
+    let b: ItemType = parse_str("type B<T> = Option<A<T>>;").unwrap();
+    let mut aliases = TypeAliases::new();
+    aliases.add(&a, &Scope::new());
+    aliases.add(&b, &Scope::new());
+
+    let ty: Type = parse_str("A<u8>").unwrap();
+    let expected = named(
+        "Vec",
+        vec![named(
+            "Option",
+            vec![CanonicalType::Path(CanonicalPath {
+                name: "A".to_string(),
+                resolved: false,
+                args: vec![named("u8", vec![])],
+                bindings: vec![],
+            })],
+        )],
+    );
+    assert_eq!(aliases.resolve_type(&resolver(), &ty), expected);
+}
+
+#[test]
+fn definitions() {
+    let aliases = aliases();
+
+    assert_eq!(aliases.len(), 7);
+    assert!(!aliases.is_empty());
+    assert!(aliases.get("my_crate::handlers::Reply").is_some());
+    assert!(aliases.get("Reply").is_none());
+    assert!(TypeAliases::default().is_empty());
+}
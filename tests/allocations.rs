@@ -0,0 +1,119 @@
+#![cfg(test)]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use desynt::{
+    DynamicPathResolver, EmptyStorage, IndexedStorage, PathResolver, PrimitivePathResolver,
+    TypeGroups, create_static_resolver,
+};
+use phf::{Map, phf_map};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+/// Counts the heap allocations made by the current thread while tracking.
+struct CountingAllocator;
+
+thread_local! {
+    static TRACKING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if TRACKING.try_with(Cell::get).unwrap_or(false) {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        }
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Return the number of heap allocations made by `f` on this thread.
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    ALLOCATIONS.with(|count| count.set(0));
+    TRACKING.with(|tracking| tracking.set(true));
+    let result = f();
+    TRACKING.with(|tracking| tracking.set(false));
+    drop(result);
+    ALLOCATIONS.with(Cell::get)
+}
+
+static MAPPINGS: Map<&'static str, &'static str> = phf_map! {
+    "my_crate::types::UserId" => "UserId",
+    "serde_json::Value" => "JsonValue",
+};
+
+#[rstest]
+#[case::custom_exact("my_crate::types::UserId", Some("UserId"))]
+#[case::builtin_exact("std::collections::HashMap", Some("HashMap"))]
+#[case::builtin_raw("r#std::option::r#Option<u8>", Some("Option"))]
+#[case::builtin_bare("Option<String>", Some("Option"))]
+#[case::builtin_shortened("option::Option", Some("Option"))]
+#[case::primitive("u32", Some("u32"))]
+#[case::miss_bare("MyStruct", None)]
+#[case::miss_path("my_mod::inner::MyStruct<u8>", None)]
+fn resolve_does_not_allocate(#[case] input: &str, #[case] expected: Option<&str>) {
+    let path: Path = parse_str(input).unwrap();
+    let mut dynamic = DynamicPathResolver::with_all_groups();
+    dynamic.add_mapping("my_crate::types::UserId", "UserId");
+    dynamic.add_mapping("serde_json::Value", "JsonValue");
+    let primitive = PrimitivePathResolver::new(EmptyStorage, TypeGroups::ALL);
+    let indexed = PathResolver::new(IndexedStorage::new(&MAPPINGS), TypeGroups::ALL);
+
+    assert_eq!(dynamic.resolve(&path), expected);
+    assert_eq!(indexed.resolve(&path), expected);
+    // The builtin type index is built on first use
+    let _ = primitive.resolve(&path);
+    assert_eq!(allocations(|| dynamic.resolve(&path)), 0);
+    assert_eq!(allocations(|| indexed.resolve(&path)), 0);
+    assert_eq!(allocations(|| primitive.resolve(&path)), 0);
+    assert_eq!(allocations(|| dynamic.has_mapping(&path)), 0);
+}
+
+#[test]
+fn long_paths_still_resolve() {
+    let segments: Vec<String> = (0..64).map(|index| format!("module_{index}")).collect();
+    let long = format!("{}::UserId", segments.join("::"));
+
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping(&long, "LongUserId");
+
+    let path: Path = parse_str(&long).unwrap();
+    assert_eq!(resolver.resolve(&path), Some("LongUserId"));
+}
+
+#[rstest]
+#[case::custom_exact("my_crate::types::UserId", Some("UserId"))]
+#[case::custom_exact_other("serde_json::Value", Some("JsonValue"))]
+#[case::builtin_exact("std::collections::HashMap", Some("HashMap"))]
+fn static_resolver_exact_hits_do_not_allocate(#[case] input: &str, #[case] expected: Option<&str>) {
+    let path: Path = parse_str(input).unwrap();
+    let plain = create_static_resolver(&MAPPINGS, TypeGroups::ALL);
+
+    assert_eq!(plain.resolve(&path), expected);
+    assert_eq!(allocations(|| plain.resolve(&path)), 0);
+    assert_eq!(allocations(|| plain.has_mapping(&path)), 0);
+}
+
+#[rstest]
+#[case::builtin_bare("Option<String>", Some("Option"))]
+#[case::primitive("u32", Some("u32"))]
+#[case::miss_bare("MyStruct", None)]
+fn static_resolver_scans_keys_unless_indexed(#[case] input: &str, #[case] expected: Option<&str>) {
+    let path: Path = parse_str(input).unwrap();
+    let plain = create_static_resolver(&MAPPINGS, TypeGroups::ALL);
+    let indexed = PathResolver::new(IndexedStorage::new(&MAPPINGS), TypeGroups::ALL);
+
+    assert_eq!(plain.resolve(&path), expected);
+    assert_eq!(indexed.resolve(&path), expected);
+    // A plain phf map has no glob, generic pattern or suffix index to consult
+    assert!(allocations(|| plain.resolve(&path)) > 0);
+    assert_eq!(allocations(|| indexed.resolve(&path)), 0);
+}
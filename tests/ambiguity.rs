@@ -0,0 +1,121 @@
+#![cfg(test)]
+
+use desynt::{AmbiguityError, DynamicPathResolver, TypeGroups};
+use syn::{Path, parse_str};
+
+fn resolver() -> DynamicPathResolver {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("crate_b::UserId", "UserIdB");
+    resolver.add_mapping("crate_a::types::UserId", "UserIdA");
+    resolver.add_mapping("crate_c::UserId", "UserIdC");
+    resolver
+}
+
+#[test]
+fn all_candidates_in_preference_order() {
+    let path: Path = parse_str("UserId").unwrap();
+    let resolver = resolver();
+
+    let patterns: Vec<&str> = resolver
+        .resolve_candidates(&path)
+        .iter()
+        .map(|candidate| candidate.pattern)
+        .collect();
+    assert_eq!(
+        patterns,
+        vec![
+            "crate_b::UserId",
+            "crate_c::UserId",
+            "crate_a::types::UserId"
+        ]
+    );
+    assert_eq!(resolver.resolve(&path), Some("UserIdB"));
+}
+
+#[test]
+fn candidates_ignore_unrelated_suffixes() {
+    let path: Path = parse_str("Id").unwrap();
+    assert!(resolver().resolve_candidates(&path).is_empty());
+}
+
+#[test]
+fn priority_overrides_default_tie_break() {
+    let mut resolver = resolver();
+    resolver.set_priority("r#crate_a::types::UserId", 5);
+
+    let path: Path = parse_str("UserId").unwrap();
+    assert_eq!(resolver.priority("crate_a::types::UserId"), 5);
+    assert_eq!(resolver.resolve(&path), Some("UserIdA"));
+    assert_eq!(resolver.resolve_candidates(&path)[0].priority, 5);
+}
+
+#[test]
+fn priority_overrides_stdlib_preference() {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping("std::option::Option", "StdOption");
+    resolver.add_mapping_with_priority("my::Option", "MyOption", 1);
+
+    let path: Path = parse_str("Option<T>").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("MyOption"));
+}
+
+#[test]
+fn strict_reports_ambiguity() {
+    let path: Path = parse_str("UserId").unwrap();
+
+    let error = resolver().resolve_strict(&path).unwrap_err();
+    assert_eq!(
+        error,
+        AmbiguityError {
+            name: "UserId".to_string(),
+            candidates: vec![
+                ("crate_b::UserId".to_string(), "UserIdB".to_string()),
+                ("crate_c::UserId".to_string(), "UserIdC".to_string()),
+                ("crate_a::types::UserId".to_string(), "UserIdA".to_string()),
+            ],
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "`UserId` is ambiguous between `crate_b::UserId` (UserIdB), \
+         `crate_c::UserId` (UserIdC), `crate_a::types::UserId` (UserIdA)"
+    );
+}
+
+#[test]
+fn strict_accepts_explicit_priority() {
+    let mut resolver = resolver();
+    resolver.set_priority("crate_c::UserId", 1);
+
+    let path: Path = parse_str("UserId").unwrap();
+    assert_eq!(resolver.resolve_strict(&path), Ok(Some("UserIdC")));
+}
+
+#[test]
+fn strict_accepts_same_canonical_name() {
+    let mut resolver = DynamicPathResolver::from_map(Default::default(), TypeGroups::NONE);
+    resolver.add_mapping("std::option::Option", "Option");
+    resolver.add_mapping("core::option::Option", "Option");
+
+    let path: Path = parse_str("Option<u8>").unwrap();
+    assert_eq!(resolver.resolve_strict(&path), Ok(Some("Option")));
+}
+
+#[test]
+fn strict_exact_and_missing() {
+    let resolver = resolver();
+
+    let exact: Path = parse_str("crate_b::UserId").unwrap();
+    let missing: Path = parse_str("unknown::Type").unwrap();
+    assert_eq!(resolver.resolve_strict(&exact), Ok(Some("UserIdB")));
+    assert_eq!(resolver.resolve_strict(&missing), Ok(None));
+}
+
+#[test]
+fn clear_removes_priorities() {
+    let mut resolver = resolver();
+    resolver.set_priority("crate_c::UserId", 1);
+    resolver.clear();
+
+    assert_eq!(resolver.priority("crate_c::UserId"), 0);
+}
@@ -0,0 +1,88 @@
+#![cfg(test)]
+
+use desynt::{BUILTIN_TYPES, EmptyStorage, PathResolver, TypeGroup, TypeGroups};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+#[test]
+fn every_builtin_path_resolves_to_its_canonical_name() {
+    let resolver = PathResolver::<EmptyStorage>::all_groups();
+    for builtin in BUILTIN_TYPES {
+        for path in builtin.all_paths() {
+            let parsed: Path = parse_str(&path).unwrap();
+            let resolution = resolver.resolve_detailed(&parsed).unwrap();
+            assert_eq!(resolution.canonical, builtin.canonical, "{path}");
+            assert_eq!(resolution.group, Some(builtin.group), "{path}");
+        }
+    }
+}
+
+#[test]
+fn every_builtin_path_starts_with_its_module() {
+    for builtin in BUILTIN_TYPES {
+        let expected = format!("{}::{}", builtin.module, builtin.canonical);
+        assert!(builtin.paths.contains(&expected.as_str()), "{expected}");
+    }
+}
+
+#[rstest]
+#[case::primitives(TypeGroup::Primitives, "u8", "String")]
+#[case::prelude(TypeGroup::Prelude, "Option", "HashMap")]
+#[case::common_std(TypeGroup::CommonStd, "HashMap", "u8")]
+#[case::concurrency(TypeGroup::Concurrency, "Mutex", "Arc")]
+#[case::system(TypeGroup::System, "Duration", "Mutex")]
+#[case::numeric(TypeGroup::Numeric, "NonZeroU8", "u8")]
+#[case::markers(TypeGroup::Markers, "PhantomData", "NonZero")]
+fn group_listing(#[case] group: TypeGroup, #[case] included: &str, #[case] excluded: &str) {
+    let names: Vec<&str> = group
+        .builtin_types()
+        .map(|builtin| builtin.canonical)
+        .collect();
+    assert!(names.contains(&included));
+    assert!(!names.contains(&excluded));
+    assert!(group.builtin_types().all(|builtin| builtin.group == group));
+}
+
+#[test]
+fn groups_listing_follows_enabled_groups() {
+    assert_eq!(TypeGroups::NONE.builtin_types().count(), 0);
+    assert_eq!(TypeGroups::ALL.builtin_types().count(), BUILTIN_TYPES.len());
+    assert_eq!(
+        TypeGroups::PRELUDE.builtin_types().count(),
+        TypeGroup::Primitives.builtin_types().count() + TypeGroup::Prelude.builtin_types().count()
+    );
+}
+
+#[test]
+fn canonical_types_follow_the_table() {
+    let resolver = PathResolver::<EmptyStorage>::with_prelude();
+    let expected: Vec<&str> = TypeGroups::PRELUDE
+        .builtin_types()
+        .map(|builtin| builtin.canonical)
+        .collect();
+    assert_eq!(resolver.canonical_types().collect::<Vec<_>>(), expected);
+}
+
+#[rstest]
+#[case::module_suffix("collections::HashMap", Some("HashMap"))]
+#[case::nested_module_suffix("sync::Arc", Some("Arc"))]
+#[case::inner_module_suffix("hash_map::HashMap", Some("HashMap"))]
+#[case::std_root("std::HashMap", Some("HashMap"))]
+#[case::foreign_module("my_mod::HashMap", None)]
+fn shortened_paths(#[case] input: &str, #[case] expected: Option<&str>) {
+    let resolver = PathResolver::<EmptyStorage>::all_groups();
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver.resolve(&path), expected);
+}
+
+/// Until 0.2.0 any std module name in the path allowed the shortening.
+#[rstest]
+#[case::collections("my_crate::collections::HashMap")]
+#[case::sync("other::sync::Arc")]
+#[case::option("my_crate::option::Option")]
+fn foreign_crates_with_std_module_names_are_not_shortenings(#[case] input: &str) {
+    let resolver = PathResolver::<EmptyStorage>::all_groups();
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver.resolve(&path), None);
+    assert!(!resolver.has_mapping(&path));
+}
@@ -0,0 +1,197 @@
+#![cfg(test)]
+
+use std::sync::Arc;
+
+use desynt::{
+    CachingResolver, DynamicPathResolver, MappingStorage, PathResolver, ResolutionPolicy,
+    TypeGroups,
+};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+fn resolve<M: MappingStorage>(resolver: &CachingResolver<M>, input: &str) -> Option<String> {
+    let path: Path = parse_str(input).unwrap();
+    resolver
+        .resolve(&path)
+        .map(|canonical| canonical.to_string())
+}
+
+#[rstest]
+#[case::custom("my_crate::types::UserId", Some("UserId"))]
+#[case::builtin("std::option::Option", Some("Option"))]
+#[case::bare("Option<String>", Some("Option"))]
+#[case::raw("r#my_crate::types::r#UserId", Some("UserId"))]
+#[case::miss("my_crate::types::Unknown", None)]
+fn matches_uncached_resolver(#[case] input: &str, #[case] expected: Option<&str>) {
+    let mut resolver = CachingResolver::new(DynamicPathResolver::with_all_groups());
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver.resolver().resolve(&path), expected);
+    assert_eq!(resolve(&resolver, input).as_deref(), expected);
+    assert_eq!(resolve(&resolver, input).as_deref(), expected);
+    assert_eq!((resolver.hits(), resolver.misses()), (1, 1));
+}
+
+#[test]
+fn keys_on_normalized_path() {
+    let mut resolver = CachingResolver::new(DynamicPathResolver::with_all_groups());
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+    resolve(&resolver, "my_crate::types::UserId");
+    resolve(&resolver, "::my_crate::types::r#UserId");
+    resolve(&resolver, "Option<u8>");
+    resolve(&resolver, "Option<String>");
+    assert_eq!(resolver.len(), 2);
+    assert_eq!((resolver.hits(), resolver.misses()), (2, 2));
+}
+
+#[test]
+fn caches_misses() {
+    let resolver = CachingResolver::new(DynamicPathResolver::with_all_groups());
+    assert_eq!(resolve(&resolver, "MyStruct"), None);
+    assert_eq!(resolve(&resolver, "MyStruct"), None);
+    assert_eq!((resolver.hits(), resolver.misses()), (1, 1));
+}
+
+#[test]
+fn generic_patterns_bypass_cache_for_arguments() {
+    let mut resolver = CachingResolver::new(DynamicPathResolver::with_all_groups());
+    resolver.add_mapping("Vec<u8>", "Bytes");
+
+    assert_eq!(resolve(&resolver, "Vec<u8>").as_deref(), Some("Bytes"));
+    assert_eq!(resolve(&resolver, "Vec<u16>").as_deref(), Some("Vec"));
+    assert_eq!(resolve(&resolver, "Vec<u8>").as_deref(), Some("Bytes"));
+    assert!(resolver.is_empty());
+    assert_eq!((resolver.hits(), resolver.misses()), (0, 3));
+
+    resolve(&resolver, "Vec");
+    assert_eq!(resolver.len(), 1);
+}
+
+#[rstest]
+#[case::add_mapping(|resolver: &mut CachingResolver| {
+    resolver.add_mapping("my_crate::types::UserId", "Identifier");
+}, Some("Identifier"))]
+#[case::add_mapping_with_priority(|resolver: &mut CachingResolver| {
+    resolver.add_mapping_with_priority("my_crate::types::UserId", "Identifier", 1);
+}, Some("Identifier"))]
+#[case::add_matcher(|resolver: &mut CachingResolver| {
+    resolver.clear();
+    resolver.add_matcher("ids", |segments: &[&str]| {
+        segments.last().filter(|name| name.ends_with("Id")).map(|_| "Identifier")
+    });
+}, Some("Identifier"))]
+#[case::clear(|resolver: &mut CachingResolver| resolver.clear(), None)]
+#[case::resolver_mut(|resolver: &mut CachingResolver| {
+    resolver.resolver_mut().clear();
+}, None)]
+fn configuration_changes_invalidate(
+    #[case] change: fn(&mut CachingResolver),
+    #[case] expected: Option<&str>,
+) {
+    let mut resolver = CachingResolver::new(DynamicPathResolver::with_all_groups());
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+    assert_eq!(
+        resolve(&resolver, "my_crate::types::UserId").as_deref(),
+        Some("UserId")
+    );
+    assert_eq!(resolver.len(), 1);
+
+    change(&mut resolver);
+    assert!(resolver.is_empty());
+    assert_eq!(
+        resolve(&resolver, "my_crate::types::UserId").as_deref(),
+        expected
+    );
+}
+
+#[rstest]
+#[case::set_groups(|resolver: &mut CachingResolver| resolver.set_groups(TypeGroups::NONE))]
+#[case::set_policy(|resolver: &mut CachingResolver| {
+    resolver.set_policy(ResolutionPolicy::EXACT);
+})]
+fn group_and_policy_changes_invalidate(#[case] change: fn(&mut CachingResolver)) {
+    let mut resolver = CachingResolver::new(DynamicPathResolver::with_all_groups());
+    assert_eq!(resolve(&resolver, "Option<u8>").as_deref(), Some("Option"));
+
+    change(&mut resolver);
+    assert!(resolver.is_empty());
+    assert_eq!(resolve(&resolver, "Option<u8>"), None);
+}
+
+#[test]
+fn evicts_oldest_entries() {
+    let resolver = CachingResolver::with_capacity(DynamicPathResolver::with_all_groups(), 2);
+    resolve(&resolver, "u8");
+    resolve(&resolver, "u16");
+    resolve(&resolver, "u32");
+    assert_eq!(resolver.len(), 2);
+
+    resolve(&resolver, "u16");
+    resolve(&resolver, "u32");
+    assert_eq!(resolver.hits(), 2);
+    resolve(&resolver, "u8");
+    assert_eq!(resolver.misses(), 4);
+}
+
+#[test]
+fn zero_capacity_disables_caching() {
+    let resolver = CachingResolver::with_capacity(DynamicPathResolver::with_all_groups(), 0);
+    assert_eq!(resolve(&resolver, "u8").as_deref(), Some("u8"));
+    assert_eq!(resolve(&resolver, "u8").as_deref(), Some("u8"));
+    assert!(resolver.is_empty());
+    assert_eq!((resolver.hits(), resolver.misses()), (0, 2));
+}
+
+#[test]
+fn reset_stats_keeps_entries() {
+    let mut resolver = CachingResolver::new(DynamicPathResolver::with_all_groups());
+    resolve(&resolver, "u8");
+    resolve(&resolver, "u8");
+    resolver.reset_stats();
+    assert_eq!((resolver.hits(), resolver.misses()), (0, 0));
+    assert_eq!(resolver.len(), 1);
+
+    resolver.invalidate();
+    assert!(resolver.is_empty());
+}
+
+#[test]
+fn wraps_any_storage() {
+    let resolver = CachingResolver::from(PathResolver::primitives_only());
+    assert_eq!(
+        resolve(&resolver, "std::primitive::u8").as_deref(),
+        Some("u8")
+    );
+    assert_eq!(
+        resolve(&resolver, "std::primitive::u8").as_deref(),
+        Some("u8")
+    );
+    assert_eq!(resolver.hits(), 1);
+    assert_eq!(resolver.into_inner().groups(), TypeGroups::PRIMITIVES);
+}
+
+#[test]
+fn shared_between_threads() {
+    let mut resolver = CachingResolver::new(DynamicPathResolver::with_all_groups());
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+    let resolver = Arc::new(resolver);
+    let inputs = ["my_crate::types::UserId", "u8", "Option<u8>", "MyStruct"];
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            let resolver = Arc::clone(&resolver);
+            scope.spawn(move || {
+                for _ in 0..25 {
+                    for input in inputs {
+                        let expected = resolver.resolver().resolve(&parse_str(input).unwrap());
+                        assert_eq!(resolve(&resolver, input).as_deref(), expected);
+                    }
+                }
+            });
+        }
+    });
+
+    assert_eq!(resolver.len(), inputs.len());
+    assert_eq!(resolver.hits() + resolver.misses(), 4 * 25 * 4);
+    assert!(resolver.misses() >= 4);
+}
@@ -0,0 +1,130 @@
+#![cfg(test)]
+
+use desynt::{CanonicalPath, CanonicalType, DynamicPathResolver};
+use syn::{Path, Type, parse_str};
+
+fn resolver() -> DynamicPathResolver {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("my::UserId", "UserId");
+    resolver
+}
+
+fn named(name: &str, args: Vec<CanonicalType>) -> CanonicalType {
+    CanonicalType::Path(CanonicalPath {
+        name: name.to_string(),
+        resolved: true,
+        args,
+        bindings: vec![],
+    })
+}
+
+#[test]
+fn nested_generics() {
+    let path: Path = parse_str("Option<Vec<my::UserId>>").unwrap();
+
+    let canonical = resolver().resolve_canonical(&path);
+    assert_eq!(canonical.name, "Option");
+    assert!(canonical.resolved);
+    assert_eq!(
+        canonical.args,
+        vec![named("Vec", vec![named("UserId", vec![])])]
+    );
+    assert!(canonical.bindings.is_empty());
+}
+
+#[test]
+fn raw_identifiers_in_arguments() {
+    let path: Path = parse_str("r#std::collections::HashMap<r#my::r#UserId, r#String>").unwrap();
+
+    let canonical = resolver().resolve_canonical(&path);
+    assert_eq!(canonical.name, "HashMap");
+    assert_eq!(
+        canonical.args,
+        vec![named("UserId", vec![]), named("String", vec![])]
+    );
+}
+
+#[test]
+fn non_generic() {
+    let path: Path = parse_str("my::UserId").unwrap();
+
+    let canonical = resolver().resolve_canonical(&path);
+    assert_eq!(canonical.name, "UserId");
+    assert!(canonical.args.is_empty());
+}
+
+#[test]
+fn unresolved_outer_with_resolved_arguments() {
+    let path: Path = parse_str("butane::ForeignKey<my::UserId>").unwrap();
+
+    let canonical = resolver().resolve_canonical(&path);
+    assert_eq!(canonical.name, "butane::ForeignKey");
+    assert!(!canonical.resolved);
+    assert_eq!(canonical.args, vec![named("UserId", vec![])]);
+}
+
+#[test]
+fn lifetimes_are_dropped() {
+    let path: Path = parse_str("std::borrow::Cow<'static, str>").unwrap();
+
+    let canonical = resolver().resolve_canonical(&path);
+    assert_eq!(canonical.name, "Cow");
+    assert_eq!(canonical.args, vec![named("str", vec![])]);
+}
+
+#[test]
+fn const_arguments() {
+    let path: Path = parse_str("arrayvec::ArrayVec<u8, 16>").unwrap();
+
+    let canonical = resolver().resolve_canonical(&path);
+    assert_eq!(
+        canonical.args,
+        vec![
+            named("u8", vec![]),
+            CanonicalType::Const(parse_str("16").unwrap())
+        ]
+    );
+}
+
+#[test]
+fn associated_type_bindings() {
+    let path: Path = parse_str("Iterator<Item = Option<my::UserId>>").unwrap();
+
+    let canonical = resolver().resolve_canonical(&path);
+    assert!(canonical.args.is_empty());
+    assert_eq!(
+        canonical.bindings,
+        vec![(
+            "Item".to_string(),
+            named("Option", vec![named("UserId", vec![])])
+        )]
+    );
+}
+
+#[test]
+fn parenthesized_arguments() {
+    let ty: Type = parse_str("dyn Fn(my::UserId, &str) -> Result<u8, String>").unwrap();
+
+    let CanonicalType::TraitObject(bounds) = resolver().resolve_type(&ty) else {
+        panic!("expected a trait object");
+    };
+    let canonical = &bounds[0];
+    assert_eq!(canonical.name, "Fn");
+    assert_eq!(
+        canonical.args,
+        vec![CanonicalType::Tuple(vec![
+            named("UserId", vec![]),
+            CanonicalType::Reference {
+                mutable: false,
+                elem: Box::new(named("str", vec![])),
+            },
+        ])]
+    );
+    assert_eq!(
+        canonical.bindings,
+        vec![(
+            "Output".to_string(),
+            named("Result", vec![named("u8", vec![]), named("String", vec![])])
+        )]
+    );
+}
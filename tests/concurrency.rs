@@ -0,0 +1,120 @@
+#![cfg(test)]
+
+use desynt::{DynamicPathResolver, EmptyStorage, PathResolver, TypeGroup, TypeGroups};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+const CONCURRENCY: TypeGroups = TypeGroups::NONE.with_group(TypeGroup::Concurrency);
+
+fn resolve(groups: TypeGroups, input: &str) -> Option<String> {
+    let resolver = PathResolver::new(EmptyStorage, groups);
+    let path: Path = parse_str(input).unwrap();
+    resolver.resolve(&path).map(str::to_string)
+}
+
+#[rstest]
+#[case::mutex("std::sync::Mutex<u8>", "Mutex")]
+#[case::rw_lock("std::sync::RwLock", "RwLock")]
+#[case::condvar("std::sync::Condvar", "Condvar")]
+#[case::once("std::sync::Once", "Once")]
+#[case::once_lock("std::sync::OnceLock", "OnceLock")]
+#[case::lazy_lock("std::sync::LazyLock", "LazyLock")]
+#[case::sync_weak("std::sync::Weak", "Weak")]
+#[case::alloc_sync_weak("alloc::sync::Weak", "Weak")]
+#[case::rc_weak("std::rc::Weak", "Weak")]
+#[case::alloc_rc_weak("alloc::rc::Weak", "Weak")]
+#[case::cell("std::cell::Cell", "Cell")]
+#[case::core_cell("core::cell::Cell", "Cell")]
+#[case::once_cell("core::cell::OnceCell", "OnceCell")]
+#[case::unsafe_cell("std::cell::UnsafeCell", "UnsafeCell")]
+#[case::atomic_bool("std::sync::atomic::AtomicBool", "AtomicBool")]
+#[case::atomic_usize("core::sync::atomic::AtomicUsize", "AtomicUsize")]
+#[case::atomic_ptr("std::sync::atomic::AtomicPtr<u8>", "AtomicPtr")]
+#[case::sender("std::sync::mpsc::Sender", "Sender")]
+#[case::sync_sender("std::sync::mpsc::SyncSender", "SyncSender")]
+#[case::receiver("std::sync::mpsc::Receiver", "Receiver")]
+#[case::join_handle("std::thread::JoinHandle", "JoinHandle")]
+fn full_paths(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(resolve(CONCURRENCY, input).as_deref(), Some(expected));
+    assert_eq!(resolve(TypeGroups::ALL, input).as_deref(), Some(expected));
+    assert_eq!(resolve(TypeGroups::PRELUDE, input), None);
+}
+
+#[rstest]
+#[case::bare("Mutex", Some("Mutex"))]
+#[case::bare_generic("AtomicU64", Some("AtomicU64"))]
+#[case::module("sync::RwLock", Some("RwLock"))]
+#[case::nested_module("atomic::AtomicI32", Some("AtomicI32"))]
+#[case::channel_module("mpsc::Receiver", Some("Receiver"))]
+#[case::thread_module("thread::JoinHandle", Some("JoinHandle"))]
+#[case::foreign_module("my_mod::Mutex", None)]
+fn shortened_paths(#[case] input: &str, #[case] expected: Option<&str>) {
+    assert_eq!(resolve(CONCURRENCY, input).as_deref(), expected);
+}
+
+#[rstest]
+#[case::tokio_mutex("tokio::sync::Mutex")]
+#[case::tokio_rw_lock("tokio::sync::RwLock")]
+#[case::tokio_sender("tokio::sync::mpsc::Sender")]
+#[case::tokio_join_handle("tokio::task::JoinHandle")]
+#[case::futures_receiver("futures::channel::mpsc::Receiver")]
+#[case::async_std_thread("async_std::thread::JoinHandle")]
+fn async_runtime_types_are_not_std(#[case] input: &str) {
+    let resolver = DynamicPathResolver::with_all_groups();
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver.resolve(&path), None);
+}
+
+#[rstest]
+#[case::mutex("core::sync::Mutex")]
+#[case::join_handle("core::thread::JoinHandle")]
+#[case::sender("alloc::sync::mpsc::Sender")]
+fn std_only_types_are_not_exact_under_core_or_alloc(#[case] input: &str) {
+    let resolver = PathResolver::new(EmptyStorage, CONCURRENCY);
+    let path: Path = parse_str(input).unwrap();
+    assert!(!resolver.has_mapping(&path));
+}
+
+#[test]
+fn bare_weak_prefers_sync() {
+    let resolver = PathResolver::new(EmptyStorage, CONCURRENCY);
+    let path: Path = parse_str("Weak").unwrap();
+    let resolution = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(resolution.canonical, "Weak");
+    assert_eq!(resolution.pattern, "std::sync::Weak");
+    assert_eq!(resolution.group, Some(TypeGroup::Concurrency));
+}
+
+#[test]
+fn canonical_types() {
+    let resolver = PathResolver::new(EmptyStorage, CONCURRENCY);
+    let types: Vec<&str> = resolver.canonical_types().collect();
+    assert!(types.contains(&"Mutex"));
+    assert!(types.contains(&"JoinHandle"));
+    assert!(!types.contains(&"Arc"));
+    assert_eq!(types.iter().filter(|name| **name == "Weak").count(), 1);
+}
+
+#[test]
+fn toggle() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    assert!(resolver.uses_concurrency());
+    let path: Path = parse_str("std::sync::Mutex").unwrap();
+    assert!(resolver.has_mapping(&path));
+
+    resolver.set_groups(TypeGroups::ALL.without_group(TypeGroup::Concurrency));
+    assert!(!resolver.uses_concurrency());
+    assert!(!resolver.has_mapping(&path));
+    assert_eq!(resolver.resolve(&path), None);
+}
+
+#[test]
+fn custom_mappings_win() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("parking_lot::Mutex", "FairMutex");
+
+    let path: Path = parse_str("Mutex").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("FairMutex"));
+    let path: Path = parse_str("std::sync::Mutex").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("Mutex"));
+}
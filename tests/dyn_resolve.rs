@@ -0,0 +1,172 @@
+#![cfg(test)]
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use desynt::{
+    DynamicPathResolver, DynamicStorage, EmptyStorage, LayeredResolver, MappingStorage,
+    NamedMatcher, PRIMITIVE_RESOLVER, PathResolver, Resolve, SliceStorage, TypeGroups,
+};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+fn resolve(resolver: &dyn Resolve, input: &str) -> Option<String> {
+    let path: Path = parse_str(input).unwrap();
+    resolver.resolve(&path).map(str::to_string)
+}
+
+#[rstest]
+#[case::exact("my_crate::types::UserId", Some("UserId"))]
+#[case::suffix("UserId", Some("UserId"))]
+#[case::glob("my_crate::billing::Money", Some("Money"))]
+#[case::matcher("api::GetUserRequest", Some("Request"))]
+#[case::builtin("Option<u8>", Some("Option"))]
+#[case::miss("my_crate::Unknown", None)]
+fn pointer_storages_delegate(#[case] input: &str, #[case] expected: Option<&str>) {
+    let mut storage = DynamicStorage::new();
+    storage.insert("my_crate::types::UserId".to_string(), "UserId".to_string());
+    storage.insert("my_crate::*::Money".to_string(), "Money".to_string());
+    storage.add_matcher(NamedMatcher::new("requests", |segments: &[&str]| {
+        segments
+            .last()
+            .filter(|name| name.ends_with("Request"))
+            .map(|_| "Request")
+    }));
+
+    let boxed: Box<dyn MappingStorage> = Box::new(storage.clone());
+    let shared: Arc<dyn MappingStorage + Send + Sync> = Arc::new(storage.clone());
+    let resolvers: Vec<Box<dyn Resolve + '_>> = vec![
+        Box::new(PathResolver::new(boxed, TypeGroups::ALL)),
+        Box::new(PathResolver::new(shared, TypeGroups::ALL)),
+        Box::new(PathResolver::new(&storage, TypeGroups::ALL)),
+        Box::new(PathResolver::new(storage.clone(), TypeGroups::ALL)),
+    ];
+
+    for resolver in &resolvers {
+        assert_eq!(resolve(resolver.as_ref(), input).as_deref(), expected);
+    }
+}
+
+#[test]
+fn resolvers_of_different_backends_in_one_vec() {
+    let mut custom = DynamicPathResolver::default();
+    custom.add_mapping("my_crate::types::UserId", "UserId");
+    let mut btree = BTreeMap::new();
+    btree.insert("serde_json::Value".to_string(), "JsonValue".to_string());
+    let mut borrowed = HashMap::new();
+    borrowed.insert("uuid::Uuid", "Uuid");
+
+    let resolvers: Vec<Box<dyn Resolve>> = vec![
+        Box::new(custom),
+        Box::new(PathResolver::new(btree, TypeGroups::NONE)),
+        Box::new(PathResolver::new(borrowed, TypeGroups::NONE)),
+        Box::new(PathResolver::new(
+            SliceStorage::new(&[("chrono::DateTime", "DateTime")]),
+            TypeGroups::NONE,
+        )),
+        Box::new(PRIMITIVE_RESOLVER),
+    ];
+
+    let resolve_any = |input: &str| {
+        let path: Path = parse_str(input).unwrap();
+        resolvers
+            .iter()
+            .find_map(|resolver| resolver.resolve(&path).map(str::to_string))
+    };
+    assert_eq!(
+        resolve_any("my_crate::types::UserId").as_deref(),
+        Some("UserId")
+    );
+    assert_eq!(
+        resolve_any("serde_json::Value").as_deref(),
+        Some("JsonValue")
+    );
+    assert_eq!(resolve_any("uuid::Uuid").as_deref(), Some("Uuid"));
+    assert_eq!(resolve_any("chrono::DateTime").as_deref(), Some("DateTime"));
+    assert_eq!(resolve_any("u8").as_deref(), Some("u8"));
+    assert_eq!(resolve_any("Unknown"), None);
+
+    let patterns: Vec<&str> = resolvers
+        .iter()
+        .flat_map(|resolver| resolver.path_patterns())
+        .collect();
+    assert_eq!(patterns.len(), 4);
+}
+
+#[test]
+fn trait_matches_inherent_methods() {
+    let mut resolver = DynamicPathResolver::with_prelude();
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+    let dyn_resolver: &dyn Resolve = &resolver;
+
+    let path: Path = parse_str("my_crate::types::UserId").unwrap();
+    assert!(dyn_resolver.has_mapping(&path));
+    assert_eq!(dyn_resolver.resolve(&path), resolver.resolve(&path));
+    assert_eq!(
+        dyn_resolver.canonical_types().collect::<Vec<_>>(),
+        resolver.canonical_types().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        dyn_resolver.path_patterns().collect::<Vec<_>>(),
+        ["my_crate::types::UserId"]
+    );
+}
+
+#[test]
+fn layered_resolver_implements_resolve() {
+    let mut overrides = DynamicPathResolver::default();
+    overrides.add_mapping("my_crate::types::UserId", "Identifier");
+    let layered = LayeredResolver::new()
+        .with_layer("overrides", overrides)
+        .with_layer(
+            "primitives",
+            PathResolver::new(EmptyStorage, TypeGroups::PRIMITIVES),
+        );
+    let shared: Arc<dyn Resolve + Send + Sync> = Arc::new(layered);
+
+    assert_eq!(
+        resolve(&shared, "my_crate::types::UserId").as_deref(),
+        Some("Identifier")
+    );
+    assert_eq!(resolve(&shared, "u8").as_deref(), Some("u8"));
+    assert!(shared.has_mapping(&parse_str("std::primitive::u8").unwrap()));
+    assert!(shared.canonical_types().any(|name| name == "Identifier"));
+    assert!(shared.canonical_types().any(|name| name == "u8"));
+    assert_eq!(
+        shared.path_patterns().collect::<Vec<_>>(),
+        ["my_crate::types::UserId"]
+    );
+}
+
+/// A resolver answering every path with its last segment.
+struct MockResolver;
+
+impl Resolve for MockResolver {
+    fn resolve(&self, path: &Path) -> Option<&str> {
+        path.segments.last().map(|_| "Mocked")
+    }
+
+    fn has_mapping(&self, _path: &Path) -> bool {
+        true
+    }
+
+    fn canonical_types(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(std::iter::once("Mocked"))
+    }
+
+    fn path_patterns(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(std::iter::empty())
+    }
+}
+
+#[test]
+fn mocks_implement_resolve() {
+    let mock = MockResolver;
+    assert_eq!(
+        resolve(&mock, "anything::At::All").as_deref(),
+        Some("Mocked")
+    );
+    assert_eq!(resolve(&&mock, "Other").as_deref(), Some("Mocked"));
+    let boxed: Box<dyn Resolve> = Box::new(mock);
+    assert_eq!(boxed.canonical_types().collect::<Vec<_>>(), ["Mocked"]);
+}
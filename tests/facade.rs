@@ -0,0 +1,159 @@
+#![cfg(test)]
+
+use desynt::{
+    BUILTIN_TYPES, CachingResolver, DynamicPathResolver, EmptyStorage, PathResolver, Strategy,
+    TypeGroup,
+};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+#[rstest]
+#[case::core_option("core::option::Option", "Option")]
+#[case::core_result("core::result::Result", "Result")]
+#[case::core_refcell("core::cell::RefCell", "RefCell")]
+#[case::core_primitive("core::primitive::u8", "u8")]
+#[case::alloc_vec("alloc::vec::Vec", "Vec")]
+#[case::alloc_string("alloc::string::String", "String")]
+#[case::alloc_btree_map("alloc::collections::BTreeMap", "BTreeMap")]
+#[case::alloc_btree_map_module("alloc::collections::btree_map::BTreeMap", "BTreeMap")]
+#[case::alloc_cow("alloc::borrow::Cow", "Cow")]
+#[case::alloc_arc("alloc::sync::Arc", "Arc")]
+fn reexported_builtins_resolve_exactly(#[case] input: &str, #[case] expected: &str) {
+    let resolver = PathResolver::<EmptyStorage>::all_groups();
+    let path: Path = parse_str(input).unwrap();
+    let resolution = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(resolution.canonical, expected);
+    assert_eq!(resolution.strategy, Strategy::Exact);
+    assert!(resolver.has_mapping(&path));
+}
+
+#[rstest]
+#[case::std_only_hash_map("alloc::collections::HashMap")]
+#[case::std_only_hash_set("core::collections::HashSet")]
+#[case::wrong_crate("core::string::String")]
+fn items_missing_from_a_crate_are_not_exact(#[case] input: &str) {
+    let resolver = PathResolver::<EmptyStorage>::all_groups();
+    let path: Path = parse_str(input).unwrap();
+    assert!(!resolver.has_mapping(&path));
+}
+
+#[test]
+fn origins_are_facade_crates() {
+    for builtin in BUILTIN_TYPES {
+        assert!(
+            ["core", "alloc", "std"].contains(&builtin.origin),
+            "{}",
+            builtin.canonical
+        );
+        assert!(builtin.paths.iter().all(|path| path.starts_with("std::")));
+    }
+}
+
+#[test]
+fn bare_names_prefer_std_paths() {
+    let resolver = PathResolver::<EmptyStorage>::all_groups();
+    let path: Path = parse_str("Cow").unwrap();
+    let resolution = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(resolution.pattern, "std::borrow::Cow");
+    assert_eq!(resolution.group, Some(TypeGroup::CommonStd));
+}
+
+#[rstest]
+#[case::flat("hashbrown::HashMap", Some("HashMap"))]
+#[case::module("hashbrown::hash_set::HashSet", Some("HashSet"))]
+#[case::raw("r#hashbrown::HashMap", Some("HashMap"))]
+#[case::prefix_is_whole_segment("hashbrown_ext::Thing", None)]
+#[case::unknown_item("hashbrown::Unknown", None)]
+fn user_facade(#[case] input: &str, #[case] expected: Option<&str>) {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_facade("hashbrown", "std::collections");
+
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver.resolve(&path), expected);
+    assert_eq!(resolver.has_mapping(&path), expected.is_some());
+}
+
+#[test]
+fn facade_reports_the_rewritten_pattern() {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+    resolver.add_facade("my_facade", "my_crate::types");
+
+    let path: Path = parse_str("my_facade::UserId").unwrap();
+    let resolution = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(resolution.canonical, "UserId");
+    assert_eq!(resolution.pattern, "my_crate::types::UserId");
+    assert_eq!(resolution.strategy, Strategy::Exact);
+    assert_eq!(resolution.group, None);
+}
+
+#[test]
+fn facade_applies_to_glob_keys() {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping("my_crate::*::Money", "Money");
+    resolver.add_facade("reexport", "my_crate");
+
+    let path: Path = parse_str("reexport::billing::Money").unwrap();
+    let resolution = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(resolution.canonical, "Money");
+    assert_eq!(resolution.strategy, Strategy::Glob);
+}
+
+#[test]
+fn facades_are_tried_in_order_and_replaced_in_place() {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping("first::Item", "First");
+    resolver.add_mapping("second::Item", "Second");
+    resolver.add_facade("facade", "first");
+    resolver.add_facade("::r#facade", "second");
+
+    let path: Path = parse_str("facade::Item").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("Second"));
+
+    assert!(resolver.remove_facade("facade"));
+    assert!(!resolver.remove_facade("facade"));
+    assert_eq!(resolver.resolve(&path), None);
+}
+
+#[test]
+fn exact_keys_win_over_facades() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("hashbrown::HashMap", "FastMap");
+    resolver.add_facade("hashbrown", "std::collections");
+
+    let path: Path = parse_str("hashbrown::HashMap").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("FastMap"));
+}
+
+#[test]
+fn facades_do_not_chain() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_facade("a", "b");
+    resolver.add_facade("b", "a");
+
+    let path: Path = parse_str("a::Thing").unwrap();
+    assert_eq!(resolver.resolve(&path), None);
+}
+
+#[test]
+fn clear_removes_facades() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_facade("hashbrown", "std::collections");
+    resolver.clear();
+
+    let path: Path = parse_str("hashbrown::HashMap").unwrap();
+    assert!(!resolver.has_mapping(&path));
+}
+
+#[test]
+fn caching_resolver_invalidates_on_facade_change() {
+    let mut resolver = CachingResolver::new(DynamicPathResolver::with_all_groups());
+    let path: Path = parse_str("hashbrown::HashSet").unwrap();
+    assert_eq!(resolver.resolve(&path), None);
+
+    resolver.add_facade("hashbrown", "std::collections");
+    assert_eq!(resolver.resolve(&path).as_deref(), Some("HashSet"));
+
+    assert!(resolver.remove_facade("hashbrown"));
+    assert_eq!(resolver.resolve(&path), None);
+}
@@ -0,0 +1,201 @@
+#![cfg(test)]
+
+use std::collections::BTreeMap;
+
+use desynt::{
+    CanonicalPath, CanonicalType, DynamicPathResolver, DynamicStorage, GenericPattern,
+    IndexedStorage, MappingStorage, PathResolver, Strategy, TypeGroups,
+};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+fn resolver() -> DynamicPathResolver {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("Vec<u8>", "Bytes");
+    resolver.add_mapping("Result<$T, my_crate::Error>", "ApiResult");
+    resolver.add_mapping("HashMap<String, _>", "StringMap");
+    resolver.add_mapping("HashMap<$K, $K>", "SelfMap");
+    resolver.add_mapping("Option<Vec<$T>>", "OptionalList");
+    resolver.add_mapping("Option<Vec<u8>>", "OptionalBytes");
+    resolver.add_mapping("Box<[$T]>", "BoxedSlice");
+    resolver.add_mapping("Cow<'_, str>", "CowStr");
+    resolver.add_mapping("Rc<(_, _)>", "SharedPair");
+    resolver.add_mapping("my_crate::Error", "Error");
+    resolver
+}
+
+fn named(name: &str) -> CanonicalType {
+    CanonicalType::Path(CanonicalPath {
+        name: name.to_string(),
+        resolved: true,
+        args: vec![],
+        bindings: vec![],
+    })
+}
+
+#[rstest]
+#[case::concrete("Vec<u8>", Some("Bytes"))]
+#[case::concrete_long_paths("std::vec::Vec<core::primitive::u8>", Some("Bytes"))]
+#[case::other_argument("Vec<u16>", Some("Vec"))]
+#[case::capture("Result<User, my_crate::Error>", Some("ApiResult"))]
+#[case::capture_mapped_argument("Result<(), r#my_crate::Error>", Some("ApiResult"))]
+#[case::capture_mismatch("Result<User, OtherError>", Some("Result"))]
+#[case::wildcard("HashMap<String, Vec<u8>>", Some("StringMap"))]
+#[case::repeated_capture("HashMap<u8, u8>", Some("SelfMap"))]
+#[case::repeated_capture_mismatch("HashMap<u8, u16>", Some("HashMap"))]
+#[case::most_specific_nested("Option<Vec<u8>>", Some("OptionalBytes"))]
+#[case::nested_capture("Option<Vec<bool>>", Some("OptionalList"))]
+#[case::slice("Box<[i32]>", Some("BoxedSlice"))]
+#[case::lifetimes_ignored("Cow<'static, str>", Some("CowStr"))]
+#[case::tuple("Rc<(u8, String)>", Some("SharedPair"))]
+#[case::tuple_arity("Rc<(u8, String, bool)>", Some("Rc"))]
+#[case::arity("Result<u8>", Some("Result"))]
+#[case::no_arguments("Vec", Some("Vec"))]
+fn resolve_generic_patterns(#[case] input: &str, #[case] expected: Option<&str>) {
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver().resolve(&path), expected);
+}
+
+#[test]
+fn captures_are_returned() {
+    let resolver = resolver();
+
+    let path: Path = parse_str("Result<Vec<u8>, my_crate::Error>").unwrap();
+    let resolution = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(resolution.canonical, "ApiResult");
+    assert_eq!(resolution.pattern, "Result<$T, my_crate::Error>");
+    assert_eq!(resolution.strategy, Strategy::GenericPattern);
+    assert!(!resolution.is_exact());
+    assert!(!resolution.is_heuristic());
+
+    // Captured types are resolved, including through generic patterns
+    let bytes = CanonicalType::Path(CanonicalPath {
+        name: "Bytes".to_string(),
+        resolved: true,
+        args: vec![named("u8")],
+        bindings: vec![],
+    });
+    assert_eq!(resolution.captures, vec![("T".to_string(), bytes.clone())]);
+    assert_eq!(resolution.capture("T"), Some(&bytes));
+    assert_eq!(resolution.capture("E"), None);
+}
+
+#[test]
+fn non_generic_strategies_capture_nothing() {
+    let resolver = resolver();
+    let path: Path = parse_str("Option<u8>").unwrap();
+    let resolution = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(resolution.strategy, Strategy::BuiltinPattern);
+    assert!(resolution.captures.is_empty());
+}
+
+#[test]
+fn resolve_type_uses_generic_patterns() {
+    let ty: syn::Type = parse_str("Option<Vec<u8>>").unwrap();
+
+    let CanonicalType::Path(outer) = resolver().resolve_type(&ty) else {
+        panic!("expected a path");
+    };
+    assert_eq!(outer.name, "OptionalBytes");
+    let CanonicalType::Path(inner) = &outer.args[0] else {
+        panic!("expected a path");
+    };
+    assert_eq!(inner.name, "Bytes");
+}
+
+#[test]
+fn invalid_patterns_are_ignored() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("Vec<u8", "Broken");
+    resolver.add_mapping("Vec<$>", "Broken");
+    resolver.add_mapping("Vec<u8> extra", "Broken");
+
+    let path: Path = parse_str("Vec<u8>").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("Vec"));
+}
+
+#[rstest]
+#[case::space("Box<&mut $T>", "Box<&mut u8>", "Box<&u8>")]
+#[case::tab("Box<&mut\t$T>", "Box<&mut u8>", "Box<&u8>")]
+#[case::capture("Box<&mut$T>", "Box<&mut u8>", "Box<&u8>")]
+#[case::slice("Box<&mut[u8]>", "Box<&mut [u8]>", "Box<&[u8]>")]
+#[case::path_starting_with_mut(
+    "Box<&mutex::Guard>",
+    "Box<&mutex::Guard>",
+    "Box<&mut mutex::Guard>"
+)]
+fn mutable_references_are_tokenized(
+    #[case] key: &str,
+    #[case] matching: &str,
+    #[case] other: &str,
+) {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping(key, "Matched");
+
+    let path: Path = parse_str(matching).unwrap();
+    assert_eq!(resolver.resolve(&path), Some("Matched"));
+    let path: Path = parse_str(other).unwrap();
+    assert_eq!(resolver.resolve(&path), None);
+}
+
+const MAPPINGS: &[(&str, &str)] = &[
+    ("Vec<u8>", "Bytes"),
+    ("Result<$T, my_crate::Error>", "ApiResult"),
+    ("HashMap<String, _>", "StringMap"),
+    ("Option<Vec<$T>>", "OptionalList"),
+    ("Option<Vec<u8>>", "OptionalBytes"),
+    ("Vec<u8", "Broken"),
+    ("my_crate::Error", "Error"),
+];
+
+#[test]
+fn patterns_are_parsed_once_when_added() {
+    let mut storage = DynamicStorage::new();
+    for (key, value) in MAPPINGS {
+        storage.insert(key.to_string(), value.to_string());
+    }
+    let keys: Vec<&str> = storage
+        .generic_patterns()
+        .unwrap()
+        .iter()
+        .map(GenericPattern::key)
+        .collect();
+    assert_eq!(
+        keys,
+        [
+            "HashMap<String, _>",
+            "Option<Vec<$T>>",
+            "Option<Vec<u8>>",
+            "Result<$T, my_crate::Error>",
+            "Vec<u8>",
+        ]
+    );
+    assert_eq!(storage.generic_pattern_keys().collect::<Vec<_>>(), keys);
+
+    storage.remove("Vec<u8>");
+    assert_eq!(storage.generic_patterns().unwrap().len(), 4);
+}
+
+#[rstest]
+#[case::concrete("Vec<u8>")]
+#[case::capture("Result<User, my_crate::Error>")]
+#[case::nested("Option<Vec<u8>>")]
+#[case::nested_capture("Option<Vec<String>>")]
+#[case::miss("Vec<u16>")]
+fn storages_without_parsed_patterns_resolve_alike(#[case] input: &str) {
+    let map: BTreeMap<String, String> = MAPPINGS
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    assert!(map.generic_patterns().is_none());
+    let indexed = IndexedStorage::new(map.clone());
+    assert_eq!(indexed.generic_patterns().unwrap().len(), 5);
+    let dynamic = DynamicPathResolver::from_map(map.clone().into_iter().collect(), TypeGroups::ALL);
+    let unindexed = PathResolver::new(map, TypeGroups::ALL);
+    let indexed = PathResolver::new(indexed, TypeGroups::ALL);
+
+    let path: Path = parse_str(input).unwrap();
+    let expected = dynamic.resolve_detailed(&path);
+    assert_eq!(unindexed.resolve_detailed(&path), expected);
+    assert_eq!(indexed.resolve_detailed(&path), expected);
+}
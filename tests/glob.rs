@@ -0,0 +1,196 @@
+#![cfg(test)]
+
+use std::collections::BTreeMap;
+
+use desynt::{
+    DynamicPathResolver, DynamicStorage, GlobPattern, IndexedStorage, MappingStorage, PathResolver,
+    Strategy, TypeGroups,
+};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+#[rstest]
+#[case::one_segment("my_crate::v1::UserId", Some("UserId"))]
+#[case::one_segment_raw("r#my_crate::r#v2::UserId", Some("UserId"))]
+#[case::one_segment_too_deep("my_crate::internal::ids::UserId", None)]
+#[case::one_segment_missing("my_crate::UserId", None)]
+#[case::any_depth("my_crate::internal::ids::AccountId", Some("AccountId"))]
+#[case::any_depth_zero("my_crate::AccountId", Some("AccountId"))]
+#[case::alternation("my_crate::v2::Request", Some("Request"))]
+#[case::alternation_fallback("my_crate::v4::Request", Some("AnyRequest"))]
+#[case::exact_wins("my_crate::v3::Request", Some("RequestV3"))]
+#[case::leading_any_depth("a::b::legacy::Thing", Some("Legacy"))]
+#[case::generic_arguments("my_crate::v1::UserId<u8>", Some("UserId"))]
+#[case::other_crate("other::v1::UserId", None)]
+fn resolve_glob_keys(#[case] input: &str, #[case] expected: Option<&str>) {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("my_crate::*::UserId", "UserId");
+    resolver.add_mapping("my_crate::**::AccountId", "AccountId");
+    resolver.add_mapping("my_crate::{v1, v2}::Request", "Request");
+    resolver.add_mapping("my_crate::**::Request", "AnyRequest");
+    resolver.add_mapping("my_crate::v3::Request", "RequestV3");
+    resolver.add_mapping("**::legacy::*", "Legacy");
+
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver.resolve(&path), expected);
+}
+
+#[test]
+fn resolution_reports_glob() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("my_crate::{v1, v2}::Request", "Request");
+    resolver.add_mapping("my_crate::v3::Request", "RequestV3");
+
+    let path: Path = parse_str("my_crate::v1::Request").unwrap();
+    let resolution = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(resolution.pattern, "my_crate::{v1, v2}::Request");
+    assert_eq!(resolution.strategy, Strategy::Glob);
+
+    let path: Path = parse_str("my_crate::v3::Request").unwrap();
+    assert_eq!(
+        resolver.resolve_detailed(&path).unwrap().strategy,
+        Strategy::Exact
+    );
+}
+
+#[test]
+fn priority_beats_specificity() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("my_crate::{v1, v2}::Request", "Request");
+    resolver.add_mapping("my_crate::**::Request", "AnyRequest");
+    resolver.set_priority("my_crate::**::Request", 1);
+
+    let path: Path = parse_str("my_crate::v1::Request").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("AnyRequest"));
+}
+
+#[test]
+fn has_mapping_includes_globs() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("my_crate::*::UserId", "UserId");
+
+    assert!(resolver.has_mapping(&parse_str("my_crate::v9::UserId").unwrap()));
+    assert!(!resolver.has_mapping(&parse_str("other::v9::UserId").unwrap()));
+}
+
+#[test]
+fn dynamic_storage_indexes_globs() {
+    let mut storage = DynamicStorage::new();
+    storage.insert("b::*::T".to_string(), "T".to_string());
+    storage.insert("a::**".to_string(), "A".to_string());
+    storage.insert("exact::T".to_string(), "T".to_string());
+    storage.insert("a::**".to_string(), "A2".to_string());
+
+    assert_eq!(
+        storage.glob_keys().collect::<Vec<_>>(),
+        vec!["a::**", "b::*::T"]
+    );
+
+    storage.remove("a::**");
+    assert_eq!(storage.glob_keys().collect::<Vec<_>>(), vec!["b::*::T"]);
+
+    storage.clear();
+    assert_eq!(storage.glob_keys().count(), 0);
+}
+
+#[test]
+fn glob_keys_from_map() {
+    let map = [
+        ("x::*".to_string(), "X".to_string()),
+        ("y".to_string(), "Y".to_string()),
+    ]
+    .into_iter()
+    .collect::<std::collections::HashMap<_, _>>();
+
+    assert_eq!(map.glob_keys().collect::<Vec<_>>(), vec!["x::*"]);
+    assert_eq!(
+        DynamicStorage::from(map).glob_keys().collect::<Vec<_>>(),
+        vec!["x::*"]
+    );
+}
+
+#[test]
+fn globs_are_compiled_once_when_added() {
+    let mut storage = DynamicStorage::new();
+    storage.insert("b::*::T".to_string(), "T".to_string());
+    storage.insert("exact::T".to_string(), "T".to_string());
+
+    let patterns = storage.glob_patterns().unwrap();
+    assert_eq!(patterns, [GlobPattern::parse("b::*::T").unwrap()]);
+    assert!(patterns[0].matches("b::c::T"));
+
+    let map: BTreeMap<&str, &str> = [("a::**", "A"), ("b", "B")].into_iter().collect();
+    assert!(map.glob_patterns().is_none());
+    assert_eq!(
+        IndexedStorage::new(map).glob_patterns().unwrap(),
+        [GlobPattern::parse("a::**").unwrap()]
+    );
+}
+
+#[rstest]
+#[case("my_crate::v1::UserId", Some("UserId"))]
+#[case("my_crate::api::v2::UserId", Some("ApiUserId"))]
+#[case("my_crate::db::UserId", Some("DbUserId"))]
+#[case("other::v1::UserId", None)]
+fn storages_without_compiled_globs_resolve_alike(
+    #[case] input: &str,
+    #[case] expected: Option<&str>,
+) {
+    let map: BTreeMap<&str, &str> = [
+        ("my_crate::*::UserId", "UserId"),
+        ("my_crate::api::**::UserId", "ApiUserId"),
+        ("my_crate::{db,cache}::UserId", "DbUserId"),
+    ]
+    .into_iter()
+    .collect();
+    let path: Path = parse_str(input).unwrap();
+
+    let plain = PathResolver::new(&map, TypeGroups::NONE);
+    let indexed = PathResolver::new(IndexedStorage::new(&map), TypeGroups::NONE);
+
+    assert_eq!(plain.resolve(&path), expected);
+    assert_eq!(indexed.resolve(&path), expected);
+}
+
+#[test]
+fn globs_are_never_suffix_candidates() {
+    let map: BTreeMap<&str, &str> = [
+        ("my_crate::*::UserId", "UserId"),
+        ("api::UserId", "ApiUserId"),
+    ]
+    .into_iter()
+    .collect();
+    let mut dynamic = DynamicStorage::new();
+    for (pattern, canonical) in &map {
+        dynamic.insert(pattern.to_string(), canonical.to_string());
+    }
+    let indexed = IndexedStorage::new(&map);
+
+    assert_eq!(
+        map.keys_ending_with("UserId").collect::<Vec<_>>(),
+        ["api::UserId"]
+    );
+    assert_eq!(
+        dynamic.keys_ending_with("UserId").collect::<Vec<_>>(),
+        ["api::UserId"]
+    );
+    assert_eq!(
+        indexed.keys_ending_with("UserId").collect::<Vec<_>>(),
+        ["api::UserId"]
+    );
+
+    let path: Path = parse_str("UserId").unwrap();
+    let resolver = PathResolver::new(&map, TypeGroups::NONE);
+    let resolution = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(resolution.pattern, "api::UserId");
+    assert_eq!(resolution.strategy, Strategy::Suffix);
+
+    let candidates = resolver.resolve_candidates(&path);
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].pattern, "api::UserId");
+
+    let mut globs_only = DynamicPathResolver::default();
+    globs_only.add_mapping("my_crate::*::UserId", "UserId");
+    assert_eq!(globs_only.resolve(&path), None);
+    assert!(globs_only.resolve_candidates(&path).is_empty());
+}
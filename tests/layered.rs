@@ -0,0 +1,133 @@
+#![cfg(test)]
+
+use desynt::{DynamicPathResolver, LayeredResolver, PathResolver, Strategy, TypeGroups};
+use phf::{Map, phf_map};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+static BASELINE: Map<&'static str, &'static str> = phf_map! {
+    "sqlx::Pool" => "DbPool",
+    "my_crate::types::UserId" => "UserId",
+    "my_crate::types::OrderId" => "OrderId",
+    "UserRecord" => "UserRecord",
+};
+
+#[rstest]
+#[case::override_wins("my_crate::types::UserId", Some(("Identifier", "overrides", 0)))]
+#[case::falls_through("my_crate::types::OrderId", Some(("OrderId", "baseline", 1)))]
+#[case::baseline_only("sqlx::Pool", Some(("DbPool", "baseline", 1)))]
+#[case::baseline_groups("std::option::Option", Some(("Option", "baseline", 1)))]
+#[case::exact_beats_earlier_heuristic("UserRecord", Some(("UserRecord", "baseline", 1)))]
+#[case::override_only("legacy::UserRecord", Some(("LegacyRecord", "overrides", 0)))]
+#[case::miss("my_crate::types::Unknown", None)]
+fn reports_answering_layer(#[case] input: &str, #[case] expected: Option<(&str, &str, usize)>) {
+    let mut overrides = DynamicPathResolver::default();
+    overrides.add_mapping("my_crate::types::UserId", "Identifier");
+    overrides.add_mapping("legacy::UserRecord", "LegacyRecord");
+    let resolver = LayeredResolver::new()
+        .with_layer("overrides", overrides)
+        .with_layer("baseline", PathResolver::new(&BASELINE, TypeGroups::ALL));
+    let path: Path = parse_str(input).unwrap();
+
+    let found = resolver.resolve_detailed(&path);
+    let found = found
+        .as_ref()
+        .map(|found| (found.resolution.canonical, found.layer, found.index));
+    assert_eq!(found, expected);
+    assert_eq!(
+        resolver.resolve(&path),
+        expected.map(|(canonical, _, _)| canonical)
+    );
+}
+
+#[test]
+fn heuristic_falls_back_to_first_layer() {
+    let mut first = DynamicPathResolver::default();
+    first.add_mapping("first::Widget", "FirstWidget");
+    let mut second = DynamicPathResolver::default();
+    second.add_mapping("second::Widget", "SecondWidget");
+
+    let resolver = LayeredResolver::new()
+        .with_layer("first", first)
+        .with_layer("second", second);
+
+    let path: Path = parse_str("Widget").unwrap();
+    let found = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(found.layer, "first");
+    assert_eq!(found.resolution.canonical, "FirstWidget");
+    assert_eq!(found.resolution.strategy, Strategy::Suffix);
+}
+
+#[rstest]
+#[case::glob("my_crate::v1::Widget", Strategy::Glob)]
+#[case::matcher("widgets::Custom", Strategy::Matcher)]
+fn user_rules_beat_later_exact_matches(#[case] input: &str, #[case] strategy: Strategy) {
+    let mut first = DynamicPathResolver::default();
+    first.add_mapping("my_crate::*::Widget", "FirstWidget");
+    first.add_matcher("widgets", |segments: &[&str]| match segments {
+        ["widgets", ..] => Some("FirstWidget"),
+        _ => None,
+    });
+    let mut second = DynamicPathResolver::default();
+    second.add_mapping(input, "SecondWidget");
+
+    let resolver = LayeredResolver::new()
+        .with_layer("first", first)
+        .with_layer("second", second);
+
+    let path: Path = parse_str(input).unwrap();
+    let found = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(found.layer, "first");
+    assert_eq!(found.resolution.canonical, "FirstWidget");
+    assert_eq!(found.resolution.strategy, strategy);
+}
+
+#[rstest]
+#[case::primitive("u8", Some("primitives"))]
+#[case::prelude("std::option::Option", Some("prelude"))]
+#[case::common_std("std::collections::HashMap", None)]
+fn layers_use_their_own_groups(#[case] input: &str, #[case] expected: Option<&str>) {
+    let resolver = LayeredResolver::new()
+        .with_layer("primitives", PathResolver::primitives_only())
+        .with_layer("prelude", PathResolver::new(&BASELINE, TypeGroups::PRELUDE));
+
+    let path: Path = parse_str(input).unwrap();
+    let found = resolver.resolve_detailed(&path);
+    assert_eq!(found.map(|found| found.layer), expected);
+}
+
+#[test]
+fn has_mapping_checks_every_layer() {
+    let mut overrides = DynamicPathResolver::default();
+    overrides.add_mapping("my_crate::types::UserId", "Identifier");
+    let resolver = LayeredResolver::new()
+        .with_layer("overrides", overrides)
+        .with_layer("baseline", PathResolver::new(&BASELINE, TypeGroups::ALL));
+    assert!(resolver.has_mapping(&parse_str("my_crate::types::UserId").unwrap()));
+    assert!(resolver.has_mapping(&parse_str("sqlx::Pool").unwrap()));
+    assert!(!resolver.has_mapping(&parse_str("my_crate::types::Unknown").unwrap()));
+}
+
+#[test]
+fn layers_in_precedence_order() {
+    let mut resolver = LayeredResolver::new()
+        .with_layer("overrides", DynamicPathResolver::default())
+        .with_layer("baseline", PathResolver::new(&BASELINE, TypeGroups::ALL));
+    resolver.push_layer("fallback", PathResolver::all_groups());
+    assert_eq!(resolver.len(), 3);
+    assert_eq!(
+        resolver.layer_names().collect::<Vec<_>>(),
+        ["overrides", "baseline", "fallback"]
+    );
+    assert_eq!(
+        format!("{:?}", resolver),
+        r#"LayeredResolver { layers: ["overrides", "baseline", "fallback"] }"#
+    );
+}
+
+#[test]
+fn empty_resolves_nothing() {
+    let resolver = LayeredResolver::new();
+    assert!(resolver.is_empty());
+    assert_eq!(resolver.resolve(&parse_str("u8").unwrap()), None);
+}
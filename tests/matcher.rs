@@ -0,0 +1,157 @@
+#![cfg(test)]
+
+use desynt::{
+    DynamicPathResolver, DynamicStorage, MappingStorage, NamedMatcher, PredicateMatcher, Scope,
+    Strategy,
+};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+/// Map `Id` types under `my_crate::model` to `Identifier`.
+fn model_id(segments: &[&str]) -> Option<&'static str> {
+    match segments {
+        ["my_crate", "model", .., name] if name.ends_with("Id") => Some("Identifier"),
+        _ => None,
+    }
+}
+
+/// Match `Request` types of `*_proto` packages.
+fn proto_request(segments: &[&str]) -> bool {
+    match segments {
+        [package, .., name] => package.ends_with("_proto") && name.ends_with("Request"),
+        _ => false,
+    }
+}
+
+#[rstest]
+#[case::closure("my_crate::model::UserId", Some("Identifier"))]
+#[case::closure_nested("my_crate::model::billing::AccountId", Some("Identifier"))]
+#[case::closure_raw("r#my_crate::model::r#OrderId", Some("Identifier"))]
+#[case::closure_generic_arguments("my_crate::model::UserId<u8>", Some("Identifier"))]
+#[case::closure_other_module("my_crate::api::UserId", None)]
+#[case::exact_wins("my_crate::model::LegacyId", Some("LegacyId"))]
+#[case::predicate("users_proto::GetUserRequest", Some("Request"))]
+#[case::predicate_nested("users_proto::v1::ListUsersRequest", Some("Request"))]
+#[case::predicate_response("users_proto::GetUserResponse", None)]
+#[case::builtin_unaffected("std::option::Option", Some("Option"))]
+fn resolve_with_matchers(#[case] input: &str, #[case] expected: Option<&str>) {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_matcher("model ids", model_id);
+    resolver.add_matcher(
+        "proto requests",
+        PredicateMatcher::new("Request", proto_request),
+    );
+    resolver.add_mapping("my_crate::model::LegacyId", "LegacyId");
+
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver.resolve(&path), expected);
+}
+
+#[test]
+fn resolution_reports_matcher() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_matcher(
+        "proto requests",
+        PredicateMatcher::new("Request", proto_request),
+    );
+    let path: Path = parse_str("users_proto::GetUserRequest").unwrap();
+    let resolution = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(resolution.canonical, "Request");
+    assert_eq!(resolution.pattern, "proto requests");
+    assert_eq!(resolution.strategy, Strategy::Matcher);
+    assert!(!resolution.is_exact());
+    assert!(!resolution.is_heuristic());
+    assert!(resolver.has_mapping(&path));
+}
+
+#[test]
+fn glob_keys_before_matchers() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_matcher("model ids", model_id);
+    resolver.add_mapping("my_crate::model::*", "Model");
+
+    let path: Path = parse_str("my_crate::model::UserId").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("Model"));
+}
+
+#[test]
+fn matchers_before_heuristics() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("other::UserId", "OtherUserId");
+    resolver.add_matcher("bare ids", |segments: &[&str]| match segments {
+        [name] if name.ends_with("Id") => Some("BareIdentifier"),
+        _ => None,
+    });
+
+    let path: Path = parse_str("UserId").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("BareIdentifier"));
+}
+
+#[test]
+fn first_matcher_wins() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_matcher(
+        "proto requests",
+        PredicateMatcher::new("Request", proto_request),
+    );
+    resolver.add_matcher("any request", |segments: &[&str]| {
+        segments
+            .last()
+            .filter(|name| name.ends_with("Request"))
+            .map(|_| "AnyRequest")
+    });
+
+    let path: Path = parse_str("users_proto::GetUserRequest").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("Request"));
+
+    assert!(resolver.remove_matcher("proto requests"));
+    assert!(!resolver.remove_matcher("proto requests"));
+    assert_eq!(resolver.resolve(&path), Some("AnyRequest"));
+}
+
+#[test]
+fn replace_matcher_in_place() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_matcher("model ids", model_id);
+    resolver.add_matcher(
+        "proto requests",
+        PredicateMatcher::new("Request", proto_request),
+    );
+    resolver.add_matcher("model ids", |_: &[&str]| Some("Replaced"));
+
+    let path: Path = parse_str("users_proto::GetUserRequest").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("Replaced"));
+}
+
+#[test]
+fn matchers_in_scope() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_matcher("model ids", model_id);
+    let scope = Scope::from_file(&parse_str("use my_crate::model::UserId;").unwrap());
+
+    let path: Path = parse_str("UserId").unwrap();
+    assert_eq!(resolver.resolve_in_scope(&path, &scope), Some("Identifier"));
+}
+
+#[test]
+fn clear_removes_matchers() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_matcher("model ids", model_id);
+    resolver.add_mapping("my_crate::model::LegacyId", "LegacyId");
+    resolver.clear();
+
+    let path: Path = parse_str("my_crate::model::UserId").unwrap();
+    assert_eq!(resolver.resolve(&path), None);
+    assert_eq!(resolver.path_patterns().count(), 0);
+}
+
+#[test]
+fn storage_lists_matchers() {
+    let mut storage = DynamicStorage::new();
+    assert_eq!(storage.matchers().count(), 0);
+
+    storage.add_matcher(NamedMatcher::new("ids", |_: &[&str]| Some("Id")));
+    let names: Vec<&str> = storage.matchers().map(|(name, _)| name).collect();
+    assert_eq!(names, ["ids"]);
+    assert_eq!(storage.clone(), storage);
+}
@@ -0,0 +1,94 @@
+#![cfg(test)]
+
+use desynt::{DynamicPathResolver, EmptyStorage, PathResolver, TypeGroup, TypeGroups};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+const NUMERIC: TypeGroups = TypeGroups::PRIMITIVES.with_group(TypeGroup::Numeric);
+
+const MARKERS: TypeGroups = TypeGroups::NONE.with_group(TypeGroup::Markers);
+
+fn resolve(groups: TypeGroups, input: &str) -> Option<String> {
+    let resolver = PathResolver::new(EmptyStorage, groups);
+    let path: Path = parse_str(input).unwrap();
+    resolver.resolve(&path).map(str::to_string)
+}
+
+#[rstest]
+#[case::non_zero_u8("std::num::NonZeroU8", "NonZeroU8")]
+#[case::non_zero_u128("core::num::NonZeroU128", "NonZeroU128")]
+#[case::non_zero_usize("std::num::NonZeroUsize", "NonZeroUsize")]
+#[case::non_zero_i8("core::num::NonZeroI8", "NonZeroI8")]
+#[case::non_zero_i64("std::num::NonZeroI64", "NonZeroI64")]
+#[case::non_zero_isize("std::num::NonZeroIsize", "NonZeroIsize")]
+#[case::non_zero_generic("std::num::NonZero<u32>", "NonZero")]
+#[case::wrapping("core::num::Wrapping<u8>", "Wrapping")]
+#[case::saturating("std::num::Saturating<i16>", "Saturating")]
+#[case::bare("NonZeroU32", "NonZeroU32")]
+#[case::module("num::Wrapping", "Wrapping")]
+#[case::primitive("u8", "u8")]
+fn numeric_wrappers(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(resolve(NUMERIC, input).as_deref(), Some(expected));
+    assert_eq!(resolve(TypeGroups::ALL, input).as_deref(), Some(expected));
+}
+
+#[rstest]
+#[case::phantom_data("std::marker::PhantomData<T>", "PhantomData")]
+#[case::core_phantom_data("core::marker::PhantomData", "PhantomData")]
+#[case::phantom_pinned("core::marker::PhantomPinned", "PhantomPinned")]
+#[case::pin("std::pin::Pin<Box<T>>", "Pin")]
+#[case::core_pin("core::pin::Pin", "Pin")]
+#[case::manually_drop("std::mem::ManuallyDrop", "ManuallyDrop")]
+#[case::core_manually_drop("core::mem::ManuallyDrop", "ManuallyDrop")]
+#[case::bare("PhantomData", "PhantomData")]
+#[case::module("pin::Pin", "Pin")]
+fn markers(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(resolve(MARKERS, input).as_deref(), Some(expected));
+    assert_eq!(resolve(TypeGroups::ALL, input).as_deref(), Some(expected));
+}
+
+#[rstest]
+#[case::numeric("std::num::NonZeroU8", TypeGroup::Numeric)]
+#[case::markers("std::marker::PhantomData", TypeGroup::Markers)]
+fn groups_are_separate(#[case] input: &str, #[case] group: TypeGroup) {
+    let all_but = TypeGroups::ALL.without_group(group);
+    assert_eq!(resolve(all_but, input), None);
+    assert_eq!(resolve(TypeGroups::PRELUDE, input), None);
+
+    let resolver = PathResolver::new(EmptyStorage, TypeGroups::NONE.with_group(group));
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver.resolve_detailed(&path).unwrap().group, Some(group));
+}
+
+#[test]
+fn with_group_and_contains() {
+    assert!(NUMERIC.contains(TypeGroup::Primitives));
+    assert!(NUMERIC.contains(TypeGroup::Numeric));
+    assert!(!NUMERIC.contains(TypeGroup::Markers));
+    let mut expected = TypeGroups::PRIMITIVES;
+    expected.numeric = true;
+    assert_eq!(NUMERIC, expected);
+    assert_eq!(
+        NUMERIC.without_group(TypeGroup::Numeric),
+        TypeGroups::PRIMITIVES
+    );
+    assert!(
+        TypeGroups::NONE
+            .without_group(TypeGroup::Markers)
+            .is_empty()
+    );
+    assert!(!MARKERS.is_empty());
+}
+
+#[test]
+fn uses_groups() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    assert!(resolver.uses_numeric());
+    assert!(resolver.uses_markers());
+
+    resolver.set_groups(TypeGroups::ALL.without_group(TypeGroup::Markers));
+    assert!(resolver.uses_numeric());
+    assert!(!resolver.uses_markers());
+    assert!(!resolver.canonical_types().any(|name| name == "PhantomData"));
+    assert!(resolver.canonical_types().any(|name| name == "NonZeroU8"));
+}
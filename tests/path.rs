@@ -41,3 +41,15 @@ fn raw_identifier_in_module() {
     let result = resolver.resolve(&path);
     assert_eq!(result, Some("CustomType"));
 }
+
+#[test]
+fn strip_raw_generic_arguments() {
+    let raw_path: Path = parse_str("Option<r#my::r#Type<r#u8>>").unwrap();
+    let stripped = raw_path.strip_raw();
+
+    assert!(raw_path.has_raw());
+    assert!(!stripped.has_raw());
+
+    let expected: Path = parse_str("Option<my::Type<u8>>").unwrap();
+    assert_eq!(stripped, expected);
+}
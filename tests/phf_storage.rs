@@ -142,3 +142,25 @@ fn const_creation() {
     assert_eq!(RESOLVER1.resolve(&path), Some("Type1"));
     assert_eq!(RESOLVER2.resolve(&path), Some("Type1"));
 }
+
+#[test]
+fn glob_keys() {
+    const GLOB_MAPPINGS: Map<&'static str, &'static str> = phf_map! {
+        "my_crate::*::UserId" => "UserId",
+        "my_crate::**::AccountId" => "AccountId",
+        "my_crate::{v1,v2}::Request" => "Request",
+        "my_crate::v1::AccountId" => "AccountIdV1",
+    };
+    const RESOLVER: StaticPathResolver = create_static_resolver(&GLOB_MAPPINGS, TypeGroups::NONE);
+
+    for (input, expected) in [
+        ("my_crate::v1::UserId", Some("UserId")),
+        ("my_crate::a::b::AccountId", Some("AccountId")),
+        ("my_crate::v1::AccountId", Some("AccountIdV1")),
+        ("my_crate::v2::Request", Some("Request")),
+        ("my_crate::v3::Request", None),
+    ] {
+        let path: Path = parse_str(input).unwrap();
+        assert_eq!(RESOLVER.resolve(&path), expected, "{input}");
+    }
+}
@@ -0,0 +1,154 @@
+#![cfg(test)]
+
+use desynt::{
+    DynamicPathResolver, EmptyStorage, PathResolver, ResolutionPolicy, Strategy, TypeGroups,
+    create_static_resolver,
+};
+use phf::{Map, phf_map};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+const MAPPINGS: Map<&'static str, &'static str> = phf_map! {
+    "my_crate::types::UserId" => "UserId",
+};
+
+const STRICT_RESOLVER: PathResolver<&'static Map<&'static str, &'static str>> =
+    create_static_resolver(&MAPPINGS, TypeGroups::ALL).with_policy(ResolutionPolicy::STRICT);
+
+fn resolver(policy: ResolutionPolicy) -> DynamicPathResolver {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+    resolver.add_mapping("api::Request", "Request");
+    resolver.set_policy(policy);
+    resolver
+}
+
+#[test]
+fn default_policy() {
+    let resolver = DynamicPathResolver::default();
+    assert_eq!(resolver.policy(), ResolutionPolicy::DEFAULT);
+    assert_eq!(ResolutionPolicy::default(), ResolutionPolicy::DEFAULT);
+}
+
+#[rstest]
+#[case::exact_custom("my_crate::types::UserId", Some("UserId"))]
+#[case::exact_builtin("std::option::Option<u8>", Some("Option"))]
+#[case::progressive("my_crate::api::Request", None)]
+#[case::suffix("UserId", None)]
+#[case::builtin_pattern("Option<u8>", None)]
+#[case::stdlib_shortening("collections::HashMap<K, V>", None)]
+fn exact(#[case] input: &str, #[case] expected: Option<&str>) {
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver(ResolutionPolicy::EXACT).resolve(&path), expected);
+}
+
+#[rstest]
+#[case::exact_custom("my_crate::types::UserId", Some("UserId"))]
+#[case::progressive("my_crate::api::Request", None)]
+#[case::suffix("UserId", None)]
+#[case::builtin_pattern("Option<u8>", Some("Option"))]
+#[case::primitive("u8", Some("u8"))]
+fn strict(#[case] input: &str, #[case] expected: Option<&str>) {
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver(ResolutionPolicy::STRICT).resolve(&path), expected);
+    assert_eq!(STRICT_RESOLVER.resolve(&path), expected);
+}
+
+#[rstest]
+#[case::progressive("my_crate::api::Request", Some("Request"))]
+#[case::suffix("UserId", Some("UserId"))]
+#[case::stdlib_shortening("collections::HashMap<K, V>", Some("HashMap"))]
+#[case::not_stdlib("other::UserId", None)]
+fn default(#[case] input: &str, #[case] expected: Option<&str>) {
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver(ResolutionPolicy::DEFAULT).resolve(&path), expected);
+}
+
+#[rstest]
+#[case::not_stdlib("other::UserId", Some("UserId"))]
+#[case::not_stdlib_builtin("other::Option<u8>", Some("Option"))]
+fn permissive(#[case] input: &str, #[case] expected: Option<&str>) {
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(
+        resolver(ResolutionPolicy::PERMISSIVE).resolve(&path),
+        expected
+    );
+}
+
+#[test]
+fn builders() {
+    const POLICY: ResolutionPolicy = ResolutionPolicy::EXACT
+        .with_progressive(true)
+        .with_suffix(true)
+        .with_builtin_patterns(true)
+        .with_stdlib_heuristic(true);
+    assert_eq!(POLICY, ResolutionPolicy::DEFAULT);
+    assert_eq!(
+        POLICY.with_unrestricted_heuristic(true),
+        ResolutionPolicy::PERMISSIVE
+    );
+    assert_eq!(
+        POLICY.with_min_matching_segments(3).min_matching_segments,
+        3
+    );
+}
+
+#[test]
+fn individual_strategies() {
+    let no_suffix = ResolutionPolicy::DEFAULT.with_suffix(false);
+    let no_progressive = ResolutionPolicy::DEFAULT.with_progressive(false);
+
+    let bare: Path = parse_str("UserId").unwrap();
+    let shortened: Path = parse_str("my_crate::api::Request").unwrap();
+    let builtin: Path = parse_str("Option<u8>").unwrap();
+
+    assert_eq!(resolver(no_suffix).resolve(&bare), None);
+    assert_eq!(resolver(no_suffix).resolve(&builtin), Some("Option"));
+    assert_eq!(resolver(no_suffix).resolve(&shortened), Some("Request"));
+    assert_eq!(resolver(no_progressive).resolve(&shortened), None);
+    assert_eq!(resolver(no_progressive).resolve(&bare), Some("UserId"));
+}
+
+#[rstest]
+#[case::exact(ResolutionPolicy::EXACT, None)]
+#[case::strict(ResolutionPolicy::STRICT, None)]
+#[case::permissive_without_progressive(
+    ResolutionPolicy::PERMISSIVE.with_progressive(false),
+    Some(Strategy::Suffix)
+)]
+#[case::permissive(ResolutionPolicy::PERMISSIVE, Some(Strategy::Progressive))]
+fn bare_mapping_of_longer_path(
+    #[case] policy: ResolutionPolicy,
+    #[case] expected: Option<Strategy>,
+) {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("Baz", "Baz");
+    resolver.set_policy(policy);
+
+    let path: Path = parse_str("foo::bar::Baz").unwrap();
+    let resolution = resolver.resolve_detailed(&path);
+    assert_eq!(resolution.map(|resolution| resolution.strategy), expected);
+}
+
+#[rstest]
+#[case::exact("my_crate::types::UserId", Some("UserId"))]
+#[case::two_segments("my_crate::api::Request", Some("Request"))]
+#[case::one_segment("UserId", None)]
+#[case::shortened_to_one("other::UserId", None)]
+fn min_matching_segments(#[case] input: &str, #[case] expected: Option<&str>) {
+    let policy = ResolutionPolicy::PERMISSIVE.with_min_matching_segments(2);
+
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver(policy).resolve(&path), expected);
+}
+
+#[test]
+fn const_with_policy() {
+    const RESOLVER: PathResolver<EmptyStorage> =
+        PathResolver::all_groups().with_policy(ResolutionPolicy::EXACT);
+
+    assert_eq!(RESOLVER.policy(), ResolutionPolicy::EXACT);
+
+    let path: Path = parse_str("Option<u8>").unwrap();
+    assert_eq!(RESOLVER.resolve(&path), None);
+}
@@ -0,0 +1,254 @@
+#![cfg(test)]
+
+use desynt::{CanonicalPath, CanonicalType, DynamicPathResolver, ProjectionError, Scope, Strategy};
+use rstest::rstest;
+use syn::{ItemImpl, Path, Type, TypePath, parse_str};
+
+fn resolver() -> DynamicPathResolver {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver
+        .add_projection("<_ as frame_system::Config>::AccountId", "AccountId")
+        .unwrap();
+    resolver
+        .add_projection(
+            "<Runtime as frame_system::Config>::AccountId",
+            "RuntimeAccountId",
+        )
+        .unwrap();
+    resolver
+        .add_projection("<_ as pallet_balances::Config>::Balance", "Balance")
+        .unwrap();
+    resolver
+        .add_projection("<Vec<T> as r#IntoIterator>::Item", "VecItem")
+        .unwrap();
+    resolver.add_projection("<Counter>::Output", "u64").unwrap();
+    resolver.add_mapping("my_crate::Pallet", "Pallet");
+    resolver
+}
+
+fn pallet_scope() -> Scope {
+    let item: ItemImpl = parse_str(
+        "impl<T: frame_system::Config, U> Iterator for Pallet<T> \
+         where U: pallet_balances::Config { type Item = T::AccountId; }",
+    )
+    .unwrap();
+    Scope::new().with_impl(&item)
+}
+
+#[rstest]
+#[case::wildcard_type("<T as frame_system::Config>::AccountId", Ok("AccountId"))]
+#[case::exact_type("<Runtime as frame_system::Config>::AccountId", Ok("RuntimeAccountId"))]
+#[case::bare_trait("<T as Config>::Balance", Ok("Balance"))]
+#[case::generic_arguments_ignored("<Vec<u8> as IntoIterator>::Item", Ok("VecItem"))]
+#[case::inherent("<Counter>::Output", Ok("u64"))]
+#[case::unknown_assoc(
+    "<T as frame_system::Config>::Hash",
+    Err("<T as frame_system::Config>::Hash")
+)]
+#[case::other_trait(
+    "<T as other::Config>::AccountId",
+    Err("<T as other::Config>::AccountId")
+)]
+#[case::not_a_projection("T::AccountId", Err("T::AccountId"))]
+fn resolve_projection(#[case] input: &str, #[case] expected: Result<&str, &str>) {
+    let ty: TypePath = parse_str(input).unwrap();
+    let expected = expected.map_err(|projection| ProjectionError {
+        projection: projection.to_string(),
+    });
+    assert_eq!(resolver().resolve_projection(&ty), expected);
+}
+
+#[rstest]
+#[case::self_assoc("Self::AccountId", Ok("AccountId"))]
+#[case::self_qself("<Self as frame_system::Config>::AccountId", Ok("AccountId"))]
+#[case::bound_param("T::AccountId", Ok("AccountId"))]
+#[case::where_clause_bound("U::Balance", Ok("Balance"))]
+#[case::unbounded_param("V::AccountId", Err("V::AccountId"))]
+#[case::wrong_bound("T::Balance", Err("<T as frame_system::Config>::Balance"))]
+fn resolve_projection_in_scope(#[case] input: &str, #[case] expected: Result<&str, &str>) {
+    let mut resolver = resolver();
+    resolver
+        .add_projection("<Pallet as Iterator>::AccountId", "AccountId")
+        .unwrap();
+
+    let ty: TypePath = parse_str(input).unwrap();
+    let expected = expected.map_err(|projection| ProjectionError {
+        projection: projection.to_string(),
+    });
+    assert_eq!(
+        resolver.resolve_projection_in_scope(&ty, &pallet_scope()),
+        expected
+    );
+}
+
+#[test]
+fn scope_records_impl() {
+    let scope = pallet_scope();
+
+    assert_eq!(
+        scope.self_type(),
+        Some(&parse_str::<Type>("Pallet<T>").unwrap())
+    );
+    assert_eq!(
+        scope.self_trait(),
+        Some(&parse_str::<Path>("Iterator").unwrap())
+    );
+    assert_eq!(
+        scope.bounds("T"),
+        &[parse_str::<Path>("frame_system::Config").unwrap()]
+    );
+    assert_eq!(
+        scope.bounds("U"),
+        &[parse_str::<Path>("pallet_balances::Config").unwrap()]
+    );
+    assert!(scope.bounds("V").is_empty());
+}
+
+#[test]
+fn resolve_in_scope_uses_impl_context() {
+    let resolver = resolver();
+    let scope = pallet_scope().with_module("my_crate").unwrap();
+
+    let path: Path = parse_str("Self").unwrap();
+    assert_eq!(resolver.resolve_in_scope(&path, &scope), Some("Pallet"));
+
+    let path: Path = parse_str("T::AccountId").unwrap();
+    let resolution = resolver.resolve_detailed_in_scope(&path, &scope).unwrap();
+    assert_eq!(resolution.canonical, "AccountId");
+    assert_eq!(resolution.pattern, "<_ as frame_system::Config>::AccountId");
+    assert_eq!(resolution.strategy, Strategy::ProjectionFallback);
+    assert!(resolution.is_heuristic());
+
+    // Self::Item is a projection of `Iterator`, which has no mapping here
+    let path: Path = parse_str("Self::Item").unwrap();
+    assert_eq!(resolver.resolve_in_scope(&path, &scope), None);
+}
+
+#[test]
+fn projection_keys_are_not_suffix_candidates() {
+    let path: Path = parse_str("AccountId").unwrap();
+    assert_eq!(resolver().resolve(&path), None);
+    assert!(resolver().resolve_candidates(&path).is_empty());
+}
+
+#[test]
+fn resolve_type_reports_unresolved_projections() {
+    let ty: Type =
+        parse_str("Vec<(<T as frame_system::Config>::AccountId, <T as Config>::Hash)>").unwrap();
+
+    let account_id = CanonicalType::Path(CanonicalPath {
+        name: "AccountId".to_string(),
+        resolved: true,
+        args: vec![],
+        bindings: vec![],
+    });
+    let hash = CanonicalType::Projection {
+        qself: Box::new(CanonicalType::Path(CanonicalPath {
+            name: "T".to_string(),
+            resolved: false,
+            args: vec![],
+            bindings: vec![],
+        })),
+        trait_path: Some(CanonicalPath {
+            name: "Config".to_string(),
+            resolved: false,
+            args: vec![],
+            bindings: vec![],
+        }),
+        assoc: "Hash".to_string(),
+    };
+    let CanonicalType::Path(vec) = resolver().resolve_type(&ty) else {
+        panic!("expected a path");
+    };
+    assert_eq!(vec.args, vec![CanonicalType::Tuple(vec![account_id, hash])]);
+}
+
+#[test]
+fn resolve_type_in_impl_scope() {
+    let ty: Type = parse_str("Option<Self>").unwrap();
+    let scope = pallet_scope();
+
+    let CanonicalType::Path(option) = resolver().resolve_type_in_scope(&ty, &scope) else {
+        panic!("expected a path");
+    };
+    let CanonicalType::Path(pallet) = &option.args[0] else {
+        panic!("expected a path");
+    };
+    assert_eq!(pallet.name, "Pallet");
+    assert_eq!(pallet.args.len(), 1);
+}
+
+#[test]
+fn add_projection_errors() {
+    let mut resolver = DynamicPathResolver::default();
+
+    assert!(
+        resolver
+            .add_projection("frame_system::Config", "AccountId")
+            .is_err()
+    );
+    assert!(resolver.add_projection("<_ as", "AccountId").is_err());
+    assert!(resolver.is_empty());
+    assert_eq!(
+        ProjectionError {
+            projection: "<T as Config>::Hash".to_string()
+        }
+        .to_string(),
+        "no mapping for projection `<T as Config>::Hash`"
+    );
+}
+
+#[rstest]
+#[case::exact_key(
+    "impl frame_system::Config for Runtime {}",
+    "Self::AccountId",
+    "RuntimeAccountId",
+    Strategy::Exact
+)]
+#[case::wildcard_key(
+    "impl frame_system::Config for Other {}",
+    "Self::AccountId",
+    "AccountId",
+    Strategy::ProjectionFallback
+)]
+#[case::wildcard_key_other_trait(
+    "impl pallet_balances::Config for Runtime {}",
+    "Self::Balance",
+    "Balance",
+    Strategy::ProjectionFallback
+)]
+fn projection_strategy(
+    #[case] item: &str,
+    #[case] input: &str,
+    #[case] canonical: &str,
+    #[case] strategy: Strategy,
+) {
+    let resolver = resolver();
+    let item: ItemImpl = parse_str(item).unwrap();
+    let scope = Scope::new().with_impl(&item);
+
+    let path: Path = parse_str(input).unwrap();
+    let resolution = resolver.resolve_detailed_in_scope(&path, &scope).unwrap();
+    assert_eq!(resolution.canonical, canonical);
+    assert_eq!(resolution.strategy, strategy);
+    assert_eq!(resolution.is_heuristic(), strategy != Strategy::Exact);
+}
+
+#[rstest]
+#[case::self_type("impl Iterator for Self { type Item = u8; }")]
+#[case::nested_self_type("impl Iterator for Box<Self> { type Item = u8; }")]
+fn self_referential_impls_have_no_self_type(#[case] item: &str) {
+    let resolver = DynamicPathResolver::with_all_groups();
+    let item: ItemImpl = parse_str(item).unwrap();
+    let scope = Scope::new().with_impl(&item);
+    assert_eq!(scope.self_type(), None);
+
+    let path: Path = parse_str("Self").unwrap();
+    assert_eq!(resolver.resolve_detailed_in_scope(&path, &scope), None);
+    let ty: Type = parse_str("Self").unwrap();
+    let CanonicalType::Path(self_path) = resolver.resolve_type_in_scope(&ty, &scope) else {
+        panic!("expected a path");
+    };
+    assert_eq!(self_path.name, "Self");
+    assert!(!self_path.resolved);
+}
@@ -1,6 +1,6 @@
 #![cfg(test)]
 
-use desynt::DynamicPathResolver;
+use desynt::{DynamicPathResolver, Strategy, TypeGroup};
 use rstest::rstest;
 use syn::Path;
 
@@ -154,3 +154,96 @@ fn complex_generic_types(#[case] input: &str, #[case] expected: Option<&str>) {
     println!("Testing: {} -> {:?}", input, result);
     assert_eq!(result, expected, "Failed for case: {}", input);
 }
+
+#[rstest]
+#[case::exact_custom(
+    "my::custom::Type",
+    "CustomType",
+    "my::custom::Type",
+    None,
+    Strategy::Exact
+)]
+#[case::exact_custom_generic(
+    "my::custom::Type<T>",
+    "CustomType",
+    "my::custom::Type",
+    None,
+    Strategy::Exact
+)]
+#[case::exact_builtin(
+    "std::vec::Vec<u8>",
+    "Vec",
+    "std::vec::Vec",
+    Some(TypeGroup::Prelude),
+    Strategy::Exact
+)]
+#[case::progressive_builtin("vec::Vec<u8>", "Vec", "Vec", None, Strategy::Progressive)]
+#[case::progressive_custom(
+    "other::custom::Type",
+    "CustomType",
+    "custom::Type",
+    None,
+    Strategy::Progressive
+)]
+#[case::suffix_custom("Type<T>", "CustomType", "custom::Type", None, Strategy::Suffix)]
+#[case::builtin_pattern(
+    "HashMap<K, V>",
+    "HashMap",
+    "std::collections::HashMap",
+    Some(TypeGroup::CommonStd),
+    Strategy::BuiltinPattern
+)]
+#[case::builtin_pattern_primitive(
+    "i32",
+    "i32",
+    "i32",
+    Some(TypeGroup::Primitives),
+    Strategy::BuiltinPattern
+)]
+#[case::builtin_pattern_primitive_raw(
+    "r#bool",
+    "bool",
+    "bool",
+    Some(TypeGroup::Primitives),
+    Strategy::BuiltinPattern
+)]
+fn resolve_detailed(
+    #[case] input: &str,
+    #[case] canonical: &str,
+    #[case] pattern: &str,
+    #[case] group: Option<TypeGroup>,
+    #[case] strategy: Strategy,
+) {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("my::custom::Type", "CustomType");
+    resolver.add_mapping("custom::Type", "CustomType");
+    resolver.add_mapping("Vec", "Vec");
+
+    let path: Path = syn::parse_str(input).unwrap();
+    let resolution = resolver.resolve_detailed(&path).unwrap();
+
+    assert_eq!(resolution.canonical, canonical, "Failed for: {}", input);
+    assert_eq!(resolution.pattern, pattern, "Failed for: {}", input);
+    assert_eq!(resolution.group, group, "Failed for: {}", input);
+    assert_eq!(resolution.strategy, strategy, "Failed for: {}", input);
+    assert_eq!(resolution.is_exact(), strategy == Strategy::Exact);
+    assert_eq!(
+        resolution.is_heuristic(),
+        matches!(
+            strategy,
+            Strategy::Progressive
+                | Strategy::Suffix
+                | Strategy::BuiltinPattern
+                | Strategy::ProjectionFallback
+        )
+    );
+    assert_eq!(resolution.is_builtin(), group.is_some());
+}
+
+#[test]
+fn resolve_detailed_unresolved() {
+    let resolver = DynamicPathResolver::with_all_groups();
+
+    let path: Path = syn::parse_str("unknown::Type").unwrap();
+    assert!(resolver.resolve_detailed(&path).is_none());
+}
@@ -0,0 +1,133 @@
+#![cfg(test)]
+
+use desynt::{CanonicalPath, CanonicalType, DynamicPathResolver};
+use rstest::rstest;
+use syn::{Type, parse_str};
+
+fn resolver() -> DynamicPathResolver {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("MyVec", "Vec");
+    resolver.add_mapping("my_crate::UserId", "UserId");
+    resolver
+}
+
+fn named(name: &str, args: Vec<CanonicalType>) -> CanonicalType {
+    CanonicalType::Path(CanonicalPath {
+        name: name.to_string(),
+        resolved: true,
+        args,
+        bindings: vec![],
+    })
+}
+
+#[test]
+fn reference_to_raw_alias_of_array() {
+    let ty: Type = parse_str("&'a r#MyVec<[u8; 4]>").unwrap();
+
+    let expected = CanonicalType::Reference {
+        mutable: false,
+        elem: Box::new(named(
+            "Vec",
+            vec![CanonicalType::Array {
+                elem: Box::new(named("u8", vec![])),
+                len: parse_str("4").unwrap(),
+            }],
+        )),
+    };
+    assert_eq!(resolver().resolve_type(&ty), expected);
+}
+
+#[test]
+fn nested_generic_arguments() {
+    let ty: Type = parse_str("Option<std::vec::Vec<my_crate::UserId>>").unwrap();
+
+    let expected = named("Option", vec![named("Vec", vec![named("UserId", vec![])])]);
+    assert_eq!(resolver().resolve_type(&ty), expected);
+}
+
+#[test]
+fn unresolved_path_keeps_normalized_name() {
+    let ty: Type = parse_str("r#other::Thing<String>").unwrap();
+
+    let expected = CanonicalType::Path(CanonicalPath {
+        name: "other::Thing".to_string(),
+        resolved: false,
+        args: vec![named("String", vec![])],
+        bindings: vec![],
+    });
+    assert_eq!(resolver().resolve_type(&ty), expected);
+}
+
+#[rstest]
+#[case::mut_reference("&mut String", CanonicalType::Reference { mutable: true, elem: Box::new(named("String", vec![])) })]
+#[case::const_ptr("*const u8", CanonicalType::Ptr { mutable: false, elem: Box::new(named("u8", vec![])) })]
+#[case::slice("[i32]", CanonicalType::Slice(Box::new(named("i32", vec![]))))]
+#[case::tuple("(bool, (char))", CanonicalType::Tuple(vec![named("bool", vec![]), named("char", vec![])]))]
+#[case::unit("()", CanonicalType::Tuple(vec![]))]
+#[case::never("!", CanonicalType::Never)]
+#[case::infer("_", CanonicalType::Infer)]
+fn structural_types(#[case] input: &str, #[case] expected: CanonicalType) {
+    let ty: Type = parse_str(input).unwrap();
+    assert_eq!(resolver().resolve_type(&ty), expected);
+}
+
+#[test]
+fn bare_fn() {
+    let with_output: Type = parse_str("fn(&str, MyVec<u8>) -> Option<bool>").unwrap();
+    let without_output: Type = parse_str("fn(u8)").unwrap();
+
+    assert_eq!(
+        resolver().resolve_type(&with_output),
+        CanonicalType::BareFn {
+            inputs: vec![
+                CanonicalType::Reference {
+                    mutable: false,
+                    elem: Box::new(named("str", vec![])),
+                },
+                named("Vec", vec![named("u8", vec![])]),
+            ],
+            output: Box::new(named("Option", vec![named("bool", vec![])])),
+        }
+    );
+    assert_eq!(
+        resolver().resolve_type(&without_output),
+        CanonicalType::BareFn {
+            inputs: vec![named("u8", vec![])],
+            output: Box::new(CanonicalType::Tuple(vec![])),
+        }
+    );
+}
+
+#[test]
+fn trait_bounds_drop_lifetimes() {
+    let object: Type = parse_str("Box<dyn std::fmt::Debug + Send + 'static>").unwrap();
+    let impl_trait: Type = parse_str("impl Into<String>").unwrap();
+
+    let debug = CanonicalPath {
+        name: "std::fmt::Debug".to_string(),
+        resolved: false,
+        args: vec![],
+        bindings: vec![],
+    };
+    let send = CanonicalPath {
+        name: "Send".to_string(),
+        resolved: false,
+        args: vec![],
+        bindings: vec![],
+    };
+    assert_eq!(
+        resolver().resolve_type(&object),
+        named("Box", vec![CanonicalType::TraitObject(vec![debug, send])])
+    );
+
+    let into = CanonicalPath {
+        name: "Into".to_string(),
+        resolved: false,
+        args: vec![named("String", vec![])],
+        bindings: vec![],
+    };
+    assert_eq!(
+        resolver().resolve_type(&impl_trait),
+        CanonicalType::ImplTrait(vec![into])
+    );
+}
@@ -0,0 +1,310 @@
+#![cfg(test)]
+
+use desynt::{CanonicalPath, CanonicalType, DynamicPathResolver, Scope, Strategy};
+use rstest::rstest;
+use syn::{File, Path, Type, parse_str};
+
+const SOURCE: &str = r#"
+    use std::collections::HashMap as Map;
+    use std::sync::{Arc, Mutex as Lock};
+    use my_crate::models::{self, r#User};
+    use my_crate::types::*;
+    use std::fmt::Write as _;
+
+    struct Ignored;
+"#;
+
+fn resolver() -> DynamicPathResolver {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("my_crate::models::User", "User");
+    resolver.add_mapping("my_crate::models::Account", "Account");
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+    resolver.add_mapping("std::sync::Mutex", "Mutex");
+    resolver
+}
+
+fn scope() -> Scope {
+    let file: File = parse_str(SOURCE).unwrap();
+    Scope::from_file(&file)
+}
+
+#[test]
+fn imports_are_recorded() {
+    let scope = scope();
+
+    assert_eq!(
+        scope.get("Map").as_deref(),
+        Some("std::collections::HashMap")
+    );
+    assert_eq!(scope.get("Arc").as_deref(), Some("std::sync::Arc"));
+    assert_eq!(scope.get("Lock").as_deref(), Some("std::sync::Mutex"));
+    assert_eq!(scope.get("models").as_deref(), Some("my_crate::models"));
+    assert_eq!(scope.get("User").as_deref(), Some("my_crate::models::User"));
+    assert_eq!(scope.get("Write"), None);
+    assert_eq!(scope.get("_"), None);
+    assert_eq!(scope.globs().collect::<Vec<_>>(), vec!["my_crate::types"]);
+}
+
+#[rstest]
+#[case::rename("Map<String, u32>", Some("HashMap"))]
+#[case::group("Arc<u8>", Some("Arc"))]
+#[case::group_rename("Lock<u8>", Some("Mutex"))]
+#[case::self_import("models::Account", Some("Account"))]
+#[case::raw_import("User", Some("User"))]
+#[case::glob("UserId", Some("UserId"))]
+#[case::not_imported("Option<u8>", Some("Option"))]
+#[case::absolute("::Map", None)]
+#[case::unknown("Other", None)]
+fn resolve_in_scope(#[case] input: &str, #[case] expected: Option<&str>) {
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver().resolve_in_scope(&path, &scope()), expected);
+}
+
+#[test]
+fn resolve_detailed_in_scope() {
+    let resolver = resolver();
+    let scope = scope();
+
+    let path: Path = parse_str("User").unwrap();
+    let resolution = resolver.resolve_detailed_in_scope(&path, &scope).unwrap();
+    assert_eq!(resolution.pattern, "my_crate::models::User");
+    assert_eq!(resolution.strategy, Strategy::Exact);
+
+    let path: Path = parse_str("UserId").unwrap();
+    let resolution = resolver.resolve_detailed_in_scope(&path, &scope).unwrap();
+    assert_eq!(resolution.pattern, "my_crate::types::UserId");
+    assert_eq!(resolution.strategy, Strategy::Exact);
+}
+
+#[test]
+fn import_shadows_plain_lookup() {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping("other::Thing", "OtherThing");
+    resolver.add_mapping("mine::Thing", "MyThing");
+
+    let mut scope = Scope::new();
+    scope.add_import("Thing", "::mine::Thing").unwrap();
+
+    let path: Path = parse_str("Thing").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("MyThing"));
+    assert_eq!(resolver.resolve_in_scope(&path, &scope), Some("MyThing"));
+
+    scope.add_import("Thing", "other::Thing").unwrap();
+    assert_eq!(resolver.resolve_in_scope(&path, &scope), Some("OtherThing"));
+}
+
+#[test]
+fn glob_only_matches_exactly() {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping("api::Request", "Request");
+
+    let mut scope = Scope::new();
+    scope.add_glob("my_crate::types").unwrap();
+
+    let path: Path = parse_str("Request").unwrap();
+    let resolution = resolver.resolve_detailed_in_scope(&path, &scope).unwrap();
+    assert_eq!(resolution.pattern, "api::Request");
+    assert_eq!(resolution.strategy, Strategy::Suffix);
+}
+
+#[test]
+fn resolve_type_in_scope() {
+    let ty: Type = parse_str("&Map<UserId, Vec<Lock<User>>>").unwrap();
+
+    let named = |name: &str, args| {
+        CanonicalType::Path(CanonicalPath {
+            name: name.to_string(),
+            resolved: true,
+            args,
+            bindings: vec![],
+        })
+    };
+    let expected = CanonicalType::Reference {
+        mutable: false,
+        elem: Box::new(named(
+            "HashMap",
+            vec![
+                named("UserId", vec![]),
+                named("Vec", vec![named("Mutex", vec![named("User", vec![])])]),
+            ],
+        )),
+    };
+    assert_eq!(resolver().resolve_type_in_scope(&ty, &scope()), expected);
+}
+
+#[test]
+fn empty_scope() {
+    let scope = Scope::new();
+    assert!(scope.is_empty());
+
+    let path: Path = parse_str("std::option::Option<u8>").unwrap();
+    assert_eq!(resolver().resolve_in_scope(&path, &scope), Some("Option"));
+}
+
+#[rstest]
+#[case::crate_prefix("crate::types::UserId", Some("my_crate::types::UserId"))]
+#[case::self_prefix("self::Request", Some("my_crate::api::v1::Request"))]
+#[case::super_prefix("super::Request", Some("my_crate::api::Request"))]
+#[case::super_chain("self::super::super::types::UserId", Some("my_crate::types::UserId"))]
+#[case::self_alone("self", Some("my_crate::api::v1"))]
+#[case::above_root("super::super::super::Thing", None)]
+#[case::not_relative("types::UserId", None)]
+#[case::absolute("::crate::types::UserId", None)]
+fn absolutize(#[case] input: &str, #[case] expected: Option<&str>) {
+    let scope = Scope::new().with_module("my_crate::api::v1").unwrap();
+
+    let path: Path = parse_str(input).unwrap();
+    let expected = expected.map(|expected| parse_str::<Path>(expected).unwrap());
+    assert_eq!(scope.absolutize(&path), expected);
+}
+
+#[test]
+fn absolutize_keeps_generic_arguments() {
+    let scope = Scope::new().with_module("r#my_crate::api").unwrap();
+
+    let path: Path = parse_str("super::Wrapper<crate::Inner>").unwrap();
+    let expected: Path = parse_str("my_crate::Wrapper<crate::Inner>").unwrap();
+    assert_eq!(scope.absolutize(&path), Some(expected));
+    assert_eq!(scope.crate_name(), Some("my_crate"));
+    assert_eq!(scope.module().as_deref(), Some("my_crate::api"));
+}
+
+#[rstest]
+#[case::crate_root("my_crate::types", "crate::types::UserId")]
+#[case::self_module("my_crate::types", "self::UserId")]
+#[case::super_module("my_crate::api", "super::types::UserId")]
+fn relative_paths_need_a_scope(#[case] module: &str, #[case] input: &str) {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+    let scope = Scope::new().with_module(module).unwrap();
+
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver.resolve(&path), None);
+    assert_eq!(resolver.resolve_in_scope(&path, &scope), Some("UserId"));
+}
+
+#[test]
+fn relative_paths_from_many_modules() {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+    resolver.add_mapping("other_crate::types::UserId", "OtherUserId");
+
+    for (module, input) in [
+        ("my_crate", "crate::types::UserId"),
+        ("my_crate", "self::types::UserId"),
+        ("my_crate::types", "self::UserId"),
+        ("my_crate::api", "super::types::UserId"),
+        ("my_crate::api::v1", "super::super::types::UserId"),
+        ("my_crate::api::v1", "crate::types::UserId"),
+    ] {
+        let scope = Scope::new().with_module(module).unwrap();
+        let path: Path = parse_str(input).unwrap();
+        let resolution = resolver.resolve_detailed_in_scope(&path, &scope).unwrap();
+        assert_eq!(resolution.canonical, "UserId", "{input} in {module}");
+        assert_eq!(resolution.strategy, Strategy::Exact, "{input} in {module}");
+    }
+}
+
+#[test]
+fn relative_imports() {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+    resolver.add_mapping("my_crate::api::Request", "Request");
+
+    let file: File = parse_str(
+        r#"
+        use crate::types::UserId as Id;
+        use super::*;
+        "#,
+    )
+    .unwrap();
+    let scope = Scope::from_file(&file)
+        .with_module("my_crate::api::v1")
+        .unwrap();
+
+    let path: Path = parse_str("Id").unwrap();
+    let resolution = resolver.resolve_detailed_in_scope(&path, &scope).unwrap();
+    assert_eq!(resolution.pattern, "my_crate::types::UserId");
+
+    let path: Path = parse_str("Request").unwrap();
+    let resolution = resolver.resolve_detailed_in_scope(&path, &scope).unwrap();
+    assert_eq!(resolution.pattern, "my_crate::api::Request");
+    assert_eq!(resolution.strategy, Strategy::Exact);
+}
+
+#[test]
+fn relative_paths_without_module() {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+
+    let path: Path = parse_str("crate::types::UserId").unwrap();
+    assert_eq!(resolver.resolve_in_scope(&path, &Scope::new()), None);
+    assert_eq!(resolver.resolve(&path), None);
+}
+
+#[rstest]
+#[case::dash("my-crate")]
+#[case::generics("my_crate::Wrapper<T>")]
+#[case::number("my_crate::1api")]
+#[case::space("my crate::api")]
+#[case::empty_segment("my_crate::::api")]
+#[case::trailing_separator("my_crate::api::")]
+fn invalid_module_paths_are_rejected(#[case] module: &str) {
+    assert!(Scope::new().with_module(module).is_err());
+
+    let mut scope = Scope::new().with_module("my_crate").unwrap();
+    assert!(scope.set_module(module).is_err());
+    assert_eq!(scope.module().as_deref(), Some("my_crate"));
+
+    assert!(scope.add_glob(module).is_err());
+    assert!(scope.add_import("Thing", module).is_err());
+    assert_eq!(scope.globs().count(), 0);
+    assert_eq!(scope.get("Thing"), None);
+}
+
+#[rstest]
+#[case::keyword("r#type::Thing", "type::Thing")]
+#[case::leading_colon("::my_crate::Thing", "my_crate::Thing")]
+fn import_targets_are_normalized(#[case] target: &str, #[case] expected: &str) {
+    let mut scope = Scope::new();
+    scope.add_import("Thing", target).unwrap();
+    assert_eq!(scope.get("Thing").as_deref(), Some(expected));
+
+    let path: Path = parse_str("Thing").unwrap();
+    let expanded = scope.expand(&path).unwrap();
+    let segments: Vec<String> = expanded
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    assert_eq!(segments.join("::"), expected);
+}
+
+#[rstest]
+#[case::path("my_crate::Thing")]
+#[case::generics("Thing<T>")]
+#[case::dash("my-thing")]
+fn invalid_import_names_are_rejected(#[case] name: &str) {
+    let mut scope = Scope::new();
+    assert!(scope.add_import(name, "my_crate::Thing").is_err());
+    assert!(scope.is_empty());
+}
+
+#[test]
+fn empty_module_path_has_no_module() {
+    let scope = Scope::new().with_module("").unwrap();
+    assert_eq!(scope.module(), None);
+    assert!(scope.is_empty());
+}
+
+#[test]
+fn invalid_segment_error_names_the_segment() {
+    let error = Scope::new().with_module("my_crate::::api").unwrap_err();
+    assert_eq!(error.to_string(), "expected an identifier, found ``");
+
+    let error = Scope::new().with_module("my-crate").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected an identifier, found `my-crate`"
+    );
+}
@@ -0,0 +1,130 @@
+#![cfg(test)]
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+use desynt::{
+    DynamicPathResolver, DynamicStorage, MappingStorage, PathResolver, SlicePathResolver,
+    SliceStorage, TypeGroups, create_slice_resolver,
+};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+const MAPPINGS: &[(&str, &str)] = &[
+    ("actix_web::HttpResponse", "HttpResponse"),
+    ("my_crate::*::Money", "Money"),
+    ("my_crate::types::UserId", "UserId"),
+    ("serde_json::Value", "JsonValue"),
+];
+
+const SLICE_RESOLVER: SlicePathResolver = create_slice_resolver(MAPPINGS, TypeGroups::ALL);
+
+fn resolve<M: MappingStorage>(storage: M, input: &str) -> Option<String> {
+    let resolver = PathResolver::new(storage, TypeGroups::ALL);
+    let path: Path = parse_str(input).unwrap();
+    resolver.resolve(&path).map(str::to_string)
+}
+
+#[rstest]
+#[case::exact("serde_json::Value", Some("JsonValue"))]
+#[case::raw("r#my_crate::types::r#UserId", Some("UserId"))]
+#[case::suffix("UserId", Some("UserId"))]
+#[case::glob("my_crate::billing::Money", Some("Money"))]
+#[case::builtin("std::option::Option<u8>", Some("Option"))]
+#[case::miss("my_crate::types::Unknown", None)]
+fn backends_resolve_alike(#[case] input: &str, #[case] expected: Option<&str>) {
+    let btree: BTreeMap<String, String> = MAPPINGS
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    let borrowed: HashMap<&'static str, &'static str> = MAPPINGS.iter().copied().collect();
+    let cow: HashMap<Cow<'static, str>, Cow<'static, str>> = MAPPINGS
+        .iter()
+        .map(|(key, value)| (Cow::Borrowed(*key), Cow::Owned(value.to_string())))
+        .collect();
+    let cow_btree: BTreeMap<Cow<'static, str>, &'static str> = MAPPINGS
+        .iter()
+        .map(|(key, value)| (Cow::Borrowed(*key), *value))
+        .collect();
+
+    assert_eq!(resolve(btree, input).as_deref(), expected);
+    assert_eq!(resolve(borrowed, input).as_deref(), expected);
+    assert_eq!(resolve(cow, input).as_deref(), expected);
+    assert_eq!(resolve(cow_btree, input).as_deref(), expected);
+    assert_eq!(
+        resolve(SliceStorage::new(MAPPINGS), input).as_deref(),
+        expected
+    );
+
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(SLICE_RESOLVER.resolve(&path), expected);
+}
+
+#[test]
+fn btree_keys_are_ordered() {
+    let mut btree = BTreeMap::new();
+    for (key, value) in MAPPINGS.iter().rev() {
+        btree.insert(key.to_string(), value.to_string());
+    }
+    let resolver = PathResolver::new(btree, TypeGroups::NONE);
+
+    let expected: Vec<&str> = MAPPINGS.iter().map(|(key, _)| *key).collect();
+    assert_eq!(resolver.path_patterns().collect::<Vec<_>>(), expected);
+    assert_eq!(
+        resolver.canonical_types().collect::<Vec<_>>(),
+        ["HttpResponse", "Money", "UserId", "JsonValue"]
+    );
+}
+
+#[test]
+fn const_slice_resolver() {
+    const RESOLVER: PathResolver<SliceStorage> = PathResolver::new(
+        SliceStorage::new(&[("a::A", "A"), ("a::B", "B"), ("b::A", "OtherA")]),
+        TypeGroups::NONE,
+    );
+
+    assert_eq!(RESOLVER.len(), 3);
+    assert!(RESOLVER.has_mapping(&parse_str("a::B").unwrap()));
+    assert!(!RESOLVER.has_mapping(&parse_str("a::C").unwrap()));
+    assert_eq!(
+        RESOLVER.resolve(&parse_str("b::A").unwrap()),
+        Some("OtherA")
+    );
+}
+
+#[test]
+fn slice_storage_entries() {
+    let storage = SliceStorage::new(MAPPINGS);
+    assert_eq!(storage.entries(), MAPPINGS);
+    assert_eq!(storage.get("serde_json::Value"), Some("JsonValue"));
+    assert!(storage.contains_key("my_crate::*::Money"));
+    assert!(!storage.contains_key("serde_json"));
+    assert!(SliceStorage::<&str>::new(&[]).is_empty());
+}
+
+#[rstest]
+#[case::unsorted(&[("b::B", "B"), ("a::A", "A")])]
+#[case::duplicate(&[("a::A", "A"), ("a::A", "Other")])]
+#[should_panic(expected = "sorted and unique")]
+fn slice_storage_rejects_unsorted(#[case] entries: &'static [(&'static str, &'static str)]) {
+    SliceStorage::new(entries);
+}
+
+#[test]
+fn dynamic_resolver_from_map() {
+    let map: HashMap<String, String> = MAPPINGS
+        .iter()
+        .map(|(pattern, canonical)| (pattern.to_string(), canonical.to_string()))
+        .collect();
+
+    let mut resolver: DynamicPathResolver =
+        PathResolver::new(DynamicStorage::from(map.clone()), TypeGroups::ALL);
+    assert_eq!(resolver.storage().as_map(), &map);
+
+    resolver.add_mapping("my_crate::types::OrderId", "OrderId");
+    assert_eq!(resolver.storage().len(), map.len() + 1);
+    assert_eq!(
+        resolver.resolve(&parse_str("my_crate::types::OrderId").unwrap()),
+        Some("OrderId")
+    );
+}
@@ -0,0 +1,121 @@
+#![cfg(test)]
+
+use std::collections::HashMap;
+
+use desynt::{
+    DynamicPathResolver, IndexedStorage, MappingStorage, PathResolver, SuffixIndex, TypeGroups,
+};
+use phf::{Map, phf_map};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+static MAPPINGS: Map<&'static str, &'static str> = phf_map! {
+    "my_crate::types::UserId" => "UserId",
+    "other::UserId" => "OtherUserId",
+    "UserId" => "BareUserId",
+    "std::option::Option" => "MyOption",
+    "my_crate::Option" => "CrateOption",
+    "a::b::c::Deep" => "Deep",
+    "x::Deep" => "ShallowDeep",
+    "prefixUserId" => "Prefixed",
+    "<_ as Trait>::UserId" => "Projection",
+};
+
+fn sorted<'a>(keys: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut keys: Vec<&str> = keys.collect();
+    keys.sort_unstable();
+    keys
+}
+
+#[rstest]
+#[case::bare(
+    "UserId",
+    &["<_ as Trait>::UserId", "UserId", "my_crate::types::UserId", "other::UserId"]
+)]
+#[case::two_segments("types::UserId", &["my_crate::types::UserId"])]
+#[case::whole_key("my_crate::types::UserId", &["my_crate::types::UserId"])]
+#[case::partial_segment("Id", &[])]
+#[case::missing("Missing", &[])]
+fn keys_ending_with(#[case] suffix: &str, #[case] expected: &[&str]) {
+    let index = SuffixIndex::from_keys(MAPPINGS.keys().copied());
+    let mut expected = expected.to_vec();
+    expected.sort_unstable();
+
+    assert_eq!(sorted(index.keys_ending_with(suffix)), expected);
+    // The default scan of the phf map agrees with the index
+    assert_eq!(sorted(MAPPINGS.keys_ending_with(suffix)), expected);
+}
+
+#[test]
+fn insert_and_remove() {
+    let mut index = SuffixIndex::new();
+    assert!(index.is_empty());
+    assert!(index.insert("a::b::Type"));
+    assert!(index.insert("c::Type"));
+    assert!(!index.insert("c::Type"));
+    assert_eq!(index.len(), 2);
+
+    assert!(index.remove("a::b::Type"));
+    assert!(!index.remove("a::b::Type"));
+    assert!(!index.remove("b::Type"));
+    assert_eq!(sorted(index.keys_ending_with("Type")), ["c::Type"]);
+    assert_eq!(index, SuffixIndex::from_keys(["c::Type"]));
+
+    index.clear();
+    assert!(index.is_empty());
+    assert_eq!(index.keys_ending_with("Type").count(), 0);
+}
+
+#[test]
+fn dynamic_storage_keeps_index() {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping("a::Type", "A");
+    resolver.add_mapping("b::Type", "B");
+
+    let path: Path = parse_str("Type").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("A"));
+
+    resolver.set_priority("b::Type", 1);
+    assert_eq!(resolver.resolve(&path), Some("B"));
+
+    resolver.clear();
+    assert_eq!(resolver.resolve(&path), None);
+}
+
+#[rstest]
+#[case::bare("UserId")]
+#[case::bare_option("Option")]
+#[case::bare_deep("Deep")]
+#[case::shortened("collections::Option")]
+#[case::unknown("Unknown")]
+fn same_candidates_for_every_storage(#[case] input: &str) {
+    let path: Path = parse_str(input).unwrap();
+    let map: HashMap<String, String> = MAPPINGS
+        .entries()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+    let scanned = PathResolver::new(&MAPPINGS, TypeGroups::ALL);
+    let indexed = PathResolver::new(IndexedStorage::new(&MAPPINGS), TypeGroups::ALL);
+    let dynamic = DynamicPathResolver::from_map(map, TypeGroups::ALL);
+
+    assert_eq!(
+        indexed.resolve_candidates(&path),
+        scanned.resolve_candidates(&path)
+    );
+    assert_eq!(
+        dynamic.resolve_candidates(&path),
+        scanned.resolve_candidates(&path)
+    );
+    assert_eq!(indexed.resolve(&path), scanned.resolve(&path));
+    assert_eq!(dynamic.resolve(&path), scanned.resolve(&path));
+}
+
+#[test]
+fn indexed_storage_delegates() {
+    let storage = IndexedStorage::new(&MAPPINGS);
+    assert_eq!(storage.len(), MAPPINGS.len());
+    assert_eq!(storage.index().len(), MAPPINGS.len());
+    assert_eq!(storage.get("other::UserId"), Some("OtherUserId"));
+    assert!(std::ptr::eq(*storage.storage(), &MAPPINGS));
+}
@@ -0,0 +1,93 @@
+#![cfg(test)]
+
+use desynt::{DynamicPathResolver, EmptyStorage, PathResolver, TypeGroup, TypeGroups};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+const SYSTEM: TypeGroups = TypeGroups::NONE.with_group(TypeGroup::System);
+
+fn resolve(groups: TypeGroups, input: &str) -> Option<String> {
+    let resolver = PathResolver::new(EmptyStorage, groups);
+    let path: Path = parse_str(input).unwrap();
+    resolver.resolve(&path).map(str::to_string)
+}
+
+#[rstest]
+#[case::path_buf("std::path::PathBuf", "PathBuf")]
+#[case::path("std::path::Path", "Path")]
+#[case::os_string("std::ffi::OsString", "OsString")]
+#[case::os_str("std::ffi::OsStr", "OsStr")]
+#[case::c_string("std::ffi::CString", "CString")]
+#[case::alloc_c_string("alloc::ffi::CString", "CString")]
+#[case::c_str("std::ffi::CStr", "CStr")]
+#[case::core_c_str("core::ffi::CStr", "CStr")]
+#[case::ip_addr("std::net::IpAddr", "IpAddr")]
+#[case::core_ip_addr("core::net::IpAddr", "IpAddr")]
+#[case::ipv4_addr("core::net::Ipv4Addr", "Ipv4Addr")]
+#[case::ipv6_addr("std::net::Ipv6Addr", "Ipv6Addr")]
+#[case::socket_addr("core::net::SocketAddr", "SocketAddr")]
+#[case::duration("std::time::Duration", "Duration")]
+#[case::core_duration("core::time::Duration", "Duration")]
+#[case::instant("std::time::Instant", "Instant")]
+#[case::system_time("::std::time::SystemTime", "SystemTime")]
+fn full_paths(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(resolve(SYSTEM, input).as_deref(), Some(expected));
+    assert_eq!(resolve(TypeGroups::ALL, input).as_deref(), Some(expected));
+    assert_eq!(resolve(TypeGroups::PRELUDE, input), None);
+}
+
+#[rstest]
+#[case::bare("Duration", Some("Duration"))]
+#[case::bare_path("PathBuf", Some("PathBuf"))]
+#[case::time_module("time::Instant", Some("Instant"))]
+#[case::net_module("net::SocketAddr", Some("SocketAddr"))]
+#[case::foreign_path("syn::Path", None)]
+#[case::foreign_module("chrono::Duration", None)]
+fn shortened_paths(#[case] input: &str, #[case] expected: Option<&str>) {
+    assert_eq!(resolve(SYSTEM, input).as_deref(), expected);
+}
+
+#[rstest]
+#[case::tokio_instant("tokio::time::Instant")]
+#[case::tokio_duration("tokio::time::Duration")]
+#[case::async_std_path_buf("async_std::path::PathBuf")]
+#[case::local_duration("my::time::Duration")]
+#[case::tokio_net("tokio::net::SocketAddr")]
+#[case::local_ffi("my::ffi::CStr")]
+fn foreign_modules_with_std_names_are_not_std(#[case] input: &str) {
+    let resolver = DynamicPathResolver::with_all_groups();
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver.resolve(&path), None);
+}
+
+#[rstest]
+#[case::path_buf("core::path::PathBuf")]
+#[case::instant("core::time::Instant")]
+#[case::os_str("alloc::ffi::OsStr")]
+fn std_only_types_are_not_exact_under_core_or_alloc(#[case] input: &str) {
+    let resolver = PathResolver::new(EmptyStorage, SYSTEM);
+    let path: Path = parse_str(input).unwrap();
+    assert!(!resolver.has_mapping(&path));
+}
+
+#[test]
+fn bare_name_prefers_std() {
+    let resolver = PathResolver::new(EmptyStorage, SYSTEM);
+    let path: Path = parse_str("Duration").unwrap();
+    let resolution = resolver.resolve_detailed(&path).unwrap();
+    assert_eq!(resolution.pattern, "std::time::Duration");
+    assert_eq!(resolution.group, Some(TypeGroup::System));
+}
+
+#[test]
+fn toggle() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    assert!(resolver.uses_system());
+    let path: Path = parse_str("std::path::PathBuf").unwrap();
+    assert!(resolver.has_mapping(&path));
+
+    resolver.set_groups(TypeGroups::ALL.without_group(TypeGroup::System));
+    assert!(!resolver.uses_system());
+    assert_eq!(resolver.resolve(&path), None);
+    assert!(!resolver.canonical_types().any(|name| name == "PathBuf"));
+}
@@ -3,8 +3,9 @@
 
 use std::collections::HashMap;
 
-use desynt::{PathResolver, TypeGroups};
-use syn::parse_str;
+use desynt::{DynamicPathResolver, PathResolver, Scope, TypeGroups};
+use rstest::rstest;
+use syn::{File, ItemType, Path, parse_str};
 
 #[test]
 fn definitions_with_raw_identifiers() {
@@ -38,3 +39,153 @@ fn definitions_with_raw_identifiers() {
     assert_eq!(resolver.resolve(&usage2), Some("Vec"));
     assert_eq!(resolver.resolve(&usage3), Some("HashMap"));
 }
+
+#[test]
+fn add_type_aliases_with_raw_identifiers() {
+    // The same aliases as above, registered without writing the mappings by hand
+    let aliases: Vec<ItemType> = [
+        "type r#MyOption<T> = Option<T>;",
+        "type r#MyVec<T> = Vec<T>;",
+        "type r#MyHashMap<K, V> = std::collections::HashMap<K, V>;",
+    ]
+    .into_iter()
+    .map(|source| parse_str(source).unwrap())
+    .collect();
+
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_type_aliases(&aliases, &Scope::new());
+
+    assert_eq!(resolver.len(), 3);
+    for (usage, expected) in [
+        ("r#MyOption<String>", "Option"),
+        ("r#MyVec<i32>", "Vec"),
+        ("r#MyHashMap<String, bool>", "HashMap"),
+    ] {
+        let path: Path = parse_str(usage).unwrap();
+        assert_eq!(resolver.resolve(&path), Some(expected), "{usage}");
+    }
+}
+
+#[test]
+fn chained_aliases_in_any_order() {
+    let a: ItemType = parse_str("type A = B;").unwrap();
+    let b: ItemType = parse_str("type B = C<u8>;").unwrap();
+    let c: ItemType = parse_str("type C<T> = Vec<T>;").unwrap();
+
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_type_aliases([&a, &b, &c], &Scope::new());
+
+    for name in ["A", "B", "C"] {
+        let path: Path = parse_str(name).unwrap();
+        assert_eq!(resolver.resolve(&path), Some("Vec"), "{name}");
+    }
+}
+
+#[test]
+fn unresolvable_aliases_are_skipped() {
+    let cycle_a: ItemType = parse_str("type CycleA = CycleB;").unwrap();
+    let cycle_b: ItemType = parse_str("type CycleB = CycleA;").unwrap();
+    let tuple: ItemType = parse_str("type Pair = (u8, u8);").unwrap();
+    let unknown: ItemType = parse_str("type Unknown = other::Thing;").unwrap();
+    let known: ItemType = parse_str("type Known = String;").unwrap();
+
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_type_aliases(
+        [&cycle_a, &cycle_b, &tuple, &unknown, &known],
+        &Scope::new(),
+    );
+
+    assert_eq!(resolver.path_patterns().collect::<Vec<_>>(), vec!["Known"]);
+}
+
+#[test]
+fn aliases_use_scope_module_and_imports() {
+    let file: File = parse_str(
+        r#"
+        use crate::models::User as Model;
+
+        type Current = Model;
+        type Parent = super::Other;
+        "#,
+    )
+    .unwrap();
+    let scope = Scope::from_file(&file)
+        .with_module("my_crate::api")
+        .unwrap();
+    let aliases = file.items.iter().filter_map(|item| match item {
+        syn::Item::Type(item) => Some(item),
+        _ => None,
+    });
+
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_mapping("my_crate::models::User", "User");
+    resolver.add_mapping("my_crate::Other", "Other");
+    resolver.add_type_aliases(aliases, &scope);
+
+    for (pattern, canonical) in [
+        ("my_crate::api::Current", "User"),
+        ("my_crate::api::Parent", "Other"),
+    ] {
+        let path: Path = parse_str(pattern).unwrap();
+        assert!(resolver.has_mapping(&path), "{pattern}");
+        assert_eq!(resolver.resolve(&path), Some(canonical), "{pattern}");
+    }
+}
+
+#[rstest]
+#[case::root("my_crate::Registry", Some("HashMap"))]
+#[case::nested_super("my_crate::ids::UserId", Some("HashMap"))]
+#[case::nested_self("my_crate::ids::Ids", Some("Vec"))]
+#[case::sibling_module("my_crate::Latest", Some("Vec"))]
+#[case::bare_name("UserId", Some("HashMap"))]
+#[case::other_crate("other_crate::UserId", None)]
+fn add_type_aliases_from_file(#[case] input: &str, #[case] expected: Option<&str>) {
+    let file: File = parse_str(
+        r#"
+        use std::collections::HashMap as Map;
+
+        pub type Registry = Map<String, u32>;
+        pub type Latest = ids::Ids;
+
+        pub mod ids {
+            pub type UserId = super::Registry;
+            pub type Ids = self::r#List;
+            type r#List = Vec<UserId>;
+        }
+        "#,
+    )
+    .unwrap();
+
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver
+        .add_type_aliases_from_file(&file, "my_crate")
+        .unwrap();
+
+    let path: Path = parse_str(input).unwrap();
+    assert_eq!(resolver.resolve(&path), expected);
+}
+
+#[test]
+fn add_type_aliases_from_file_without_module() {
+    let file: File = parse_str("type r#Id = u64; mod inner { type Inner = u8; }").unwrap();
+
+    let mut resolver = DynamicPathResolver::with_primitives();
+    resolver.add_type_aliases_from_file(&file, "").unwrap();
+
+    let mut patterns: Vec<&str> = resolver.path_patterns().collect();
+    patterns.sort_unstable();
+    assert_eq!(patterns, vec!["Id", "Inner"]);
+}
+
+#[test]
+fn add_type_aliases_from_file_rejects_invalid_module() {
+    let file: File = parse_str("type Id = u64;").unwrap();
+
+    let mut resolver = DynamicPathResolver::with_primitives();
+    assert!(
+        resolver
+            .add_type_aliases_from_file(&file, "my-crate")
+            .is_err()
+    );
+    assert_eq!(resolver.len(), 0);
+}
@@ -0,0 +1,237 @@
+#![cfg(test)]
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+use desynt::{
+    DynamicPathResolver, IndexedStorage, MappingValue, PathResolver, Resolution, Strategy,
+    TypeGroup, TypeGroups, ValueMap,
+};
+use phf::{Map, phf_map};
+use rstest::rstest;
+use syn::{Path, parse_str};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Column {
+    name: &'static str,
+    sql: &'static str,
+}
+
+impl MappingValue for Column {
+    fn canonical(&self) -> &str {
+        self.name
+    }
+}
+
+const fn column(name: &'static str, sql: &'static str) -> Column {
+    Column { name, sql }
+}
+
+static COLUMNS: Map<&'static str, Column> = phf_map! {
+    "uuid::Uuid" => column("Uuid", "UUID"),
+    "chrono::DateTime" => column("DateTime", "TIMESTAMPTZ"),
+    "my_crate::*::Money" => column("Money", "NUMERIC(19, 4)"),
+    "Vec<u8>" => column("Bytes", "BYTEA"),
+};
+
+/// The SQL type of builtin types, for matches without a stored column.
+fn builtin_column(resolution: &Resolution<'_>) -> Option<Column> {
+    match resolution.canonical {
+        "i64" => Some(column("i64", "BIGINT")),
+        "String" => Some(column("String", "TEXT")),
+        _ => None,
+    }
+}
+
+#[rstest]
+#[case::exact("uuid::Uuid", Some(("Uuid", "UUID")))]
+#[case::suffix("DateTime<Utc>", Some(("DateTime", "TIMESTAMPTZ")))]
+#[case::glob("my_crate::billing::Money", Some(("Money", "NUMERIC(19, 4)")))]
+#[case::generic_pattern("Vec<u8>", Some(("Bytes", "BYTEA")))]
+#[case::builtin("std::string::String", None)]
+#[case::miss("my_crate::Unknown", None)]
+fn resolves_stored_values(
+    #[case] input: &str,
+    #[case] expected: Option<(&'static str, &'static str)>,
+) {
+    let path: Path = parse_str(input).unwrap();
+    let expected = expected.map(|(name, sql)| column(name, sql));
+
+    let static_resolver = PathResolver::new(&COLUMNS, TypeGroups::ALL);
+    assert_eq!(static_resolver.resolve_value(&path), expected.as_ref());
+
+    let columns: HashMap<String, Column> = COLUMNS
+        .entries()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect();
+    let dynamic_resolver = PathResolver::new(columns, TypeGroups::ALL);
+    assert_eq!(dynamic_resolver.resolve_value(&path), expected.as_ref());
+
+    let indexed = PathResolver::new(IndexedStorage::new(&COLUMNS), TypeGroups::ALL);
+    assert_eq!(indexed.resolve_value(&path), expected.as_ref());
+}
+
+#[rstest]
+#[case::stored("uuid::Uuid", Some(("Uuid", "UUID")), false)]
+#[case::builtin_converted("std::string::String", Some(("String", "TEXT")), true)]
+#[case::primitive_converted("i64", Some(("i64", "BIGINT")), true)]
+#[case::builtin_unconverted("bool", None, true)]
+#[case::miss("my_crate::Unknown", None, false)]
+fn converts_builtin_matches(
+    #[case] input: &str,
+    #[case] expected: Option<(&'static str, &'static str)>,
+    #[case] converted: bool,
+) {
+    let resolver = PathResolver::new(&COLUMNS, TypeGroups::ALL);
+    let path: Path = parse_str(input).unwrap();
+
+    let mut called = false;
+    let value = resolver.resolve_value_with(&path, |resolution| {
+        called = true;
+        builtin_column(resolution)
+    });
+    assert_eq!(called, converted);
+    assert_eq!(
+        value.as_deref(),
+        expected.map(|(name, sql)| column(name, sql)).as_ref()
+    );
+    if let Some(value) = value {
+        assert_eq!(matches!(value, Cow::Owned(_)), converted);
+    }
+}
+
+#[test]
+fn conversion_receives_resolution() {
+    let resolver = PathResolver::new(&COLUMNS, TypeGroups::ALL);
+    let path: Path = parse_str("Option<i64>").unwrap();
+
+    let value = resolver.resolve_value_with(&path, |resolution| {
+        assert_eq!(resolution.group, Some(TypeGroup::Prelude));
+        assert_eq!(resolution.strategy, Strategy::BuiltinPattern);
+        Some(column("Option", "NULL"))
+    });
+    assert_eq!(value.unwrap().sql, "NULL");
+}
+
+#[test]
+fn string_storages_return_canonical_names() {
+    let mut resolver = DynamicPathResolver::with_all_groups();
+    resolver.add_mapping("my_crate::types::UserId", "UserId");
+
+    let path: Path = parse_str("my_crate::types::UserId").unwrap();
+    assert_eq!(
+        resolver.resolve_value(&path).map(String::as_str),
+        Some("UserId")
+    );
+
+    let path: Path = parse_str("u32").unwrap();
+    assert_eq!(resolver.resolve_value(&path), None);
+    let value = resolver.resolve_value_with(&path, |resolution| {
+        Some(resolution.canonical.to_uppercase())
+    });
+    assert_eq!(value.as_deref().map(String::as_str), Some("U32"));
+}
+
+#[test]
+fn matcher_matches_are_converted() {
+    let mut resolver = DynamicPathResolver::default();
+    resolver.add_matcher("ids", |segments: &[&str]| {
+        segments
+            .last()
+            .filter(|name| name.ends_with("Id"))
+            .map(|_| "Identifier")
+    });
+
+    let path: Path = parse_str("my_crate::OrderId").unwrap();
+    assert_eq!(resolver.resolve_value(&path), None);
+    let value = resolver.resolve_value_with(&path, |resolution| {
+        Some(format!("matched {}", resolution.pattern))
+    });
+    assert_eq!(value.as_deref().map(String::as_str), Some("matched ids"));
+}
+
+#[test]
+fn canonical_names_come_from_values() {
+    let resolver = PathResolver::new(&COLUMNS, TypeGroups::NONE);
+    let path: Path = parse_str("chrono::DateTime<Utc>").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("DateTime"));
+
+    let mut canonical: Vec<&str> = resolver.canonical_types().collect();
+    canonical.sort_unstable();
+    assert_eq!(canonical, ["Bytes", "DateTime", "Money", "Uuid"]);
+}
+
+/// A value that does not name a canonical type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SqlType {
+    Uuid,
+    Timestamp,
+    Numeric,
+    Bytea,
+}
+
+static SQL_TYPES: ValueMap<Map<&'static str, SqlType>> = ValueMap(phf_map! {
+    "uuid::Uuid" => SqlType::Uuid,
+    "chrono::DateTime" => SqlType::Timestamp,
+    "my_crate::*::Money" => SqlType::Numeric,
+    "Vec<u8>" => SqlType::Bytea,
+});
+
+#[rstest]
+#[case::exact("uuid::Uuid", Some(("Uuid", SqlType::Uuid)))]
+#[case::suffix("DateTime<Utc>", Some(("DateTime", SqlType::Timestamp)))]
+#[case::glob("my_crate::billing::Money", Some(("Money", SqlType::Numeric)))]
+#[case::generic_pattern("Vec<u8>", Some(("Vec", SqlType::Bytea)))]
+#[case::builtin("std::string::String", None)]
+#[case::miss("my_crate::Unknown", None)]
+fn value_maps_hold_any_value(#[case] input: &str, #[case] expected: Option<(&str, SqlType)>) {
+    let path: Path = parse_str(input).unwrap();
+    let expected_value = expected.map(|(_, value)| value);
+
+    let static_resolver = PathResolver::new(&SQL_TYPES, TypeGroups::ALL);
+    assert_eq!(
+        static_resolver.resolve_value(&path),
+        expected_value.as_ref()
+    );
+    if let Some((canonical, _)) = expected {
+        assert_eq!(static_resolver.resolve(&path), Some(canonical));
+    }
+
+    let hash_map: HashMap<String, SqlType> = SQL_TYPES
+        .0
+        .entries()
+        .map(|(key, value)| (key.to_string(), *value))
+        .collect();
+    let dynamic_resolver = PathResolver::new(ValueMap(hash_map), TypeGroups::ALL);
+    assert_eq!(
+        dynamic_resolver.resolve_value(&path),
+        expected_value.as_ref()
+    );
+
+    let b_tree_map: BTreeMap<&str, SqlType> = SQL_TYPES
+        .0
+        .entries()
+        .map(|(key, value)| (*key, *value))
+        .collect();
+    let ordered_resolver = PathResolver::new(ValueMap(b_tree_map), TypeGroups::ALL);
+    assert_eq!(
+        ordered_resolver.resolve_value(&path),
+        expected_value.as_ref()
+    );
+}
+
+#[test]
+fn value_maps_name_mappings_after_patterns() {
+    let mut sql_types = BTreeMap::new();
+    sql_types.insert("my_crate::models::*", SqlType::Numeric);
+    sql_types.insert("<_ as Config>::AccountId", SqlType::Bytea);
+    sql_types.insert("uuid::Uuid", SqlType::Uuid);
+    let resolver = PathResolver::new(ValueMap(sql_types), TypeGroups::NONE);
+
+    let canonical: Vec<&str> = resolver.canonical_types().collect();
+    assert_eq!(canonical, ["AccountId", "my_crate::models::*", "Uuid"]);
+
+    let path: Path = parse_str("my_crate::models::Price").unwrap();
+    assert_eq!(resolver.resolve(&path), Some("my_crate::models::*"));
+    assert_eq!(resolver.resolve_value(&path), Some(&SqlType::Numeric));
+}